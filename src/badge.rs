@@ -0,0 +1,144 @@
+use jiff::civil::Date;
+use serde_json::json;
+
+use crate::models::{store::Store, task::When};
+
+/// Output formats for `tdo badge`, one per kind of consumer
+#[derive(Clone, Copy, Debug, Default, clap::ValueEnum)]
+pub enum BadgeFormat {
+    /// Just the number, always printed
+    #[default]
+    Plain,
+    /// Dock badge label: the number, or an empty line to clear the badge
+    Macos,
+    /// LauncherEntry properties (`count`, `count-visible`) as JSON
+    Gnome,
+    /// Taskbar overlay text: the number capped at "99+", or an empty line
+    Windows,
+}
+
+/// Overlay icons only fit a couple of digits
+const WINDOWS_BADGE_MAX: usize = 99;
+
+/// Open tasks the Today view would show, split the same way
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BadgeCount {
+    /// Tasks in Today, including the evening
+    pub today: usize,
+    /// Tasks scheduled for a day that has passed
+    pub overdue: usize,
+}
+
+impl BadgeCount {
+    /// Count in a single pass over the active tasks, without sorting or
+    /// rendering anything, so status bars can poll it often
+    pub fn from_store(store: &Store, today: Date) -> Self {
+        let mut count = BadgeCount::default();
+        for task in store.get_active_tasks() {
+            if task.completed_at.is_some() || task.is_deferred() || store.is_task_on_hold(task) {
+                continue;
+            }
+            if task.when.is_today(today) {
+                count.today += 1;
+            } else if let When::Scheduled { date, .. } = task.when
+                && date < today
+            {
+                count.overdue += 1;
+            }
+        }
+        count
+    }
+
+    pub fn total(&self) -> usize {
+        self.today + self.overdue
+    }
+
+    pub fn render(&self, format: BadgeFormat) -> String {
+        let total = self.total();
+        match format {
+            BadgeFormat::Plain => total.to_string(),
+            BadgeFormat::Macos if total == 0 => String::new(),
+            BadgeFormat::Macos => total.to_string(),
+            BadgeFormat::Gnome => json!({
+                "count": total,
+                "count-visible": total > 0,
+                "today": self.today,
+                "overdue": self.overdue,
+            })
+            .to_string(),
+            BadgeFormat::Windows if total == 0 => String::new(),
+            BadgeFormat::Windows if total > WINDOWS_BADGE_MAX => format!("{}+", WINDOWS_BADGE_MAX),
+            BadgeFormat::Windows => total.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::Task;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_counts_today_and_overdue_open_tasks() {
+        let today = date("2025-03-10");
+        let mut store = Store::default();
+        for when in [
+            When::Today { evening: false },
+            When::Today { evening: true },
+            When::Scheduled {
+                date: date("2025-03-08"),
+                evening: false,
+            },
+            When::Scheduled {
+                date: today,
+                evening: true,
+            },
+            When::Anytime,
+        ] {
+            store.add_task(Task {
+                id: uuid::Uuid::new_v4(),
+                when,
+                ..Task::default()
+            });
+        }
+        store.add_task(Task {
+            id: uuid::Uuid::new_v4(),
+            when: When::Today { evening: false },
+            completed_at: Some(jiff::Timestamp::now()),
+            ..Task::default()
+        });
+
+        let count = BadgeCount::from_store(&store, today);
+        assert_eq!(
+            count,
+            BadgeCount {
+                today: 3,
+                overdue: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let empty = BadgeCount::default();
+        assert_eq!(empty.render(BadgeFormat::Plain), "0");
+        assert_eq!(empty.render(BadgeFormat::Macos), "");
+        assert_eq!(empty.render(BadgeFormat::Windows), "");
+
+        let busy = BadgeCount {
+            today: 100,
+            overdue: 4,
+        };
+        assert_eq!(busy.render(BadgeFormat::Macos), "104");
+        assert_eq!(busy.render(BadgeFormat::Windows), "99+");
+
+        let gnome: serde_json::Value =
+            serde_json::from_str(&busy.render(BadgeFormat::Gnome)).unwrap();
+        assert_eq!(gnome["count"], 104);
+        assert_eq!(gnome["count-visible"], true);
+    }
+}
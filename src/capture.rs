@@ -0,0 +1,109 @@
+/// Fields pulled out of a quick-capture title such as
+/// `Review PR #work @deep-work +Launch //friday !2025-06-01`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Capture {
+    /// The words that aren't tokens, in order
+    pub title: String,
+    /// `#tag`, repeatable
+    pub tags: Vec<String>,
+    /// `@context`
+    pub context: Option<String>,
+    /// `+project`, matched like `--project`
+    pub project: Option<String>,
+    /// `//when`, in any format `--when` accepts
+    pub when: Option<String>,
+    /// `!deadline`, a date or an offset like `!+3d`
+    pub deadline: Option<String>,
+}
+
+/// Split inline tokens out of a task title. A token is a whole word starting
+/// with its marker and followed by something; anything else stays in the title,
+/// so "Fix #123", "a + b" and "Hello!" are left alone. A leading backslash keeps
+/// a word in the title as written, e.g. `\#hashtag`. When a token appears more
+/// than once the last one wins, except tags which accumulate.
+pub fn parse_capture(input: &str) -> Capture {
+    let mut capture = Capture::default();
+    let mut words = vec![];
+
+    for word in input.split_whitespace() {
+        if let Some(escaped) = word.strip_prefix('\\') {
+            words.push(escaped);
+            continue;
+        }
+
+        if let Some(when) = token(word, "//") {
+            capture.when = Some(when.to_string());
+        } else if let Some(tag) = token(word, "#").filter(|tag| !is_number(tag)) {
+            capture.tags.push(tag.to_string());
+        } else if let Some(context) = token(word, "@") {
+            capture.context = Some(context.to_string());
+        } else if let Some(project) = token(word, "+").filter(|p| !p.starts_with(char::is_numeric))
+        {
+            capture.project = Some(project.to_string());
+        } else if let Some(deadline) =
+            token(word, "!").filter(|d| d.starts_with(|c: char| c.is_ascii_digit() || c == '+'))
+        {
+            capture.deadline = Some(deadline.to_string());
+        } else {
+            words.push(word);
+        }
+    }
+
+    capture.title = words.join(" ");
+    capture
+}
+
+/// The rest of `word` after `marker`, if there is any
+fn token<'a>(word: &'a str, marker: &str) -> Option<&'a str> {
+    word.strip_prefix(marker).filter(|rest| !rest.is_empty())
+}
+
+/// Issue and ticket references like "#123" aren't tags
+fn is_number(text: &str) -> bool {
+    text.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_capture_tokens() {
+        assert_eq!(
+            parse_capture("Review PR #work @deep-work //friday !2025-06-01 +Launch #urgent"),
+            Capture {
+                title: "Review PR".to_string(),
+                tags: vec!["work".to_string(), "urgent".to_string()],
+                context: Some("deep-work".to_string()),
+                project: Some("Launch".to_string()),
+                when: Some("friday".to_string()),
+                deadline: Some("2025-06-01".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_capture_keeps_plain_words() {
+        let capture = parse_capture("Fix #123 so a + b works! Call at 5 ! +1 later");
+        assert_eq!(
+            capture.title,
+            "Fix #123 so a + b works! Call at 5 ! +1 later"
+        );
+        assert_eq!(
+            capture,
+            Capture {
+                title: capture.title.clone(),
+                ..Capture::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_capture_offsets_and_escapes() {
+        let capture = parse_capture(r"Post \#throwback photo !+3d //tomorrow");
+        assert_eq!(capture.title, "Post #throwback photo");
+        assert!(capture.tags.is_empty());
+        assert_eq!(capture.deadline.as_deref(), Some("+3d"));
+        assert_eq!(capture.when.as_deref(), Some("tomorrow"));
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{hooks::CompletionFeedback, ui::Palette, workspace::Workspace};
+
+/// User preferences, read from `config.json` next to the store.
+/// Every field is optional in the file and falls back to its default.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Show the Evening section before the regular Today tasks
+    pub evening_first: bool,
+
+    /// Fetch the page title of links added with a task (same as `--fetch-title`)
+    pub fetch_url_titles: bool,
+
+    /// Move open tasks to Today once their deadline is this many days away (or closer)
+    pub promote_deadline_days: Option<u32>,
+
+    /// Nudge to process the Inbox once it holds more than this many tasks
+    pub inbox_limit: Option<usize>,
+
+    /// End task views with a summary of projects, tags and the nearest deadline
+    pub show_footer: bool,
+
+    /// Aging rules applied by `tdo groom`
+    pub groom_rules: Vec<GroomRule>,
+
+    /// Leave Saturdays and Sundays free when running `tdo autoschedule`
+    pub autoschedule_skip_weekends: bool,
+
+    /// Ask to type the name before deleting a project or area that would take
+    /// more than this many tasks with it; `null` turns the check off
+    pub confirm_cascade_over: Option<usize>,
+
+    /// Colors for overdue tasks and other status cues: "default", or
+    /// "deuteranopia" / "protanopia" for palettes that don't rely on red vs green
+    pub palette: Palette,
+
+    /// What happens after `tdo done`: "streak" prints the days-in-a-row count,
+    /// "bell" rings the terminal bell, "off" does nothing, and
+    /// `{"command": "..."}` runs a `task-completed` hook
+    pub completion_feedback: CompletionFeedback,
+
+    /// Named workspaces for `--workspace`, e.g.
+    /// `{"work": {"areas": ["Work"], "tags": ["work"]}}`
+    pub workspaces: BTreeMap<String, Workspace>,
+
+    /// Show the age (e.g. `·14d`) of Inbox and Anytime tasks left untouched for
+    /// at least this many days; `null` turns the marker off
+    pub stale_after_days: Option<u32>,
+
+    /// Warn at the top of Today about projects due within this many days,
+    /// or already overdue; `null` turns the warning off
+    pub project_deadline_warning_days: Option<u32>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            evening_first: false,
+            fetch_url_titles: false,
+            promote_deadline_days: None,
+            inbox_limit: None,
+            show_footer: false,
+            groom_rules: vec![],
+            autoschedule_skip_weekends: false,
+            confirm_cascade_over: Some(DEFAULT_CONFIRM_CASCADE_OVER),
+            palette: Palette::Default,
+            completion_feedback: CompletionFeedback::Streak,
+            workspaces: BTreeMap::new(),
+            stale_after_days: Some(DEFAULT_STALE_AFTER_DAYS),
+            project_deadline_warning_days: Some(DEFAULT_PROJECT_DEADLINE_WARNING_DAYS),
+        }
+    }
+}
+
+/// Deleting a container with more tasks than this asks for confirmation by default
+const DEFAULT_CONFIRM_CASCADE_OVER: usize = 10;
+
+/// Open Inbox and Anytime tasks show their age after this many days without changes
+const DEFAULT_STALE_AFTER_DAYS: u32 = 14;
+
+/// Projects due within this many days are listed at the top of Today
+const DEFAULT_PROJECT_DEADLINE_WARNING_DAYS: u32 = 7;
+
+/// Lists a grooming rule can match and move tasks between
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GroomList {
+    Inbox,
+    Anytime,
+    Someday,
+}
+
+/// "Tasks in `list` created more than `older_than_days` ago get `add_tag` and/or move to `move_to`"
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GroomRule {
+    pub list: GroomList,
+    pub older_than_days: u32,
+    #[serde(default)]
+    pub add_tag: Option<String>,
+    #[serde(default)]
+    pub move_to: Option<GroomList>,
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read config from '{path}': {source}")]
+    LoadFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse config from '{path}': {source}")]
+    ParseFailed {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+impl Config {
+    /// Load the config file, using defaults when it doesn't exist
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| ConfigError::ParseFailed {
+                path: path.to_path_buf(),
+                source: e,
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError::LoadFailed {
+                path: path.to_path_buf(),
+                source: e,
+            }),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use jiff::civil::Date;
+
+use crate::models::{area::Area, store::Store, task::When};
+
+/// Days, today included, whose deadlines count as due this week
+const DUE_THIS_WEEK_DAYS: i64 = 7;
+
+/// One row of `tdo dashboard`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AreaSummary {
+    /// Projects counted the way `area list` counts them
+    pub projects: usize,
+    /// Open tasks the Today view would show, including the evening
+    pub today: usize,
+    /// Open tasks scheduled for a day that has passed
+    pub overdue: usize,
+    /// Open tasks and projects with a deadline in the next seven days
+    pub due_this_week: usize,
+}
+
+impl AreaSummary {
+    /// Count one area's open projects and tasks. Deferred tasks and ones in
+    /// waiting, shelved or archived projects are left out, as in the Today view.
+    pub fn from_store(store: &Store, area: &Area, today: Date) -> Self {
+        let last_day = today.saturating_add(jiff::Span::new().days(DUE_THIS_WEEK_DAYS - 1));
+        let is_due_this_week =
+            |deadline: Option<Date>| deadline.is_some_and(|date| date >= today && date <= last_day);
+        let mut summary = AreaSummary::default();
+
+        for project in store.get_projects_for_area(area.id) {
+            if project.deleted_at.is_some()
+                || project.completed_at.is_some()
+                || project.incubated_at.is_some()
+                || project.archived_at.is_some()
+            {
+                continue;
+            }
+            summary.projects += 1;
+            if is_due_this_week(project.deadline) {
+                summary.due_this_week += 1;
+            }
+        }
+
+        for task in store.get_tasks_in_area(area.id) {
+            if task.deleted_at.is_some()
+                || task.completed_at.is_some()
+                || task.is_deferred()
+                || store.is_task_on_hold(task)
+            {
+                continue;
+            }
+            if task.when.is_today(today) {
+                summary.today += 1;
+            } else if let When::Scheduled { date, .. } = task.when
+                && date < today
+            {
+                summary.overdue += 1;
+            }
+            if is_due_this_week(task.deadline) {
+                summary.due_this_week += 1;
+            }
+        }
+
+        summary
+    }
+
+    /// Whether there's nothing to look at in the area today
+    pub fn is_quiet(&self) -> bool {
+        self.today == 0 && self.overdue == 0 && self.due_this_week == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{project::Project, task::Task};
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_summary_counts_area_tasks_and_projects() {
+        let today = date("2025-03-10");
+        let mut store = Store::default();
+        let area = Area {
+            id: uuid::Uuid::new_v4(),
+            name: "Work".to_string(),
+            ..Area::default()
+        };
+        let launch = Project {
+            id: uuid::Uuid::new_v4(),
+            area_id: Some(area.id),
+            deadline: Some(date("2025-03-16")),
+            ..Project::default()
+        };
+        let shelved = Project {
+            id: uuid::Uuid::new_v4(),
+            area_id: Some(area.id),
+            incubated_at: Some(jiff::Timestamp::now()),
+            ..Project::default()
+        };
+        let (area_id, launch_id, shelved_id) = (area.id, launch.id, shelved.id);
+        store.add_area(area);
+        store.add_project(launch);
+        store.add_project(shelved);
+
+        for (project_id, area_id, when, deadline) in [
+            (Some(launch_id), None, When::Today { evening: true }, None),
+            (
+                None,
+                Some(area_id),
+                When::Scheduled {
+                    date: date("2025-03-08"),
+                    evening: false,
+                },
+                Some(date("2025-03-09")),
+            ),
+            (None, Some(area_id), When::Anytime, Some(date("2025-03-12"))),
+            (None, Some(area_id), When::Anytime, Some(date("2025-03-17"))),
+            // On hold with its shelved project
+            (Some(shelved_id), None, When::Today { evening: false }, None),
+            // Not in the area
+            (None, None, When::Today { evening: false }, None),
+        ] {
+            store.add_task(Task {
+                id: uuid::Uuid::new_v4(),
+                project_id,
+                area_id,
+                when,
+                deadline,
+                ..Task::default()
+            });
+        }
+
+        let area = store.get_area(area_id).unwrap();
+        let summary = AreaSummary::from_store(&store, area, today);
+        assert_eq!(
+            summary,
+            AreaSummary {
+                projects: 1,
+                today: 1,
+                overdue: 1,
+                due_this_week: 2,
+            }
+        );
+        assert!(!summary.is_quiet());
+    }
+
+    #[test]
+    fn test_summary_leaves_out_deferred_and_on_hold_tasks() {
+        let today = date("2025-03-10");
+        let mut store = Store::default();
+        let area = Area {
+            id: uuid::Uuid::new_v4(),
+            ..Area::default()
+        };
+        let blocker = Project {
+            id: uuid::Uuid::new_v4(),
+            ..Project::default()
+        };
+        let waiting = Project {
+            id: uuid::Uuid::new_v4(),
+            area_id: Some(area.id),
+            blocked_by: vec![blocker.id],
+            ..Project::default()
+        };
+        let archived = Project {
+            id: uuid::Uuid::new_v4(),
+            area_id: Some(area.id),
+            archived_at: Some(jiff::Timestamp::now()),
+            deadline: Some(today),
+            ..Project::default()
+        };
+        let (area_id, waiting_id, archived_id) = (area.id, waiting.id, archived.id);
+        store.add_area(area);
+        store.add_project(blocker);
+        store.add_project(waiting);
+        store.add_project(archived);
+
+        let overdue = When::Scheduled {
+            date: date("2025-03-01"),
+            evening: false,
+        };
+        for (project_id, area_id, when, defer_until) in [
+            // Deferred well past any real date, so it stays deferred
+            (
+                None,
+                Some(area_id),
+                When::Today { evening: false },
+                Some(date("2999-01-01")),
+            ),
+            (Some(waiting_id), None, When::Today { evening: false }, None),
+            (Some(archived_id), None, overdue, None),
+        ] {
+            store.add_task(Task {
+                id: uuid::Uuid::new_v4(),
+                project_id,
+                area_id,
+                when,
+                defer_until,
+                deadline: Some(today),
+                ..Task::default()
+            });
+        }
+
+        let area = store.get_area(area_id).unwrap();
+        let summary = AreaSummary::from_store(&store, area, today);
+        // The waiting project still counts as a project, not its task
+        assert_eq!(
+            summary,
+            AreaSummary {
+                projects: 1,
+                ..AreaSummary::default()
+            }
+        );
+        assert!(summary.is_quiet());
+    }
+
+    #[test]
+    fn test_summary_due_this_week_window() {
+        let today = date("2025-03-10");
+        let mut store = Store::default();
+        let area = Area {
+            id: uuid::Uuid::new_v4(),
+            ..Area::default()
+        };
+        let area_id = area.id;
+        store.add_area(area);
+
+        // Yesterday and a week from today fall outside; today and six days on count
+        for deadline in ["2025-03-09", "2025-03-10", "2025-03-16", "2025-03-17"] {
+            store.add_task(Task {
+                id: uuid::Uuid::new_v4(),
+                area_id: Some(area_id),
+                when: When::Anytime,
+                deadline: Some(date(deadline)),
+                ..Task::default()
+            });
+        }
+
+        let area = store.get_area(area_id).unwrap();
+        assert_eq!(
+            AreaSummary::from_store(&store, area, today).due_this_week,
+            2
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::{fs, process::Command};
+
+use thiserror::Error;
+
+/// Used when neither `$VISUAL` nor `$EDITOR` is set
+const FALLBACK_EDITOR: &str = "vi";
+
+#[derive(Error, Debug)]
+pub enum EditorError {
+    #[error("Failed to prepare the file to edit: {0}")]
+    TempFile(#[source] std::io::Error),
+
+    #[error("Failed to start editor '{editor}': {source}")]
+    Launch {
+        editor: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Editor '{0}' exited with an error; nothing was changed")]
+    Failed(String),
+}
+
+/// The editor command from `$VISUAL` or `$EDITOR`, e.g. "vim" or "code --wait"
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string())
+}
+
+/// Open `initial` in the user's editor and return the text once it is closed
+pub fn edit_text(initial: &str) -> Result<String, EditorError> {
+    let path = std::env::temp_dir().join(format!("tdo-notes-{}.md", std::process::id()));
+    // Editors expect text files to end with a newline
+    let content = if initial.is_empty() || initial.ends_with('\n') {
+        initial.to_string()
+    } else {
+        format!("{}\n", initial)
+    };
+    fs::write(&path, content).map_err(EditorError::TempFile)?;
+
+    let editor = editor_command();
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or(FALLBACK_EDITOR);
+
+    let status = Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .map_err(|e| EditorError::Launch {
+            editor: editor.clone(),
+            source: e,
+        });
+
+    let result = match status {
+        Ok(status) if status.success() => fs::read_to_string(&path).map_err(EditorError::TempFile),
+        Ok(_) => Err(EditorError::Failed(editor)),
+        Err(e) => Err(e),
+    };
+
+    let _ = fs::remove_file(&path);
+    result
+}
@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+
+use jiff::civil::Date;
+
+use crate::{
+    models::{
+        project::Project,
+        store::Store,
+        task::{Task, When},
+    },
+    ui,
+};
+
+/// Supported export formats
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Markdown document
+    Md,
+    /// Standalone HTML page, styled for printing
+    Html,
+}
+
+/// Heading used for completed tasks that belong to no project or area
+const UNASSIGNED_HEADING: &str = "Unassigned";
+
+/// Render completed tasks as a changelog-style document grouped by project
+pub fn export_logbook(format: ExportFormat, title: &str, tasks: &[&Task], store: &Store) -> String {
+    let sections = group_completed_tasks(tasks, store);
+    let summary = format!(
+        "{} completed {}",
+        tasks.len(),
+        if tasks.len() == 1 { "task" } else { "tasks" }
+    );
+
+    match format {
+        ExportFormat::Md => {
+            let mut output = format!("# {}\n\n_{}_\n", title, summary);
+            for (heading, section) in sections {
+                output.push_str(&format!("\n## {}\n\n", heading));
+                for task in section {
+                    output.push_str(&format!("- [x] {} ({})\n", task.title, completed_on(task)));
+                }
+            }
+            output
+        }
+        ExportFormat::Html => {
+            let mut body = format!("<p class=\"meta\">{}</p>\n", summary);
+            for (heading, section) in sections {
+                body.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape_html(&heading)));
+                for task in section {
+                    body.push_str(&format!(
+                        "<li class=\"done\"><span class=\"box\">☑</span> {} <span class=\"meta\">{}</span></li>\n",
+                        escape_html(&task.title),
+                        completed_on(task)
+                    ));
+                }
+                body.push_str("</ul>\n");
+            }
+            html_page(title, &body)
+        }
+    }
+}
+
+/// Completed tasks grouped by project or area, unassigned last, oldest first within a group
+fn group_completed_tasks<'a>(tasks: &[&'a Task], store: &Store) -> Vec<(String, Vec<&'a Task>)> {
+    let mut grouped: BTreeMap<String, Vec<&Task>> = BTreeMap::new();
+
+    for task in tasks {
+        let heading =
+            ui::get_task_context(task, store).unwrap_or_else(|| UNASSIGNED_HEADING.to_string());
+        grouped.entry(heading).or_default().push(task);
+    }
+
+    // Unassigned tasks go last, everything else alphabetically
+    let mut sections: Vec<_> = grouped.into_iter().collect();
+    sections.sort_by_key(|(h, _)| (h == UNASSIGNED_HEADING, h.to_lowercase()));
+    for (_, section) in &mut sections {
+        section.sort_by_key(|t| t.completed_at);
+    }
+
+    sections
+}
+
+fn completed_on(task: &Task) -> String {
+    task.completed_at
+        .map(|ts| ui::get_local_date(ts).to_string())
+        .unwrap_or_default()
+}
+
+/// Render a project's open tasks as an agenda, one section per schedule
+pub fn export_project(format: ExportFormat, project: &Project, store: &Store) -> String {
+    let mut tasks: Vec<_> = store
+        .get_tasks_for_project(project.id)
+        .filter(|t| t.completed_at.is_none() && t.deleted_at.is_none())
+        .collect();
+    tasks.sort_by_key(|t| t.task_number);
+
+    let sections = PROJECT_SECTIONS.iter().filter_map(|name| {
+        let section: Vec<&Task> = tasks
+            .iter()
+            .copied()
+            .filter(|t| project_section(t) == *name)
+            .collect();
+        (!section.is_empty()).then_some((*name, section))
+    });
+
+    let mut meta = vec![];
+    if let Some(area) = project.area_id.and_then(|id| store.get_area(id)) {
+        meta.push(format!("Area: {}", area.name));
+    }
+    if let Some(deadline) = project.deadline {
+        meta.push(format!("Deadline: {}", deadline));
+    }
+    if !project.tags.is_empty() {
+        meta.push(
+            project
+                .tags
+                .iter()
+                .map(|t| format!("#{}", t))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+    }
+    meta.push(format!(
+        "{} open {}",
+        tasks.len(),
+        if tasks.len() == 1 { "task" } else { "tasks" }
+    ));
+
+    match format {
+        ExportFormat::Md => {
+            let mut output = format!("# {}\n\n_{}_\n", project.name, meta.join(" · "));
+            if let Some(notes) = &project.notes {
+                output.push_str(&format!("\n{}\n", notes));
+            }
+            for (name, section) in sections {
+                output.push_str(&format!("\n## {}\n\n", name));
+                for task in section {
+                    output.push_str(&format!("- [ ] {}", task.title));
+                    if let Some(deadline) = task.deadline {
+                        output.push_str(&format!(" (due {})", deadline));
+                    }
+                    output.push('\n');
+                    for item in &task.checklist {
+                        let mark = if item.completed { 'x' } else { ' ' };
+                        output.push_str(&format!("  - [{}] {}\n", mark, item.title));
+                    }
+                    if let Some(notes) = &task.notes {
+                        for line in notes.lines() {
+                            output.push_str(&format!("  > {}\n", line));
+                        }
+                    }
+                }
+            }
+            output
+        }
+        ExportFormat::Html => {
+            let mut body = format!("<p class=\"meta\">{}</p>\n", escape_html(&meta.join(" · ")));
+            if let Some(notes) = &project.notes {
+                body.push_str(&format!("<p class=\"notes\">{}</p>\n", escape_html(notes)));
+            }
+            for (name, section) in sections {
+                body.push_str(&format!("<h2>{}</h2>\n<ul>\n", name));
+                for task in section {
+                    body.push_str(&format!(
+                        "<li><span class=\"box\">☐</span> {}",
+                        escape_html(&task.title)
+                    ));
+                    if let Some(deadline) = task.deadline {
+                        body.push_str(&format!(
+                            " <span class=\"deadline\">due {}</span>",
+                            deadline.strftime("%a, %b %d")
+                        ));
+                    }
+                    if let Some(notes) = &task.notes {
+                        body.push_str(&format!("<p class=\"notes\">{}</p>", escape_html(notes)));
+                    }
+                    if !task.checklist.is_empty() {
+                        body.push_str("<ul>");
+                        for item in &task.checklist {
+                            body.push_str(&format!(
+                                "<li><span class=\"box\">{}</span> {}</li>",
+                                if item.completed { "☑" } else { "☐" },
+                                escape_html(&item.title)
+                            ));
+                        }
+                        body.push_str("</ul>");
+                    }
+                    body.push_str("</li>\n");
+                }
+                body.push_str("</ul>\n");
+            }
+            html_page(&project.name, &body)
+        }
+    }
+}
+
+/// Agenda sections in the order they are worked through
+const PROJECT_SECTIONS: [&str; 5] = ["Today", "Upcoming", "Inbox", "Anytime", "Someday"];
+
+fn project_section(task: &Task) -> &'static str {
+    match task.when {
+        When::Today { .. } => "Today",
+        When::Scheduled { .. } => "Upcoming",
+        When::Inbox => "Inbox",
+        When::Anytime => "Anytime",
+        When::Someday => "Someday",
+    }
+}
+
+/// Wrap a body in a standalone page with a print-friendly stylesheet
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body {{ font: 11pt/1.5 Georgia, serif; max-width: 42em; margin: 2em auto; color: #222; }}
+  h1 {{ margin-bottom: 0.2em; }}
+  h2 {{ font-size: 1.1em; border-bottom: 1px solid #999; margin-top: 1.5em; }}
+  ul {{ list-style: none; padding-left: 0; }}
+  ul ul {{ padding-left: 1.6em; }}
+  li {{ margin: 0.4em 0; break-inside: avoid; }}
+  .box {{ font-size: 1.2em; margin-right: 0.3em; }}
+  .meta, .deadline {{ color: #666; font-size: 0.9em; }}
+  .deadline {{ font-style: italic; }}
+  .notes {{ white-space: pre-wrap; margin: 0.2em 0 0 1.6em; color: #444; font-size: 0.9em; }}
+  @media print {{ body {{ margin: 0; }} }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{body}</body>
+</html>
+"#,
+        title = escape_html(title),
+        body = body
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Parse a `YYYY-MM` month into its first day and the first day of the following month
+pub fn parse_month(month: &str) -> Option<(Date, Date)> {
+    let start: Date = format!("{}-01", month).parse().ok()?;
+    let end = start.checked_add(jiff::Span::new().months(1)).ok()?;
+    Some((start, end))
+}
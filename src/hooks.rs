@@ -0,0 +1,194 @@
+use std::{collections::HashSet, process::Command};
+
+use jiff::civil::Date;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::{store::Store, task::Task};
+
+/// Moments a user command can be hooked to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookEvent {
+    /// One or more tasks were completed with `tdo done`
+    TaskCompleted,
+}
+
+impl HookEvent {
+    /// Value of `TDO_EVENT` for the hook command
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::TaskCompleted => "task-completed",
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum HookError {
+    #[error("Failed to run hook '{command}': {source}")]
+    Launch {
+        command: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Hook '{0}' exited with an error")]
+    Failed(String),
+}
+
+/// Run a user command through the shell for `event`. The tasks involved are
+/// passed in the environment: `TDO_EVENT`, `TDO_COUNT`, `TDO_TASK_NUMBERS`
+/// (space separated) and `TDO_TASK_TITLES` (one per line).
+pub fn run_hook(command: &str, event: HookEvent, tasks: &[Task]) -> Result<(), HookError> {
+    let numbers: Vec<String> = tasks.iter().map(|t| t.task_number.to_string()).collect();
+    let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    let status = shell
+        .arg(command)
+        .env("TDO_EVENT", event.name())
+        .env("TDO_COUNT", tasks.len().to_string())
+        .env("TDO_TASK_NUMBERS", numbers.join(" "))
+        .env("TDO_TASK_TITLES", titles.join("\n"))
+        .status()
+        .map_err(|e| HookError::Launch {
+            command: command.to_string(),
+            source: e,
+        })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(HookError::Failed(command.to_string()))
+    }
+}
+
+/// Feedback given after `tdo done` completes tasks, set with `completion_feedback`
+/// in the config: `"off"`, `"bell"`, `"streak"` or `{"command": "..."}`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompletionFeedback {
+    /// Only the usual confirmation
+    Off,
+    /// Ring the terminal bell
+    Bell,
+    /// Print how many days in a row tasks have been completed
+    #[default]
+    Streak,
+    /// Run a command as a `task-completed` hook
+    Command(String),
+}
+
+/// Consecutive days, up to today, with at least one completed task
+#[derive(Debug, PartialEq, Eq)]
+pub struct CompletionStreak {
+    pub days: usize,
+    pub done_today: usize,
+}
+
+impl CompletionStreak {
+    pub fn from_store(store: &Store, today: Date) -> Self {
+        let completed_on: Vec<Date> = store
+            .get_active_tasks()
+            .filter_map(|t| t.completed_at)
+            .map(|ts| jiff::Zoned::new(ts, jiff::tz::TimeZone::system()).date())
+            .collect();
+        let done_today = completed_on.iter().filter(|date| **date == today).count();
+
+        let days_with_completions: HashSet<Date> = completed_on.into_iter().collect();
+        let days = today
+            .series(jiff::Span::new().days(-1))
+            .take_while(|date| days_with_completions.contains(date))
+            .count();
+
+        CompletionStreak { days, done_today }
+    }
+
+    /// A line of encouragement, e.g. "🔥 4-day streak · 3 done today"
+    pub fn message(&self) -> String {
+        if self.days > 1 {
+            format!(
+                "🔥 {}-day streak · {} done today",
+                self.days, self.done_today
+            )
+        } else {
+            format!(
+                "{} done today · finish one tomorrow to start a streak",
+                self.done_today
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    fn completed_on(day: &str) -> Task {
+        Task {
+            id: uuid::Uuid::new_v4(),
+            completed_at: Some(
+                date(day)
+                    .at(12, 0, 0, 0)
+                    .to_zoned(jiff::tz::TimeZone::system())
+                    .unwrap()
+                    .timestamp(),
+            ),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_streak_counts_consecutive_days_up_to_today() {
+        let mut store = Store::default();
+        for day in [
+            "2025-03-10",
+            "2025-03-10",
+            "2025-03-09",
+            "2025-03-08",
+            "2025-03-06",
+        ] {
+            store.add_task(completed_on(day));
+        }
+        store.add_task(Task {
+            id: uuid::Uuid::new_v4(),
+            ..Task::default()
+        });
+
+        let streak = CompletionStreak::from_store(&store, date("2025-03-10"));
+        assert_eq!(
+            streak,
+            CompletionStreak {
+                days: 3,
+                done_today: 2
+            }
+        );
+        assert_eq!(streak.message(), "🔥 3-day streak · 2 done today");
+
+        // A day without completions breaks the streak
+        let streak = CompletionStreak::from_store(&store, date("2025-03-11"));
+        assert_eq!(streak.days, 0);
+    }
+
+    #[test]
+    fn test_completion_feedback_config_values() {
+        let parse = |json: &str| serde_json::from_str::<CompletionFeedback>(json).unwrap();
+        assert_eq!(parse(r#""bell""#), CompletionFeedback::Bell);
+        assert_eq!(
+            parse(r#"{"command": "say done"}"#),
+            CompletionFeedback::Command("say done".to_string())
+        );
+    }
+}
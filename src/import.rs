@@ -0,0 +1,158 @@
+/// A checklist item read from a Markdown file
+pub struct MarkdownItem {
+    pub title: String,
+    pub done: bool,
+    /// Checkboxes indented under this one
+    pub subitems: Vec<MarkdownItem>,
+}
+
+/// Items grouped under the heading they appear below
+pub struct MarkdownSection {
+    /// `None` for items that come before the first heading
+    pub heading: Option<String>,
+    pub items: Vec<MarkdownItem>,
+}
+
+/// Parse `- [ ]` / `- [x]` items out of a Markdown document, grouped by heading.
+/// Indented checkboxes become subitems of the item above them; any other line is ignored.
+/// Sections without items are dropped.
+pub fn parse_markdown_checklist(content: &str) -> Vec<MarkdownSection> {
+    let mut sections = vec![MarkdownSection {
+        heading: None,
+        items: vec![],
+    }];
+    // Indentation of the current top-level item, so deeper checkboxes nest under it
+    let mut item_indent = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(heading) = parse_heading(trimmed) {
+            sections.push(MarkdownSection {
+                heading: Some(heading),
+                items: vec![],
+            });
+            item_indent = None;
+            continue;
+        }
+
+        let Some((title, done)) = parse_checkbox(trimmed) else {
+            continue;
+        };
+        let item = MarkdownItem {
+            title,
+            done,
+            subitems: vec![],
+        };
+
+        let indent = indentation(line);
+        let items = &mut sections.last_mut().expect("at least one section").items;
+        match (item_indent, items.last_mut()) {
+            (Some(parent_indent), Some(parent)) if indent > parent_indent => {
+                parent.subitems.push(item);
+            }
+            _ => {
+                item_indent = Some(indent);
+                items.push(item);
+            }
+        }
+    }
+
+    sections.retain(|s| !s.items.is_empty());
+    sections
+}
+
+fn parse_heading(line: &str) -> Option<String> {
+    let text = line.strip_prefix('#')?.trim_start_matches('#');
+    if !text.starts_with(' ') {
+        return None;
+    }
+
+    let heading = text.trim().trim_end_matches('#').trim();
+    if heading.is_empty() {
+        None
+    } else {
+        Some(heading.to_string())
+    }
+}
+
+/// Parse `- [ ] title`, also accepting `*` and `+` bullets and `[x]` / `[X]` for done items
+fn parse_checkbox(line: &str) -> Option<(String, bool)> {
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))?;
+
+    let (done, title) = if let Some(title) = rest.strip_prefix("[ ]") {
+        (false, title)
+    } else if let Some(title) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, title)
+    } else {
+        return None;
+    };
+
+    let title = title.trim();
+    if title.is_empty() {
+        None
+    } else {
+        Some((title.to_string(), done))
+    }
+}
+
+/// Leading whitespace width, counting a tab as four spaces
+fn indentation(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown_checklist() {
+        let content = "\
+# TODO
+
+- [ ] Loose item
+Some prose that is ignored
+- plain bullet, ignored
+
+## Website ##
+- [x] Pick a theme
+- [ ] Write about page
+  - [ ] Draft
+  - [X] Photo
+* [ ] Launch
+
+## Empty
+";
+        let sections = parse_markdown_checklist(content);
+        assert_eq!(sections.len(), 2);
+
+        assert_eq!(sections[0].heading.as_deref(), Some("TODO"));
+        assert_eq!(sections[0].items.len(), 1);
+        assert_eq!(sections[0].items[0].title, "Loose item");
+
+        let website = &sections[1];
+        assert_eq!(website.heading.as_deref(), Some("Website"));
+        let titles: Vec<_> = website.items.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, ["Pick a theme", "Write about page", "Launch"]);
+        assert!(website.items[0].done);
+        assert_eq!(website.items[1].subitems.len(), 2);
+        assert!(website.items[1].subitems[1].done);
+    }
+
+    #[test]
+    fn test_items_before_first_heading() {
+        let sections = parse_markdown_checklist("- [ ] First\n#hashtag, not a heading\n");
+        assert_eq!(sections.len(), 1);
+        assert!(sections[0].heading.is_none());
+        assert_eq!(sections[0].items.len(), 1);
+    }
+}
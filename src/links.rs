@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+/// How long to wait for a page before giving up on its title
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Only the start of the page is read; the title lives in the head
+const MAX_BODY_BYTES: u64 = 512 * 1024;
+
+#[derive(Error, Debug)]
+pub enum FetchTitleError {
+    #[error("Request failed: {0}")]
+    Request(#[from] ureq::Error),
+
+    #[error("Page has no title")]
+    NoTitle,
+}
+
+/// Find the first http(s) link in a piece of text
+pub fn find_url(text: &str) -> Option<&str> {
+    text.split_whitespace()
+        .find(|word| word.starts_with("http://") || word.starts_with("https://"))
+        .map(|word| word.trim_end_matches([',', '.', ')', ']', '>', '"', '\'']))
+}
+
+/// Fetch a page and return the contents of its `<title>` element
+pub fn fetch_page_title(url: &str) -> Result<String, FetchTitleError> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(FETCH_TIMEOUT))
+        .build()
+        .into();
+
+    let body = agent
+        .get(url)
+        .call()?
+        .body_mut()
+        .with_config()
+        .limit(MAX_BODY_BYTES)
+        .lossy_utf8(true)
+        .read_to_string()?;
+
+    extract_title(&body).ok_or(FetchTitleError::NoTitle)
+}
+
+/// Pull the text of the first `<title>` element out of an HTML document
+fn extract_title(html: &str) -> Option<String> {
+    // ASCII lowercasing keeps byte offsets aligned with the original
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+
+    let title = decode_entities(&html[start..end])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if title.is_empty() { None } else { Some(title) }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_url_in_title() {
+        assert_eq!(
+            find_url("Read https://example.com/post, later"),
+            Some("https://example.com/post")
+        );
+        assert_eq!(find_url("No links here"), None);
+    }
+
+    #[test]
+    fn test_extract_title() {
+        let html = "<html><head><TITLE lang=\"en\">\n  Rust &amp; Friends\n</TITLE></head></html>";
+        assert_eq!(extract_title(html), Some("Rust & Friends".to_string()));
+        assert_eq!(extract_title("<title>  </title>"), None);
+        assert_eq!(extract_title("<p>nothing</p>"), None);
+    }
+}
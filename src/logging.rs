@@ -0,0 +1,34 @@
+use std::{fs::OpenOptions, io::IsTerminal, path::Path, sync::Mutex};
+
+use tracing::Level;
+
+/// Install the debug log subscriber. Nothing is logged unless `-v` is passed:
+/// `-v` logs storage operations and timings, `-vv` adds lock retries and other detail.
+/// Logs go to stderr, or are appended to `log_file` when one is given.
+pub fn init(verbosity: u8, log_file: Option<&Path>) -> std::io::Result<()> {
+    let level = match verbosity {
+        0 => return Ok(()),
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_target(false);
+
+    match log_file {
+        Some(path) => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            builder
+                .with_ansi(false)
+                .with_writer(Mutex::new(file))
+                .init();
+        }
+        None => builder
+            .with_ansi(std::io::stderr().is_terminal())
+            .with_writer(std::io::stderr)
+            .init(),
+    }
+
+    Ok(())
+}
@@ -1,31 +1,101 @@
-use std::path::PathBuf;
+use std::{io::Write, path::PathBuf};
 
-use clap::{Parser, Subcommand};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use colored::*;
+use uuid::Uuid;
 
 use crate::{
-    models::task::{When, WhenInstantiationError},
+    badge::{BadgeCount, BadgeFormat},
+    capture::Capture,
+    config::{Config, GroomList},
+    dashboard::AreaSummary,
+    export::ExportFormat,
+    hooks::{CompletionFeedback, CompletionStreak, HookEvent},
+    models::{
+        project::Project,
+        schema,
+        store::Store,
+        task::{Energy, Task, When, WhenInstantiationError, normalize_context, parse_past_instant},
+    },
     services::{
+        archive::{ArchiveTasksError, ArchiveTasksParameters, archive_tasks},
         areas::{
-            CreateAreaError, CreateAreaParameters, DeleteAreaError, DeleteAreaParameters,
-            create_area, delete_area,
+            ArchiveAreaError, ArchiveAreaParameters, AreaPlacement, CreateAreaError,
+            CreateAreaParameters, DeleteAreaError, DeleteAreaParameters, EditAreaError,
+            EditAreaParameters, MoveAreaProjectsError, MoveAreaProjectsParameters,
+            MoveAreaProjectsResult, OrderAreaError, OrderAreaParameters, RenameAreaError,
+            RenameAreaParameters, RestoreAreaError, RestoreAreaParameters, RestoreAreaResult,
+            SetAreaNotesError, SetAreaNotesParameters, archive_area, create_area, delete_area,
+            edit_area, move_area_projects, order_area, rename_area, restore_area, set_area_notes,
         },
+        autoschedule::{AutoscheduleError, AutoscheduleParameters, autoschedule},
+        doctor::{DoctorError, DoctorParameters, run_doctor},
+        groom::{GroomAction, GroomError, GroomParameters, groom},
+        import::{ImportMarkdownError, ImportMarkdownParameters, import_markdown},
+        plan::{ApplyWeekPlanError, ApplyWeekPlanParameters, apply_week_plan},
         projects::{
-            CreateProjectError, CreateProjectParameters, DeleteProjectError,
-            DeleteProjectParameters, create_project, delete_project,
+            ArchiveProjectError, ArchiveProjectParameters, BlockProjectError,
+            BlockProjectParameters, CreateProjectError, CreateProjectParameters,
+            CreateProjectResult, DeleteProjectError, DeleteProjectParameters,
+            DuplicateProjectError, DuplicateProjectParameters, DuplicateProjectResult,
+            EditProjectError, EditProjectParameters, IncubateProjectError,
+            IncubateProjectParameters, OrderProjectError, OrderProjectParameters, ProjectPlacement,
+            RenameProjectError, RenameProjectParameters, RestoreProjectError,
+            RestoreProjectParameters, RestoreProjectResult, SetNextActionError,
+            SetNextActionParameters, SetProjectNotesError, SetProjectNotesParameters,
+            UnblockProjectError, UnblockProjectParameters, archive_project, block_project,
+            create_project, delete_project, duplicate_project, edit_project, incubate_project,
+            order_project, rename_project, restore_project, set_next_action, set_project_notes,
+            unblock_project,
         },
         tasks::{
-            AddTaskError, AddTaskParameters, CompleteTaskError, CompleteTaskParameters, add_task,
-            complete_task,
+            AddTaskError, AddTaskParameters, AddedTask, AppendNoteError, AppendNoteParameters,
+            AttachError, AttachParameters, CompleteTaskError, CompleteTasksParameters,
+            DeleteTaskError, DeleteTaskParameters, DuplicateTaskError, DuplicateTaskParameters,
+            EditTaskError, EditTaskParameters, LogTaskParameters, MoveChanges, MoveTaskError,
+            MoveTaskParameters, MoveTasksParameters, NameLookupError, PromoteDueTasksError,
+            PromoteDueTasksParameters, RestoreTaskError, RestoreTaskParameters, SetEveningError,
+            SetEveningParameters, SetPinnedError, SetPinnedParameters, SnoozeOutcome,
+            SnoozeTaskError, SnoozeTaskParameters, StartTimerError, StartTimerParameters,
+            StopTimerError, SurfaceDeferredTasksError, TaskList, UncompleteTaskError,
+            UncompleteTaskParameters, add_task, add_tasks, append_note, attach, complete_tasks,
+            delete_task, duplicate_task, edit_task, find_area_id, find_project_id, find_task,
+            log_task, move_task, move_tasks, promote_due_tasks, restore_task, set_evening,
+            set_pinned, snooze_task, start_timer, stop_timer, surface_deferred_tasks,
+            uncomplete_task,
+        },
+        trash::{
+            EmptyTrashError, EmptyTrashParameters, PurgeItemError, PurgeItemParameters,
+            PurgeResult, RestoreFromTrashError, RestoreFromTrashParameters, empty_trash,
+            purge_item, restore_from_trash,
         },
     },
-    storage::{Storage, json::JsonFileStorage},
+    stats::CycleTimeGrouping,
+    storage::{
+        Storage,
+        journal::Intent,
+        json::{JsonFileStorage, LockWait},
+    },
 };
 
+mod badge;
+mod capture;
+mod config;
+mod dashboard;
+mod editor;
+mod export;
+mod hooks;
+mod import;
+mod links;
+mod logging;
 mod models;
+mod plan;
+mod selftest;
 mod services;
+mod stats;
 mod storage;
 mod ui;
+mod workspace;
 
 #[derive(Parser)]
 #[command(
@@ -33,6 +103,38 @@ mod ui;
     about = "A minimal and clean task manager for your terminal"
 )]
 struct Cli {
+    /// Force interactive output (colors, alignment, prompts) even when not attached to a terminal
+    #[arg(long, global = true)]
+    interactive: bool,
+
+    /// Print the first line of each task's notes beneath it
+    #[arg(long, global = true)]
+    show_notes: bool,
+
+    /// Wrap long task titles onto extra lines instead of truncating them
+    #[arg(long, global = true)]
+    full_titles: bool,
+
+    /// Log storage and timing details to stderr (-v for debug, -vv for trace)
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Write the -v log to this file instead of stderr
+    #[arg(long, global = true, requires = "verbose")]
+    log_file: Option<PathBuf>,
+
+    /// Wait as long as needed when another tdo process is saving
+    #[arg(long, global = true, conflicts_with = "no_wait")]
+    wait: bool,
+
+    /// Fail immediately when another tdo process is saving
+    #[arg(long, global = true)]
+    no_wait: bool,
+
+    /// Limit views to a workspace from the config and file new tasks under it
+    #[arg(long, global = true)]
+    workspace: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -40,25 +142,129 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Show today's tasks (including overdue)
-    Today,
+    Today {
+        /// Only show the evening tasks
+        #[arg(long)]
+        evening_only: bool,
+
+        /// Only show tasks in this context (e.g., "@home")
+        #[arg(long)]
+        context: Option<String>,
+    },
 
     /// List tasks in the inbox
-    Inbox,
+    Inbox {
+        /// Only show tasks in this context (e.g., "@home")
+        #[arg(long)]
+        context: Option<String>,
+    },
+
+    /// Show tasks scheduled or due tomorrow
+    Tomorrow {
+        /// Only show tasks in this context (e.g., "@home")
+        #[arg(long)]
+        context: Option<String>,
+    },
 
     /// Show upcoming tasks (future-dated)
-    Upcoming,
+    Upcoming {
+        /// Only show tasks in this context (e.g., "@home")
+        #[arg(long)]
+        context: Option<String>,
+    },
+
+    /// Show only what matters right now: pinned tasks, tasks due today and reminders this hour
+    #[command(alias = "focus-list")]
+    Now,
+
+    /// One next action per active project, plus projects with nothing to do next
+    #[command(alias = "next")]
+    NextActions,
+
+    /// Print the number of Today and overdue tasks for dock, panel and taskbar badges
+    Badge {
+        /// Output for the tool showing the badge
+        #[arg(long, value_enum, default_value_t)]
+        format: BadgeFormat,
+    },
+
+    /// One-screen overview of every area: projects, tasks for today,
+    /// overdue tasks and deadlines in the next seven days
+    Dashboard,
 
     /// Show anytime tasks
-    Anytime,
+    Anytime {
+        /// Only show tasks with this energy level
+        #[arg(long, value_enum)]
+        energy: Option<Energy>,
+
+        /// Only show tasks in this context (e.g., "@home")
+        #[arg(long)]
+        context: Option<String>,
+    },
 
     /// Show someday tasks
-    Someday,
+    Someday {
+        /// Only show tasks in this context (e.g., "@home")
+        #[arg(long)]
+        context: Option<String>,
+    },
+
+    /// Shelve a project for later; its tasks leave Today, Anytime and the project list
+    Incubate {
+        /// Slug of the project
+        slug: String,
+
+        /// Take the project back out of the incubator
+        #[arg(long)]
+        resume: bool,
+    },
+
+    /// Show incubated projects and when they were shelved
+    Incubator,
 
     /// Show completed tasks (last 14 days)
-    Logbook,
+    Logbook {
+        /// Export the logbook instead of displaying it
+        #[arg(long, value_enum)]
+        export: Option<ExportFormat>,
+
+        /// Limit to a specific month (e.g., "2025-06")
+        #[arg(long)]
+        month: Option<String>,
+
+        /// Also read tasks moved out by `tdo archive`
+        #[arg(long)]
+        include_archive: bool,
+    },
+
+    /// Move old completed tasks out of the store into yearly archive files
+    Archive {
+        /// Archive tasks completed longer ago than this (e.g., "90d", "6mo")
+        #[arg(long, default_value = "90d")]
+        older_than: String,
+    },
+
+    /// Summaries of tracked time
+    #[command(subcommand)]
+    Report(ReportCommands),
+
+    /// Show cycle-time metrics for recently completed tasks
+    Stats {
+        /// Group completed tasks by project, area or tag
+        #[arg(long, value_enum, default_value_t)]
+        by: CycleTimeGrouping,
+
+        /// Only include tasks completed in the last N days
+        #[arg(long, default_value_t = 7)]
+        days: i64,
+    },
 
     /// Show deleted items
-    Trash,
+    Trash {
+        #[command(subcommand)]
+        command: Option<TrashCommands>,
+    },
 
     /// Show all active tasks
     All,
@@ -66,13 +272,22 @@ enum Commands {
     /// Add a new task
     Add {
         /// Task title
-        title: String,
+        #[arg(required_unless_present = "stdin", conflicts_with = "stdin")]
+        title: Option<String>,
+
+        /// Read one task title per line from stdin; the other flags apply to every task
+        #[arg(long)]
+        stdin: bool,
 
         /// Schedule for today
         #[arg(long)]
         today: bool,
 
-        /// Schedule for today (evening)
+        /// Schedule for tomorrow
+        #[arg(long)]
+        tomorrow: bool,
+
+        /// Schedule for the evening of --today, --tomorrow or the --when date
         #[arg(long)]
         evening: bool,
 
@@ -88,7 +303,7 @@ enum Commands {
         #[arg(short, long)]
         when: Option<String>,
 
-        /// Set a hard deadline
+        /// Set a hard deadline (YYYY-MM-DD, or +3d, +1w, +2m from today)
         #[arg(short, long)]
         deadline: Option<String>,
 
@@ -96,10 +311,22 @@ enum Commands {
         #[arg(short, long)]
         project: Option<String>,
 
+        /// Create the project (--project or a +project token) if no project matches its name
+        #[arg(long)]
+        create_project: bool,
+
         /// Assign to an area
         #[arg(short, long)]
         area: Option<String>,
 
+        /// Surface a project task under this area instead of the project's area
+        #[arg(long)]
+        area_override: Option<String>,
+
+        /// Create the --area (or --area-override) if no area matches its name
+        #[arg(long)]
+        create_area: bool,
+
         /// Add tags (can be used multiple times)
         #[arg(short, long, action = clap::ArgAction::Append)]
         tag: Vec<String>,
@@ -107,18 +334,84 @@ enum Commands {
         /// Add notes
         #[arg(short, long)]
         notes: Option<String>,
+
+        /// Attach a link (saved in the notes)
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Fetch the page title of the task's link and save it in the notes
+        #[arg(long)]
+        fetch_title: bool,
+
+        /// Energy the task requires (low, medium, high)
+        #[arg(long, value_enum)]
+        energy: Option<Energy>,
+
+        /// GTD context where the task can be done (e.g., "@home")
+        #[arg(long)]
+        context: Option<String>,
+
+        /// Remind me at a time (e.g., "17:00", "tomorrow 9:30", "friday 8:00")
+        #[arg(long)]
+        remind: Option<String>,
+
+        /// Hide from Today and Anytime until this date (e.g., "2025-03-01")
+        #[arg(long)]
+        defer: Option<String>,
+
+        /// How long the task should take (e.g., "30m", "1h30m")
+        #[arg(long)]
+        estimate: Option<String>,
+
+        /// Keep #tags, @contexts, +projects, //dates and !deadlines in the title as written
+        #[arg(long)]
+        raw: bool,
+
+        /// Give the task this UUID; adding it again returns the existing task
+        #[arg(long, conflicts_with = "stdin")]
+        id: Option<Uuid>,
+
+        /// Key identifying this add, so a script retrying it doesn't create a duplicate
+        #[arg(long, conflicts_with = "stdin")]
+        idempotency_key: Option<String>,
     },
 
-    /// Moves a task
+    /// Moves a task, or with --all every task matching the --from filters
+    #[command(group(clap::ArgGroup::new("bulk_filter").multiple(true)))]
     Move {
         /// Task number
-        task_number: String,
+        #[arg(required_unless_present = "all")]
+        task_number: Option<String>,
+
+        /// Move every open task matching the --from filters instead of one task
+        #[arg(long, conflicts_with = "task_number", requires = "bulk_filter")]
+        all: bool,
+
+        /// With --all, only move tasks in this list
+        #[arg(long, value_enum, group = "bulk_filter")]
+        from: Option<TaskList>,
+
+        /// With --all, only move tasks in this project
+        #[arg(long, group = "bulk_filter")]
+        from_project: Option<String>,
+
+        /// With --all, only move tasks with this tag
+        #[arg(long, group = "bulk_filter")]
+        from_tag: Option<String>,
+
+        /// Move to a list (inbox, today, anytime, someday)
+        #[arg(long, value_enum, conflicts_with_all = ["today", "tomorrow", "evening", "someday", "anytime", "when"])]
+        to: Option<TaskList>,
 
         /// Schedule for today
         #[arg(long)]
         today: bool,
 
-        /// Schedule for today (evening)
+        /// Schedule for tomorrow
+        #[arg(long)]
+        tomorrow: bool,
+
+        /// Schedule for the evening of --today, --tomorrow or the --when date
         #[arg(long)]
         evening: bool,
 
@@ -134,18 +427,166 @@ enum Commands {
         #[arg(short, long)]
         when: Option<String>,
 
-        /// Set a hard deadline
+        /// Set a hard deadline (YYYY-MM-DD, or +3d, +1w, +2m from today)
+        #[arg(short, long)]
+        deadline: Option<String>,
+
+        /// Assign to a project
+        #[arg(short, long)]
+        project: Option<String>,
+
+        /// Assign to an area
+        #[arg(short, long)]
+        area: Option<String>,
+
+        /// Surface a project task under this area instead of the project's area
+        #[arg(long)]
+        area_override: Option<String>,
+
+        /// Add tags (can be used multiple times)
+        #[arg(short, long, action = clap::ArgAction::Append)]
+        tag: Vec<String>,
+
+        /// Replace notes
         #[arg(short, long)]
+        notes: Option<String>,
+
+        /// Energy the task requires (low, medium, high)
+        #[arg(long, value_enum)]
+        energy: Option<Energy>,
+
+        /// GTD context where the task can be done (e.g., "@home")
+        #[arg(long)]
+        context: Option<String>,
+
+        /// Hide from Today and Anytime until this date (e.g., "2025-03-01")
+        #[arg(long)]
+        defer: Option<String>,
+
+        /// How long the task should take (e.g., "30m", "1h30m")
+        #[arg(long)]
+        estimate: Option<String>,
+    },
+
+    /// Edit a task's title, notes or deadline
+    Edit {
+        /// Task number or part of its title
+        task_number: String,
+
+        /// New title
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Replace notes
+        #[arg(short, long, conflicts_with = "clear_notes")]
+        notes: Option<String>,
+
+        /// Remove notes
+        #[arg(long)]
+        clear_notes: bool,
+
+        /// Edit the notes in $VISUAL / $EDITOR
+        #[arg(long, conflicts_with_all = ["notes", "clear_notes"])]
+        notes_editor: bool,
+
+        /// Set a hard deadline (YYYY-MM-DD, or +3d, +1w, +2m from today)
+        #[arg(short, long, conflicts_with = "clear_deadline")]
         deadline: Option<String>,
 
+        /// Remove the deadline
+        #[arg(long)]
+        clear_deadline: bool,
+
+        /// Set a reminder (e.g., "17:00", "tomorrow 9:30", "friday 8:00")
+        #[arg(long, conflicts_with = "clear_reminder")]
+        remind: Option<String>,
+
+        /// Remove the reminder
+        #[arg(long)]
+        clear_reminder: bool,
+    },
+
+    /// Append a timestamped line to a task's notes
+    Note {
+        /// Task number or part of its title
+        task_number: String,
+
+        /// Text to add
+        text: String,
+    },
+
+    /// Attach a URL or file to a task
+    Attach {
+        /// Task number or part of its title
+        task_number: String,
+
+        /// URL or path of a file
+        target: String,
+
+        /// Name to show instead of the URL or path
+        #[arg(short, long)]
+        label: Option<String>,
+    },
+
+    /// Open a task's attachment with the system's default application
+    Open {
+        /// Task number or part of its title
+        task_number: String,
+
+        /// Which attachment to open, as numbered in `tdo show`
+        #[arg(default_value_t = 1)]
+        index: usize,
+    },
+
+    /// Push a Today or scheduled task to a later day, or silence it for a few hours
+    Snooze {
+        /// Task number or part of its title
+        task_number: String,
+
+        /// "1d", "1w" or a weekday move the task (default: one day later);
+        /// "2h" or "30m" only silence reminders and overdue highlighting
+        duration: Option<String>,
+
+        /// Remove an active snooze
+        #[arg(long, conflicts_with = "duration")]
+        clear: bool,
+    },
+
+    /// Show every detail of a single task
+    Show { task_number: u64 },
+
+    /// List open tasks whose reminder time has passed
+    Remind {
+        /// Also send a desktop notification for each task
+        #[arg(long)]
+        notify: bool,
+    },
+
+    /// Record something already done; it goes straight to the Logbook
+    Log {
+        /// What was done
+        title: String,
+
+        /// When it was done (e.g., "yesterday", "friday 17:00", "2025-03-01"); defaults to now
+        #[arg(long)]
+        at: Option<String>,
+
         /// Assign to a project
         #[arg(short, long)]
         project: Option<String>,
 
+        /// Create the --project if no project matches its name
+        #[arg(long, requires = "project")]
+        create_project: bool,
+
         /// Assign to an area
         #[arg(short, long)]
         area: Option<String>,
 
+        /// Create the --area if no area matches its name
+        #[arg(long, requires = "area")]
+        create_area: bool,
+
         /// Add tags (can be used multiple times)
         #[arg(short, long, action = clap::ArgAction::Append)]
         tag: Vec<String>,
@@ -155,8 +596,66 @@ enum Commands {
         notes: Option<String>,
     },
 
-    /// Complete a task
-    Done { task_number_or_fuzzy_name: String },
+    /// Complete one or more tasks
+    Done {
+        #[arg(required = true)]
+        task_numbers_or_fuzzy_names: Vec<String>,
+    },
+
+    /// Copy a task into a new one (title, notes, tags, checklist, project)
+    Duplicate {
+        /// Task number or part of its title
+        task_number: String,
+
+        /// Schedule the copy for today
+        #[arg(long)]
+        today: bool,
+
+        /// Schedule the copy for tomorrow
+        #[arg(long)]
+        tomorrow: bool,
+
+        /// Schedule the copy for this evening
+        #[arg(long)]
+        evening: bool,
+
+        /// Put the copy in Someday
+        #[arg(long)]
+        someday: bool,
+
+        /// Put the copy in Anytime
+        #[arg(long)]
+        anytime: bool,
+
+        /// Schedule the copy for a specific date (e.g., "2025-03-01")
+        #[arg(short, long)]
+        when: Option<String>,
+    },
+
+    /// Reopen a completed task
+    #[command(alias = "reopen")]
+    Undone { task_number_or_fuzzy_name: String },
+
+    /// Move a task to the trash
+    Delete { task_number_or_fuzzy_name: String },
+
+    /// Move a Today or scheduled task to the evening of its day
+    Evening { task_number_or_fuzzy_name: String },
+
+    /// Move an evening task back to the regular list of its day
+    Morning { task_number_or_fuzzy_name: String },
+
+    /// Start a timer on a task, stopping any other running timer
+    Start { task_number_or_fuzzy_name: String },
+
+    /// Stop the running timer
+    Stop,
+
+    /// Pin a task to the top of Today and Anytime
+    Pin { task_number_or_fuzzy_name: String },
+
+    /// Unpin a task
+    Unpin { task_number_or_fuzzy_name: String },
 
     /// Manage areas
     #[command(subcommand)]
@@ -169,6 +668,80 @@ enum Commands {
     /// Manage tags
     #[command(subcommand)]
     Tag(TagCommands),
+
+    /// Manage GTD contexts
+    #[command(subcommand)]
+    Context(ContextCommands),
+
+    /// Restore items from the trash
+    #[command(subcommand)]
+    Restore(RestoreCommands),
+
+    /// Import tasks from other formats
+    #[command(subcommand)]
+    Import(ImportCommands),
+
+    /// Manage store backups
+    #[command(subcommand)]
+    Backup(BackupCommands),
+
+    /// Check the store for integrity problems and orphaned items
+    Doctor {
+        /// Repair orphans by detaching them from deleted projects and areas
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Exercise save, load, migration and backups against a scratch store and
+    /// report environment problems (permissions, rename support, clock skew)
+    Selftest,
+
+    /// Apply the aging rules from config.json to old Inbox, Anytime and Someday tasks
+    Groom {
+        /// Save the changes instead of only previewing them
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Spread Anytime tasks over the coming days, a few per day
+    Autoschedule {
+        /// Most tasks on a single day, counting ones already scheduled
+        #[arg(long, default_value_t = 3)]
+        per_day: usize,
+
+        /// Only schedule tasks with this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Leave weekends free (overrides `autoschedule_skip_weekends` in config.json)
+        #[arg(long)]
+        skip_weekends: bool,
+    },
+
+    /// Plan the coming days in your editor and schedule tasks from the result
+    #[command(subcommand)]
+    Plan(PlanCommands),
+
+    /// Print the JSON Schema of the store file
+    Schema,
+
+    /// Revert the most recent change to the store
+    Undo,
+
+    /// Replay or roll back a command that was interrupted while saving
+    Recover {
+        /// Run the interrupted command again
+        #[arg(long, conflicts_with_all = ["rollback", "discard"])]
+        replay: bool,
+
+        /// Restore the store as it was before the interrupted command
+        #[arg(long, conflicts_with = "discard")]
+        rollback: bool,
+
+        /// Forget the interrupted command and keep the store as it is
+        #[arg(long)]
+        discard: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -177,13 +750,98 @@ enum AreaCommands {
     New {
         /// Name of the area
         name: String,
+        /// Slug to use instead of one derived from the name
+        #[arg(long)]
+        slug: Option<String>,
     },
     /// Delete an area
-    Delete { name: String },
+    Delete {
+        name: String,
+        /// Skip the confirmation asked for when many tasks would be deleted
+        #[arg(long)]
+        yes: bool,
+    },
     /// List all areas
-    List,
+    List {
+        /// Show archived areas instead
+        #[arg(long)]
+        archived: bool,
+    },
+    /// Put a dormant area away, with its projects and tasks, without trashing it
+    Archive {
+        /// Slug or part of the name
+        area: String,
+    },
+    /// Bring an archived area back
+    Unarchive {
+        /// Slug or part of the name
+        area: String,
+    },
     /// View projects in an area
-    View { slug: String },
+    View {
+        slug: String,
+        /// Only show tasks with this tag, or in a project with it
+        #[arg(long)]
+        tag: Option<String>,
+        /// Only show tasks for today, overdue ones included
+        #[arg(long)]
+        today: bool,
+    },
+    /// Task and project deadlines in an area, by date
+    Deadlines { slug: String },
+    /// Place an area before or after another one in `area list`
+    Order {
+        /// Slug of the area to move
+        slug: String,
+        /// Slug of the area to place it before
+        #[arg(long, conflicts_with = "after", required_unless_present = "after")]
+        before: Option<String>,
+        /// Slug of the area to place it after
+        #[arg(long)]
+        after: Option<String>,
+    },
+    /// Move every project of an area to another area
+    MoveProjects {
+        /// Slug of the area the projects leave
+        from: String,
+        /// Slug of the area the projects go to
+        to: String,
+    },
+    /// Set an area's notes, or edit them in $VISUAL / $EDITOR
+    Notes {
+        /// Slug of the area
+        slug: String,
+        /// New notes; without them the editor opens
+        #[arg(conflicts_with = "clear")]
+        notes: Option<String>,
+        /// Remove the notes
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Set an area's color or icon
+    Edit {
+        /// Slug of the area
+        slug: String,
+        /// Show it in this color (e.g. blue, bright magenta)
+        #[arg(long, conflicts_with = "clear_color")]
+        color: Option<String>,
+        /// Remove the color
+        #[arg(long)]
+        clear_color: bool,
+        /// Show this emoji or symbol before its name
+        #[arg(long, conflicts_with = "clear_icon")]
+        icon: Option<String>,
+        /// Remove the icon
+        #[arg(long)]
+        clear_icon: bool,
+    },
+    /// Rename an area; its slug is regenerated
+    Rename {
+        /// Slug or part of the current name
+        area: String,
+        /// New name
+        new_name: String,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -195,13 +853,136 @@ enum ProjectCommands {
         /// Assign to an area
         #[arg(short, long)]
         area: Option<String>,
+        /// Set a deadline (YYYY-MM-DD, or +3d, +1w, +2m from today)
+        #[arg(short, long)]
+        deadline: Option<String>,
+        /// Slug to use instead of one derived from the name
+        #[arg(long)]
+        slug: Option<String>,
+        /// Add a task to the new project (can be used multiple times)
+        #[arg(long = "task", value_name = "TITLE", action = clap::ArgAction::Append)]
+        tasks: Vec<String>,
     },
     /// Delete an project
-    Delete { name: String },
+    Delete {
+        name: String,
+        /// Skip the confirmation asked for when many tasks would be deleted
+        #[arg(long)]
+        yes: bool,
+    },
     /// List all projects
-    List,
+    List {
+        /// Show archived projects instead
+        #[arg(long)]
+        archived: bool,
+    },
+    /// Copy a project and its tasks, reopened, into a new project
+    Duplicate {
+        /// Slug of the project to copy
+        slug: String,
+        /// Name of the new project
+        #[arg(short, long)]
+        name: String,
+    },
+    /// Place a project before or after another one in lists and area views
+    Order {
+        /// Slug of the project to move
+        slug: String,
+        /// Slug of the project to place it before
+        #[arg(long, conflicts_with = "after", required_unless_present = "after")]
+        before: Option<String>,
+        /// Slug of the project to place it after
+        #[arg(long)]
+        after: Option<String>,
+    },
+    /// Archive a finished-with or dormant project without trashing its tasks
+    Archive {
+        /// Slug or part of the name
+        project: String,
+        /// Bring an archived project back
+        #[arg(long)]
+        unarchive: bool,
+    },
+    /// Rename a project; its slug is regenerated
+    Rename {
+        /// Slug or part of the current name
+        project: String,
+        /// New name
+        new_name: String,
+    },
     /// View tasks in a project
-    View { slug: String },
+    View {
+        slug: String,
+        /// Also list completed tasks, below the open ones
+        #[arg(long, visible_alias = "completed")]
+        all: bool,
+    },
+    /// Choose a task as its project's next action
+    Next { task_number_or_fuzzy_name: String },
+    /// Hold a project back until another one is completed
+    Block {
+        /// Slug of the project that waits
+        slug: String,
+        /// Slug of the project to complete first
+        #[arg(long)]
+        on: String,
+    },
+    /// Stop a project waiting on other projects
+    Unblock {
+        /// Slug of the project
+        slug: String,
+        /// Only stop waiting on this project (default: all of them)
+        #[arg(long)]
+        on: Option<String>,
+    },
+    /// Edit a project
+    Edit {
+        /// Slug of the project
+        slug: String,
+        /// Add tags (can be used multiple times)
+        #[arg(short, long, action = clap::ArgAction::Append)]
+        tag: Vec<String>,
+        /// Remove tags (can be used multiple times)
+        #[arg(long, action = clap::ArgAction::Append)]
+        untag: Vec<String>,
+        /// Set the deadline (YYYY-MM-DD, or +3d, +1w, +2m from today)
+        #[arg(short, long, conflicts_with = "clear_deadline")]
+        deadline: Option<String>,
+        /// Remove the deadline
+        #[arg(long)]
+        clear_deadline: bool,
+        /// Show it in this color (e.g. blue, bright magenta)
+        #[arg(long, conflicts_with = "clear_color")]
+        color: Option<String>,
+        /// Remove the color
+        #[arg(long)]
+        clear_color: bool,
+        /// Show this emoji or symbol before its name
+        #[arg(long, conflicts_with = "clear_icon")]
+        icon: Option<String>,
+        /// Remove the icon
+        #[arg(long)]
+        clear_icon: bool,
+    },
+    /// Set a project's notes, or edit them in $VISUAL / $EDITOR
+    Notes {
+        /// Slug of the project
+        slug: String,
+        /// New notes; without them the editor opens
+        #[arg(conflicts_with = "clear")]
+        notes: Option<String>,
+        /// Remove the notes
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Export a project's open tasks as a printable agenda
+    Export {
+        /// Slug of the project
+        slug: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "html")]
+        format: ExportFormat,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -212,8 +993,145 @@ enum TagCommands {
     View { name: String },
 }
 
+#[derive(Debug, Subcommand)]
+enum ContextCommands {
+    /// List all contexts
+    List,
+    /// View tasks in a specific context (also `tdo context <name>`)
+    View { name: String },
+    /// `tdo context <name>`, turned into `View` right after parsing
+    #[command(external_subcommand)]
+    Named(Vec<String>),
+}
+
+#[derive(Debug, Subcommand)]
+enum RestoreCommands {
+    /// Restore a deleted task
+    Task { task_number: u64 },
+    /// Restore a deleted project and the tasks deleted along with it
+    Project {
+        name: String,
+        /// Also restore the tasks deleted along with it (the default)
+        #[arg(long, conflicts_with = "without_tasks")]
+        with_tasks: bool,
+        /// Leave the tasks deleted along with it in the trash
+        #[arg(long)]
+        without_tasks: bool,
+    },
+    /// Restore a deleted area and the projects and tasks deleted along with it
+    Area {
+        name: String,
+        /// Also restore the projects and tasks deleted along with it (the default)
+        #[arg(long, conflicts_with = "without_contents")]
+        with_contents: bool,
+        /// Leave the projects and tasks deleted along with it in the trash
+        #[arg(long)]
+        without_contents: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ReportCommands {
+    /// Time tracked per project
+    Time {
+        /// Only include time started in the last N days
+        #[arg(long, default_value_t = 7)]
+        days: i64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TrashCommands {
+    /// Restore several trashed items at once
+    #[command(group(clap::ArgGroup::new("filter").required(true).multiple(true)))]
+    Restore {
+        /// Restore a project and the tasks deleted along with it
+        #[arg(long, group = "filter")]
+        project: Option<String>,
+
+        /// Restore everything deleted on or after this date (e.g., "2025-03-01")
+        #[arg(long, group = "filter")]
+        since: Option<String>,
+    },
+    /// Permanently remove trashed tasks, projects and areas
+    Empty {
+        /// Only remove items deleted longer ago than this (e.g., "30d", "2w")
+        #[arg(long)]
+        older_than: Option<String>,
+
+        /// Don't ask for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Permanently remove one trashed task, project or area
+    Purge {
+        /// Task number, or the name of a trashed project or area
+        item: String,
+
+        /// Don't ask for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ImportCommands {
+    /// Import `- [ ]` / `- [x]` items from a Markdown file; headings become projects
+    Markdown {
+        /// Markdown file to read
+        file: PathBuf,
+
+        /// Put every item in this project instead of one project per heading
+        #[arg(short, long)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum PlanCommands {
+    /// Edit a Markdown plan of the next 7 days; moved and new lines are scheduled
+    Week {
+        /// First day of the plan (YYYY-MM-DD, default: today)
+        #[arg(long)]
+        start: Option<String>,
+
+        /// Print the plan instead of opening it in the editor
+        #[arg(long)]
+        print: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum BackupCommands {
+    /// Check that every backup loads and passes integrity checks
+    Verify,
+}
+
 fn main() {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    // `tdo context home` is short for `tdo context view home`
+    if let Some(Commands::Context(ContextCommands::Named(args))) = &mut cli.command {
+        if args.len() > 1 {
+            eprintln!("Error: Unexpected arguments: {}", args[1..].join(" "));
+            eprintln!("\nUsage: tdo context <name>");
+            std::process::exit(1);
+        }
+        let name = args.remove(0);
+        cli.command = Some(Commands::Context(ContextCommands::View { name }));
+    }
+
+    ui::init_output(ui::OutputOptions {
+        force_interactive: cli.interactive,
+        show_notes: cli.show_notes,
+        full_titles: cli.full_titles,
+    });
+
+    if let Err(e) = logging::init(cli.verbose, cli.log_file.as_deref()) {
+        eprintln!("Error: Failed to open log file: {}", e);
+        std::process::exit(1);
+    }
 
     // Initialize storage
     let storage_path = dirs::data_local_dir()
@@ -229,7 +1147,37 @@ fn main() {
         });
     }
 
-    let storage = JsonFileStorage::new(storage_path);
+    let config_path = storage_path.with_file_name("config.json");
+    let data_dir = storage_path
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let config = match Config::load(&config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: Failed to load config: {}", e);
+            std::process::exit(1);
+        }
+    };
+    ui::set_palette(config.palette);
+    ui::set_stale_after_days(config.stale_after_days);
+
+    // By default saves retry briefly when another process holds the lock
+    let lock_wait = if cli.wait {
+        LockWait::Forever
+    } else if cli.no_wait {
+        LockWait::NoWait
+    } else {
+        LockWait::Retry
+    };
+    // Every save is journaled so an interrupted command can be recovered
+    let intent = Intent {
+        operation: operation_name(&matches),
+        args: std::env::args().skip(1).collect(),
+    };
+    let storage = JsonFileStorage::new(storage_path)
+        .with_lock_wait(lock_wait)
+        .with_intent(intent);
 
     let mut store = match storage.load() {
         Ok(store) => store,
@@ -239,77 +1187,109 @@ fn main() {
         }
     };
 
-    match cli.command {
-        Some(Commands::Today) => {
-            let today = jiff::Zoned::now().date();
-
-            // Collect today tasks
-            let mut today_regular: Vec<_> = store
-                .get_active_tasks()
-                .filter(|t| matches!(t.when, When::Today { evening: false }))
-                .filter(|t| t.completed_at.is_none())
-                .collect();
-
-            let mut today_evening: Vec<_> = store
-                .get_active_tasks()
-                .filter(|t| matches!(t.when, When::Today { evening: true }))
-                .filter(|t| t.completed_at.is_none())
-                .collect();
-
-            // Collect overdue tasks
-            let mut overdue_tasks: Vec<_> = store
-                .get_active_tasks()
-                .filter(|t| {
-                    if let When::Scheduled { date } = t.when {
-                        date < today && t.completed_at.is_none()
-                    } else {
-                        false
-                    }
-                })
-                .collect();
+    // Badge output is read by scripts that poll it, so it stays a bare count
+    if !matches!(
+        cli.command,
+        Some(Commands::Recover { .. } | Commands::Badge { .. })
+    ) {
+        warn_about_interrupted_command(&storage);
+    }
 
-            // Sort by task number
-            today_regular.sort_by_key(|t| t.task_number);
-            today_evening.sort_by_key(|t| t.task_number);
-            overdue_tasks.sort_by_key(|t| t.task_number);
+    // Deferred tasks whose date has arrived come back before any task list is shown
+    if shows_task_list(&cli.command) {
+        match surface_deferred_tasks(&mut store, &storage) {
+            Ok(surfaced) if !surfaced.is_empty() => {
+                println!(
+                    "{}",
+                    format!(
+                        "↑ {} deferred {} moved to Today",
+                        surfaced.len(),
+                        if surfaced.len() == 1 { "task" } else { "tasks" }
+                    )
+                    .dimmed()
+                );
+            }
+            Ok(_) => {}
+            Err(SurfaceDeferredTasksError::Storage(e)) => {
+                eprintln!("Error: Failed to save deferred tasks: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 
-            let total = today_regular.len() + today_evening.len() + overdue_tasks.len();
+    // Deadline promotion runs whenever a task list is about to be shown
+    if let Some(within_days) = config.promote_deadline_days
+        && shows_task_list(&cli.command)
+    {
+        let params = PromoteDueTasksParameters { within_days };
+        match promote_due_tasks(&mut store, &storage, params) {
+            Ok(promoted) if !promoted.is_empty() => {
+                println!(
+                    "{}",
+                    format!(
+                        "↑ {} {} moved to Today (deadline within {} days)",
+                        promoted.len(),
+                        if promoted.len() == 1 { "task" } else { "tasks" },
+                        within_days
+                    )
+                    .dimmed()
+                );
+            }
+            Ok(_) => {}
+            Err(PromoteDueTasksError::Storage(e)) => {
+                eprintln!("Error: Failed to save promoted tasks: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 
-            if total == 0 {
-                println!("No tasks for today");
+    // Views only see the workspace's slice of the store; they never save it
+    let workspace = cli.workspace.as_ref().map(|name| {
+        config.workspaces.get(name).cloned().unwrap_or_else(|| {
+            eprintln!("Error: Workspace '{}' not found", name);
+            if config.workspaces.is_empty() {
+                eprintln!(
+                    "\nDefine workspaces under \"workspaces\" in {}",
+                    config_path.display()
+                );
             } else {
-                ui::render_view_header(&format!("Today ({})", today.strftime("%b %d")), total);
-
-                // Show overdue first if any
-                if !overdue_tasks.is_empty() {
-                    ui::render_section_header("Overdue");
-                    for task in overdue_tasks {
-                        ui::render_task_line(task, &store, true);
-                    }
-                }
-
-                // Show regular today tasks
-                if !today_regular.is_empty() {
-                    for task in today_regular {
-                        ui::render_task_line(task, &store, false);
-                    }
-                }
-
-                // Show evening tasks
-                if !today_evening.is_empty() {
-                    ui::render_section_header("Evening");
-                    for task in today_evening {
-                        ui::render_task_line(task, &store, false);
-                    }
+                eprintln!("\nAvailable workspaces:");
+                for name in config.workspaces.keys() {
+                    eprintln!("  - {}", name);
                 }
             }
+            std::process::exit(1);
+        })
+    });
+    if let Some(workspace) = &workspace
+        && is_workspace_view(&cli.command)
+    {
+        store = workspace.scope(&store).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            eprintln!("\nFix the workspace in {}", config_path.display());
+            std::process::exit(1);
+        });
+    }
+
+    match cli.command {
+        Some(Commands::Today {
+            evening_only,
+            context,
+        }) => {
+            let options = TodayViewOptions {
+                context: context.as_deref(),
+                evening_only,
+            };
+            show_today(&store, &config, &options);
+            print_inbox_nudge(&store, &config, "tdo inbox");
         }
-        Some(Commands::Inbox) => {
+        Some(Commands::Inbox { context }) => {
             // Filter inbox tasks
             let inbox_tasks: Vec<_> = store
                 .get_active_tasks()
                 .filter(|t| matches!(t.when, When::Inbox))
                 .filter(|t| t.completed_at.is_none())
+                .filter(|t| context.as_deref().is_none_or(|c| t.has_context(c)))
                 .collect();
 
             // Display
@@ -317,35 +1297,188 @@ fn main() {
                 println!("Inbox is empty");
             } else {
                 ui::render_view_header("Inbox", inbox_tasks.len());
-                for task in inbox_tasks {
+                for task in &inbox_tasks {
                     ui::render_task_line(task, &store, false);
                 }
+                if config.show_footer {
+                    ui::render_view_footer(&inbox_tasks, &store);
+                }
+            }
+
+            print_inbox_nudge(&store, &config, "tdo move <id>");
+        }
+        Some(Commands::Badge { format }) => {
+            let count = BadgeCount::from_store(&store, jiff::Zoned::now().date());
+            println!("{}", count.render(format));
+        }
+        Some(Commands::Dashboard) => {
+            let today = jiff::Zoned::now().date();
+            let mut areas: Vec<_> = store
+                .get_active_areas()
+                .filter(|a| a.archived_at.is_none())
+                .collect();
+
+            if areas.is_empty() {
+                println!("No areas found");
+                println!("  {}", "Create one with `tdo area new \"Name\"`".dimmed());
+                return;
+            }
+            areas.sort_by_key(|a| a.display_order());
+
+            println!("{} ({})\n", "DASHBOARD".cyan(), today.strftime("%a, %b %d"));
+
+            let name_width = areas
+                .iter()
+                .map(|a| a.name.chars().count())
+                .max()
+                .unwrap_or(0)
+                .max("AREA".len());
+            println!(
+                "    {}",
+                format!(
+                    "{:<name_width$}  {:>8}  {:>5}  {:>7}  {:>13}",
+                    "AREA", "PROJECTS", "TODAY", "OVERDUE", "DUE THIS WEEK"
+                )
+                .dimmed()
+            );
+
+            // Counts are padded before coloring so escape codes don't skew the columns
+            let cell = |count: usize, width: usize, style: fn(&str) -> ColoredString| {
+                let text = format!("{:>width$}", count);
+                if count == 0 {
+                    text.dimmed()
+                } else {
+                    style(&text)
+                }
+            };
+            for area in areas {
+                let summary = AreaSummary::from_store(&store, area, today);
+                let name = format!("{:<name_width$}", area.name);
+                println!(
+                    "  {} {}  {}  {}  {}  {}",
+                    "•".green(),
+                    if summary.is_quiet() {
+                        name.dimmed()
+                    } else {
+                        name.bold()
+                    },
+                    cell(summary.projects, 8, |text| text.normal()),
+                    cell(summary.today, 5, |text| text.cyan()),
+                    cell(summary.overdue, 7, ui::alert),
+                    cell(summary.due_this_week, 13, |text| text.yellow())
+                );
+            }
+        }
+        Some(Commands::Now) => {
+            let now = jiff::Zoned::now();
+            let today = now.date();
+
+            let mut now_tasks: Vec<_> = store
+                .get_active_tasks()
+                .filter(|t| t.completed_at.is_none() && !t.is_deferred())
+                .filter(|t| {
+                    t.pinned
+                        || t.deadline.is_some_and(|deadline| deadline <= today)
+                        || t.reminder.as_ref().is_some_and(|reminder| {
+                            reminder.date() == today && reminder.hour() == now.hour()
+                        })
+                })
+                .collect();
+            now_tasks.sort_by_key(|t| (!t.pinned, t.deadline.is_none(), t.task_number));
+
+            if now_tasks.is_empty() {
+                println!("Nothing needs attention right now");
+            } else {
+                ui::render_view_header("Now", now_tasks.len());
+                for task in &now_tasks {
+                    ui::render_task_line(task, &store, ui::is_overdue(task));
+                }
+
+                if config.show_footer {
+                    ui::render_view_footer(&now_tasks, &store);
+                }
+            }
+        }
+        Some(Commands::NextActions) => {
+            // Projects waiting on another one or shelved aren't actionable yet, nor stalled
+            let mut projects: Vec<_> = store
+                .get_active_projects()
+                .filter(|p| {
+                    p.completed_at.is_none()
+                        && p.incubated_at.is_none()
+                        && !store.is_project_archived(p)
+                })
+                .filter(|p| store.get_blocking_projects(p.id).is_empty())
+                .collect();
+            projects.sort_by_key(|p| p.name.to_lowercase());
+
+            let (next_actions, stalled): (Vec<_>, Vec<_>) = projects
+                .iter()
+                .map(|p| (*p, store.get_next_action(p.id)))
+                .partition(|(_, task)| task.is_some());
+            let next_actions: Vec<&Task> =
+                next_actions.into_iter().filter_map(|(_, t)| t).collect();
+
+            if next_actions.is_empty() && stalled.is_empty() {
+                println!("No active projects");
+                return;
+            }
+
+            if !next_actions.is_empty() {
+                ui::render_view_header("Next Actions", next_actions.len());
+                for task in &next_actions {
+                    ui::render_task_line(task, &store, ui::is_overdue(task));
+                }
+            }
+
+            if !stalled.is_empty() {
+                ui::render_section_header(&format!("Stalled — {}", stalled.len()));
+                for (project, _) in stalled {
+                    println!(
+                        "  {} {}  {}",
+                        "•".yellow(),
+                        project.name,
+                        format!("tdo project view {}", project.slug).dimmed()
+                    );
+                }
+            }
+
+            if config.show_footer && !next_actions.is_empty() {
+                ui::render_view_footer(&next_actions, &store);
             }
         }
-        Some(Commands::Anytime) => {
+        Some(Commands::Anytime { energy, context }) => {
             // Filter anytime tasks
-            let anytime_tasks: Vec<_> = store
+            let mut anytime_tasks: Vec<_> = store
                 .get_active_tasks()
                 .filter(|t| matches!(t.when, When::Anytime))
-                .filter(|t| t.completed_at.is_none())
+                .filter(|t| t.completed_at.is_none() && !t.is_deferred())
+                .filter(|t| !store.is_task_on_hold(t))
+                .filter(|t| energy.is_none() || t.energy == energy)
+                .filter(|t| context.as_deref().is_none_or(|c| t.has_context(c)))
                 .collect();
+            anytime_tasks.sort_by_key(|t| (!t.pinned, t.task_number));
 
             // Display
             if anytime_tasks.is_empty() {
                 println!("No anytime tasks");
             } else {
                 ui::render_view_header("Anytime", anytime_tasks.len());
-                for task in anytime_tasks {
+                for task in &anytime_tasks {
                     ui::render_task_line(task, &store, false);
                 }
+                if config.show_footer {
+                    ui::render_view_footer(&anytime_tasks, &store);
+                }
             }
         }
-        Some(Commands::Someday) => {
+        Some(Commands::Someday { context }) => {
             // Filter someday tasks
             let someday_tasks: Vec<_> = store
                 .get_active_tasks()
                 .filter(|t| matches!(t.when, When::Someday))
                 .filter(|t| t.completed_at.is_none())
+                .filter(|t| context.as_deref().is_none_or(|c| t.has_context(c)))
                 .collect();
 
             // Display
@@ -353,9 +1486,12 @@ fn main() {
                 println!("No someday tasks");
             } else {
                 ui::render_view_header("Someday", someday_tasks.len());
-                for task in someday_tasks {
+                for task in &someday_tasks {
                     ui::render_task_line(task, &store, false);
                 }
+                if config.show_footer {
+                    ui::render_view_footer(&someday_tasks, &store);
+                }
             }
         }
         Some(Commands::All) => {
@@ -377,12 +1513,9 @@ fn main() {
                         When::Today { evening: true } => "Today (Evening)",
                         When::Someday => "Someday",
                         When::Anytime => "Anytime",
-                        When::Scheduled { date: _ } => "Scheduled",
+                        When::Scheduled { .. } => "Scheduled",
                     };
-                    grouped
-                        .entry(group.to_string())
-                        .or_insert_with(Vec::new)
-                        .push(task);
+                    grouped.entry(group.to_string()).or_default().push(task);
                 }
 
                 // Display in a logical order
@@ -404,9 +1537,69 @@ fn main() {
                         }
                     }
                 }
+
+                if config.show_footer {
+                    ui::render_view_footer(&all_tasks, &store);
+                }
+            }
+        }
+        Some(Commands::Tomorrow { context }) => {
+            let tomorrow = jiff::Zoned::now()
+                .date()
+                .tomorrow()
+                .expect("tomorrow should be valid");
+
+            // Tasks scheduled for tomorrow
+            let mut scheduled_tasks: Vec<_> = store
+                .get_active_tasks()
+                .filter(|t| t.completed_at.is_none())
+                .filter(|t| matches!(t.when, When::Scheduled { date, .. } if date == tomorrow))
+                .filter(|t| context.as_deref().is_none_or(|c| t.has_context(c)))
+                .collect();
+
+            // Tasks due tomorrow that aren't already scheduled for it
+            let mut due_tasks: Vec<_> = store
+                .get_active_tasks()
+                .filter(|t| t.completed_at.is_none())
+                .filter(|t| t.deadline == Some(tomorrow))
+                .filter(|t| !matches!(t.when, When::Scheduled { date, .. } if date == tomorrow))
+                .filter(|t| context.as_deref().is_none_or(|c| t.has_context(c)))
+                .collect();
+
+            scheduled_tasks.sort_by_key(|t| t.task_number);
+            due_tasks.sort_by_key(|t| t.task_number);
+
+            let total = scheduled_tasks.len() + due_tasks.len();
+
+            if total == 0 {
+                println!("No tasks for tomorrow");
+            } else {
+                ui::render_view_header(
+                    &format!("Tomorrow · {}", tomorrow.strftime("%b %d")),
+                    total,
+                );
+
+                if !scheduled_tasks.is_empty() {
+                    ui::render_section_header(&format!("Scheduled — {}", scheduled_tasks.len()));
+                    for task in &scheduled_tasks {
+                        ui::render_task_line(task, &store, false);
+                    }
+                }
+
+                if !due_tasks.is_empty() {
+                    ui::render_section_header(&format!("Due — {}", due_tasks.len()));
+                    for task in &due_tasks {
+                        ui::render_task_line(task, &store, false);
+                    }
+                }
+
+                if config.show_footer {
+                    let shown: Vec<_> = scheduled_tasks.iter().chain(&due_tasks).copied().collect();
+                    ui::render_view_footer(&shown, &store);
+                }
             }
         }
-        Some(Commands::Upcoming) => {
+        Some(Commands::Upcoming { context }) => {
             use jiff::civil::Date;
             use std::collections::BTreeMap;
 
@@ -416,84 +1609,258 @@ fn main() {
             let upcoming_tasks: Vec<_> = store
                 .get_active_tasks()
                 .filter(|t| {
-                    if let When::Scheduled { date } = t.when {
+                    if let When::Scheduled { date, .. } = t.when {
                         date > today && t.completed_at.is_none()
                     } else {
                         false
                     }
                 })
+                .filter(|t| context.as_deref().is_none_or(|c| t.has_context(c)))
+                .filter(|t| !store.is_task_archived(t))
+                .collect();
+
+            // Deadlines of open projects still ahead; projects have no context
+            let project_deadlines: Vec<_> = store
+                .get_active_projects()
+                .filter(|p| {
+                    p.completed_at.is_none() && !store.is_project_archived(p) && context.is_none()
+                })
+                .filter_map(|p| {
+                    p.deadline
+                        .filter(|date| *date > today)
+                        .map(|date| (date, p))
+                })
                 .collect();
 
-            if upcoming_tasks.is_empty() {
+            if upcoming_tasks.is_empty() && project_deadlines.is_empty() {
                 println!("No upcoming tasks");
             } else {
                 // Group by date
                 let mut grouped: BTreeMap<Date, Vec<&crate::models::task::Task>> = BTreeMap::new();
+                let mut projects_due: BTreeMap<Date, Vec<&Project>> = BTreeMap::new();
 
                 for task in &upcoming_tasks {
-                    if let When::Scheduled { date } = task.when {
-                        grouped.entry(date).or_insert_with(Vec::new).push(task);
+                    if let When::Scheduled { date, .. } = task.when {
+                        grouped.entry(date).or_default().push(task);
                     }
                 }
+                for (date, project) in &project_deadlines {
+                    grouped.entry(*date).or_default();
+                    projects_due.entry(*date).or_default().push(project);
+                }
 
                 ui::render_view_header("Upcoming", upcoming_tasks.len());
 
-                // Display by date
+                // Display by date: project deadlines first, then the tasks with
+                // the day's evening tasks after the rest
                 for (date, mut tasks) in grouped {
-                    tasks.sort_by_key(|t| t.task_number);
+                    tasks.sort_by_key(|t| (t.when.is_evening(), t.task_number));
                     ui::render_section_header(&ui::format_date_header(date));
-                    for task in tasks {
+                    let mut projects = projects_due.remove(&date).unwrap_or_default();
+                    projects.sort_by(|a, b| a.name.cmp(&b.name));
+                    for project in projects {
+                        println!(
+                            "      ⚑  {} {}",
+                            project.name.bold(),
+                            "project deadline".dimmed()
+                        );
+                    }
+                    let evening_start = tasks.iter().position(|t| t.when.is_evening());
+                    for (index, task) in tasks.iter().enumerate() {
+                        if Some(index) == evening_start {
+                            println!("\n     {}", "Evening".dimmed());
+                        }
                         ui::render_task_line(task, &store, false);
                     }
                 }
+
+                if config.show_footer {
+                    ui::render_view_footer(&upcoming_tasks, &store);
+                }
             }
         }
-        Some(Commands::Logbook) => {
-            use std::collections::BTreeMap;
+        Some(Commands::Incubate { slug, resume }) => {
+            let params = IncubateProjectParameters { slug, resume };
 
-            // Collect completed tasks from last 14 days
-            let completed_tasks: Vec<_> = store
-                .tasks
-                .values()
-                .filter(|t| {
-                    if let Some(completed_at) = t.completed_at {
-                        ui::is_within_days(completed_at, 14)
-                    } else {
-                        false
-                    }
-                })
+            match incubate_project(&mut store, &storage, params) {
+                Ok(project) if resume => {
+                    println!("✓ Project resumed: {}", project.name);
+                }
+                Ok(project) => {
+                    println!("✓ Project incubated: {}", project.name);
+                    println!(
+                        "  {}",
+                        format!("Resume it with `tdo incubate {} --resume`", project.slug).dimmed()
+                    );
+                }
+                Err(IncubateProjectError::ProjectNotFound(slug)) => {
+                    eprintln!("Error: Project '{}' not found", slug);
+                    eprintln!("\nUse `tdo project list` to see project slugs");
+                    std::process::exit(1);
+                }
+                Err(IncubateProjectError::AlreadyIncubated(name)) => {
+                    eprintln!("Error: Project '{}' is already in the incubator", name);
+                    std::process::exit(1);
+                }
+                Err(IncubateProjectError::NotIncubated(name)) => {
+                    eprintln!("Error: Project '{}' is not in the incubator", name);
+                    std::process::exit(1);
+                }
+                Err(IncubateProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Incubator) => {
+            let mut projects: Vec<_> = store
+                .get_active_projects()
+                .filter(|p| p.incubated_at.is_some())
                 .collect();
 
-            if completed_tasks.is_empty() {
-                println!("No completed tasks in the last 14 days");
-            } else {
-                // Group by month
-                let mut grouped: BTreeMap<(i16, i8), Vec<&crate::models::task::Task>> =
-                    BTreeMap::new();
+            if projects.is_empty() {
+                println!("The incubator is empty");
+                return;
+            }
 
-                for task in &completed_tasks {
-                    if let Some(completed_at) = task.completed_at {
-                        let year_month = ui::get_year_month(completed_at);
-                        grouped
-                            .entry(year_month)
-                            .or_insert_with(Vec::new)
-                            .push(task);
-                    }
+            // Longest shelved first, as the likeliest to revive or drop
+            projects.sort_by_key(|p| p.incubated_at);
+            println!(
+                "{} ({} {})\n",
+                "INCUBATOR".cyan(),
+                projects.len(),
+                if projects.len() == 1 {
+                    "project"
+                } else {
+                    "projects"
                 }
+            );
 
-                ui::render_view_header("Logbook", completed_tasks.len());
-
-                // Display by month (most recent first)
-                for (_year_month, tasks) in grouped.iter().rev() {
-                    // Sort tasks within month by completion time (most recent first)
-                    let mut sorted_tasks = tasks.clone();
-                    sorted_tasks
-                        .sort_by(|a, b| b.completed_at.unwrap().cmp(&a.completed_at.unwrap()));
+            let today = jiff::Zoned::now().date();
+            for project in projects {
+                let shelved_on = ui::get_local_date(project.incubated_at.expect("incubated"));
+                let open_tasks = store
+                    .get_tasks_for_project(project.id)
+                    .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                    .count();
+
+                println!("{} {}", "•".green(), project.name.bold());
+                println!(
+                    "    {}",
+                    format!(
+                        "shelved {} ({}d ago) · {} open {}",
+                        shelved_on.strftime("%b %d, %Y"),
+                        (today - shelved_on).get_days(),
+                        open_tasks,
+                        if open_tasks == 1 { "task" } else { "tasks" }
+                    )
+                    .dimmed()
+                );
+            }
+        }
+        Some(Commands::Logbook {
+            export,
+            month,
+            include_archive,
+        }) => {
+            use jiff::civil::Date;
+            use std::collections::BTreeMap;
+
+            // Resolve the requested month, if any
+            let month_range = match &month {
+                Some(month_str) => match export::parse_month(month_str) {
+                    Some(range) => Some(range),
+                    None => {
+                        eprintln!("Error: Invalid month '{}'", month_str);
+                        eprintln!("\nExpected format: YYYY-MM (e.g., 2025-06)");
+                        std::process::exit(1);
+                    }
+                },
+                None => None,
+            };
+
+            // Archived tasks that are back in the store (e.g., after an undo) count once
+            let archived: Vec<Task> = if include_archive {
+                match storage.load_archive() {
+                    Ok(tasks) => tasks
+                        .into_iter()
+                        .filter(|t| !store.tasks.contains_key(&t.id))
+                        .collect(),
+                    Err(e) => {
+                        eprintln!("Error: Failed to read archive: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                vec![]
+            };
+
+            // Collect completed tasks from the requested month, or the last 14 days
+            let completed_tasks: Vec<_> = store
+                .tasks
+                .values()
+                .chain(&archived)
+                .filter(|t| match (t.completed_at, month_range) {
+                    (Some(completed_at), Some((start, end))) => {
+                        let date = ui::get_local_date(completed_at);
+                        date >= start && date < end
+                    }
+                    (Some(completed_at), None) => ui::is_within_days(completed_at, 14),
+                    (None, _) => false,
+                })
+                .collect();
+
+            let period = match month_range {
+                Some((start, _)) => start.strftime("%B %Y").to_string(),
+                None => "the last 14 days".to_string(),
+            };
+
+            if let Some(format) = export {
+                let title = format!("Logbook — {}", period);
+                print!(
+                    "{}",
+                    export::export_logbook(format, &title, &completed_tasks, &store)
+                );
+                return;
+            }
+
+            if completed_tasks.is_empty() {
+                if month_range.is_some() {
+                    println!("No completed tasks in {}", period);
+                } else {
+                    println!("No completed tasks in the last 14 days");
+                }
+            } else {
+                // Group by completion day
+                let mut grouped: BTreeMap<Date, Vec<&crate::models::task::Task>> = BTreeMap::new();
+
+                for task in &completed_tasks {
+                    if let Some(completed_at) = task.completed_at {
+                        grouped
+                            .entry(ui::get_local_date(completed_at))
+                            .or_default()
+                            .push(task);
+                    }
+                }
 
-                    // Use the first task's timestamp to format the month header
-                    let month_header =
-                        ui::format_month_header(sorted_tasks[0].completed_at.unwrap());
-                    ui::render_section_header(&month_header);
+                ui::render_view_header("Logbook", completed_tasks.len());
+
+                // Display by day (most recent first)
+                for (date, tasks) in grouped.iter().rev() {
+                    // Sort tasks within day by completion time (most recent first)
+                    let mut sorted_tasks = tasks.clone();
+                    sorted_tasks.sort_by_key(|t| std::cmp::Reverse(t.completed_at.unwrap()));
+
+                    ui::render_section_header(&format!(
+                        "{} — {} {}",
+                        ui::format_past_date_header(*date),
+                        sorted_tasks.len(),
+                        if sorted_tasks.len() == 1 {
+                            "task"
+                        } else {
+                            "tasks"
+                        }
+                    ));
 
                     for task in sorted_tasks {
                         ui::render_task_line_with_completion_date(task, &store, false);
@@ -501,7 +1868,277 @@ fn main() {
                 }
             }
         }
-        Some(Commands::Trash) => {
+        Some(Commands::Archive { older_than }) => {
+            let params = ArchiveTasksParameters {
+                older_than: older_than.clone(),
+            };
+
+            match archive_tasks(&mut store, &storage, params) {
+                Ok(archived) if archived.is_empty() => {
+                    println!("✓ No tasks completed more than {} ago", older_than);
+                }
+                Ok(archived) => {
+                    println!(
+                        "✓ Archived {} completed {}",
+                        archived.len(),
+                        if archived.len() == 1 { "task" } else { "tasks" }
+                    );
+                    println!(
+                        "  {}",
+                        "Use `tdo logbook --include-archive` to see them".dimmed()
+                    );
+                }
+                Err(ArchiveTasksError::InvalidAge(age_str, error)) => {
+                    eprintln!("Error: Invalid age '{}': {}", age_str, error);
+                    eprintln!("\nExamples: 90d, 12w, 6mo");
+                    std::process::exit(1);
+                }
+                Err(ArchiveTasksError::Storage(e)) => {
+                    eprintln!("Error: Failed to archive tasks: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Report(ReportCommands::Time { days })) => {
+            if days < 1 {
+                eprintln!("Error: --days must be at least 1");
+                std::process::exit(1);
+            }
+
+            let rows = stats::tracked_time_by_project(&store, days);
+
+            if rows.is_empty() {
+                println!("No time tracked in the last {} days", days);
+            } else {
+                let total: jiff::SignedDuration = rows.iter().map(|r| r.tracked).sum();
+                println!(
+                    "{} ({} in the last {} days)\n",
+                    "TIME TRACKED".cyan(),
+                    ui::format_tracked_time(total),
+                    days
+                );
+
+                let label_width = rows
+                    .iter()
+                    .map(|r| r.label.chars().count())
+                    .max()
+                    .unwrap_or(0);
+
+                for row in rows {
+                    println!(
+                        "  {} {:<width$}  {:>8}  {}",
+                        "•".green(),
+                        row.label.bold(),
+                        ui::format_tracked_time(row.tracked),
+                        format!(
+                            "({} {})",
+                            row.tasks,
+                            if row.tasks == 1 { "task" } else { "tasks" }
+                        )
+                        .dimmed(),
+                        width = label_width
+                    );
+                }
+            }
+        }
+        Some(Commands::Stats { by, days }) => {
+            if days < 1 {
+                eprintln!("Error: --days must be at least 1");
+                std::process::exit(1);
+            }
+
+            let rows = stats::cycle_times(&store, by, days);
+
+            if rows.is_empty() {
+                println!("No completed tasks in the last {} days", days);
+            } else {
+                println!(
+                    "{} (median days to completion, last {} days)\n",
+                    "CYCLE TIME".cyan(),
+                    days
+                );
+
+                let label_width = rows
+                    .iter()
+                    .map(|r| r.label.chars().count())
+                    .max()
+                    .unwrap_or(0);
+
+                for row in rows {
+                    println!(
+                        "  {} {:<width$}  {:>6.1}d  {}",
+                        "•".green(),
+                        row.label.bold(),
+                        row.median_days,
+                        format!(
+                            "({} {})",
+                            row.completed,
+                            if row.completed == 1 { "task" } else { "tasks" }
+                        )
+                        .dimmed(),
+                        width = label_width
+                    );
+                }
+            }
+        }
+        Some(Commands::Trash {
+            command: Some(TrashCommands::Restore { project, since }),
+        }) => {
+            let params = RestoreFromTrashParameters { project, since };
+
+            match restore_from_trash(&mut store, &storage, params) {
+                Ok(result) => {
+                    let total = result.areas.len() + result.projects.len() + result.tasks.len();
+                    println!(
+                        "✓ Restored {} {}",
+                        total,
+                        if total == 1 { "item" } else { "items" }
+                    );
+                    for area in &result.areas {
+                        println!("  Area: {}", area.name);
+                    }
+                    for project in &result.projects {
+                        println!("  Project: {}", project.name);
+                    }
+                    for task in &result.tasks {
+                        println!("  #{}  {}", task.task_number, task.title);
+                    }
+
+                    // Restored items may still sit in a trashed container
+                    let still_trashed: std::collections::BTreeSet<_> = result
+                        .tasks
+                        .iter()
+                        .filter_map(|t| t.project_id)
+                        .chain(result.projects.iter().map(|p| p.id))
+                        .filter_map(|id| store.get_project(id))
+                        .filter_map(|p| {
+                            if p.deleted_at.is_some() {
+                                Some(format!("Project '{}'", p.name))
+                            } else {
+                                p.area_id
+                                    .and_then(|id| store.get_area(id))
+                                    .filter(|a| a.deleted_at.is_some())
+                                    .map(|a| format!("Area '{}'", a.name))
+                            }
+                        })
+                        .collect();
+                    for container in still_trashed {
+                        println!(
+                            "  {}",
+                            format!("{} is still in the trash", container).yellow()
+                        );
+                    }
+                }
+                Err(RestoreFromTrashError::NothingToRestore) => {
+                    println!("Nothing in the trash matches");
+                }
+                Err(RestoreFromTrashError::InvalidDate(date_str, error)) => {
+                    eprintln!("Error: Invalid date '{}': {}", date_str, error);
+                    eprintln!("\nExpected format: YYYY-MM-DD (e.g., 2025-03-01)");
+                    std::process::exit(1);
+                }
+                Err(RestoreFromTrashError::ProjectNotFound(name)) => {
+                    eprintln!("Error: Project '{}' not found", name);
+                    std::process::exit(1);
+                }
+                Err(RestoreFromTrashError::AmbiguousProjectName(names)) => {
+                    eprintln!("Error: Project name is ambiguous. Multiple projects found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(RestoreFromTrashError::Storage(e)) => {
+                    eprintln!("Error: Failed to save restored items: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Trash {
+            command: Some(TrashCommands::Empty { older_than, yes }),
+        }) => {
+            let run = |store: &mut Store, apply| {
+                let params = EmptyTrashParameters {
+                    older_than: older_than.clone(),
+                    apply,
+                };
+                match empty_trash(store, &storage, params) {
+                    Ok(result) => result,
+                    Err(EmptyTrashError::InvalidAge(age_str, error)) => {
+                        eprintln!("Error: Invalid age '{}': {}", age_str, error);
+                        eprintln!("\nExamples: 30d, 2w, 6mo");
+                        std::process::exit(1);
+                    }
+                    Err(EmptyTrashError::Storage(e)) => {
+                        eprintln!("Error: Failed to save store: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            };
+
+            let preview = run(&mut store, false);
+            if preview.total() == 0 {
+                match &older_than {
+                    Some(age) => println!("✓ Nothing in the trash is older than {}", age),
+                    None => println!("✓ Trash is already empty"),
+                }
+                return;
+            }
+
+            let summary = purge_summary(&preview);
+            if !yes {
+                confirm_purge(&summary);
+            }
+
+            let purged = run(&mut store, true);
+            println!("✓ Permanently deleted {}", purge_summary(&purged));
+        }
+        Some(Commands::Trash {
+            command: Some(TrashCommands::Purge { item, yes }),
+        }) => {
+            let run = |store: &mut Store, apply| {
+                let params = PurgeItemParameters {
+                    item: item.clone(),
+                    apply,
+                };
+                match purge_item(store, &storage, params) {
+                    Ok(result) => result,
+                    Err(PurgeItemError::NotFound(name)) => {
+                        eprintln!("Error: No trashed task, project or area matches '{}'", name);
+                        eprintln!("\nRun `tdo trash` to see what's in the trash");
+                        std::process::exit(1);
+                    }
+                    Err(PurgeItemError::NotInTrash(label)) => {
+                        eprintln!("Error: {} is not in the trash", label);
+                        eprintln!("\nOnly deleted items can be purged; delete it first");
+                        std::process::exit(1);
+                    }
+                    Err(PurgeItemError::Ambiguous(labels)) => {
+                        eprintln!("Error: Name is ambiguous. Multiple trashed items found:");
+                        for label in labels {
+                            eprintln!("  - {}", label);
+                        }
+                        eprintln!("\nPlease be more specific.");
+                        std::process::exit(1);
+                    }
+                    Err(PurgeItemError::Storage(e)) => {
+                        eprintln!("Error: Failed to save store: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            };
+
+            let preview = run(&mut store, false);
+            let summary = purge_summary(&preview);
+            if !yes {
+                confirm_purge(&summary);
+            }
+
+            let purged = run(&mut store, true);
+            println!("✓ Permanently deleted {}", purge_summary(&purged));
+        }
+        Some(Commands::Trash { command: None }) => {
             // Collect deleted items
             let deleted_tasks: Vec<_> = store.get_deleted_tasks().collect();
             let deleted_projects: Vec<_> = store.get_deleted_projects().collect();
@@ -539,144 +2176,318 @@ fn main() {
                 }
             }
         }
+        Some(Commands::Show { task_number }) => match store.get_task_by_number(task_number) {
+            Some(task) => ui::render_task_detail(task, &store),
+            None => {
+                eprintln!("Error: Task #{} not found", task_number);
+                std::process::exit(1);
+            }
+        },
+        Some(Commands::Remind { notify }) => {
+            let now = jiff::Timestamp::now();
+
+            // Snoozing a task silences its reminder too
+            let mut due: Vec<_> = store
+                .get_active_tasks()
+                .filter(|t| t.completed_at.is_none() && !t.is_snoozed())
+                .filter(|t| t.reminder.as_ref().is_some_and(|r| r.timestamp() <= now))
+                .collect();
+            due.sort_by_key(|t| t.reminder.as_ref().map(|r| r.timestamp()));
+
+            if due.is_empty() {
+                println!("No reminders due");
+                return;
+            }
+
+            ui::render_view_header("Reminders", due.len());
+            for task in due {
+                ui::render_task_line(task, &store, ui::is_overdue(task));
+
+                if notify && let Err(e) = send_desktop_notification("tdo reminder", &task.title) {
+                    eprintln!("Error: Failed to send notification: {}", e);
+                    eprintln!(
+                        "\nDesktop notifications use notify-send (Linux) or osascript (macOS)"
+                    );
+                    std::process::exit(1);
+                }
+            }
+        }
         Some(Commands::Add {
             title,
+            stdin,
             today,
+            tomorrow,
             evening,
             someday,
             anytime,
             when: when_str,
             deadline,
             project,
+            create_project,
             area,
+            area_override,
+            create_area,
             tag,
             notes,
+            url,
+            fetch_title,
+            energy,
+            context,
+            remind,
+            defer,
+            estimate,
+            raw,
+            id,
+            idempotency_key,
         }) => {
             // Parse when flags
-            let when = match When::from_command_flags(today, evening, someday, anytime, when_str) {
-                Ok(w) => w,
-                Err(WhenInstantiationError::ScheduleAtIncorrect(date_str)) => {
-                    eprintln!("Error: Invalid schedule date format: '{}'", date_str);
-                    eprintln!(
-                        "\nExpected format: YYYY-MM-DD (e.g., 2025-03-01) or relative dates like 'friday', 'next monday'"
-                    );
-                    std::process::exit(1);
-                }
-                Err(WhenInstantiationError::ConflictingFlags(flags)) => {
-                    eprintln!("Error: Cannot use multiple scheduling flags together");
-                    eprintln!("\nConflicting flags provided: {}", flags.join(", "));
-                    eprintln!("\nPlease use only one of:");
-                    eprintln!("  --today       Schedule for today");
-                    eprintln!("  --someday     Defer to someday");
-                    eprintln!("  --anytime     Available anytime");
-                    eprintln!("  --when DATE   Schedule for a specific date");
-                    std::process::exit(1);
-                }
-                Err(WhenInstantiationError::EveningWithoutToday) => {
-                    eprintln!("Error: The --evening flag can only be used with --today");
-                    eprintln!("\nExample: tdo add 'Review PRs' --today --evening");
-                    std::process::exit(1);
-                }
+            let when_given =
+                today || tomorrow || evening || someday || anytime || when_str.is_some();
+            let when =
+                When::from_command_flags(today, tomorrow, evening, someday, anytime, when_str)
+                    .unwrap_or_else(|e| exit_with_when_error(e));
+
+            let titles = match title {
+                Some(title) => vec![title],
+                None => read_titles_from_stdin(),
             };
 
-            // Build parameters
-            let params = AddTaskParameters {
-                title: title.clone(),
-                notes,
-                when,
-                deadline,
-                project,
-                area,
-                tags: tag,
+            if titles.is_empty() {
+                eprintln!("Error: No task titles found on stdin");
+                std::process::exit(1);
+            }
+
+            // Pull inline tokens out of the titles; explicit flags win over them
+            let mut captures: Vec<Capture> = titles
+                .into_iter()
+                .map(|title| {
+                    if raw {
+                        Capture {
+                            title,
+                            ..Capture::default()
+                        }
+                    } else {
+                        capture::parse_capture(&title)
+                    }
+                })
+                .collect();
+            if captures.iter().any(|c| c.title.is_empty()) {
+                eprintln!("Error: Task title is empty once the inline tokens are taken out");
+                eprintln!("\nUse --raw to keep #tags, @contexts and the like in the title");
+                std::process::exit(1);
+            }
+
+            // A single task's project token is offered for creation like --project
+            let project = match project {
+                None if !stdin => captures[0].project.take(),
+                project => project,
             };
 
-            // Call service
-            match add_task(&mut store, &storage, params) {
-                Ok(task) => {
-                    println!("✓ Task added: {}", task.title);
-                    println!("  #{}", task.task_number);
-                    if let Some(project_id) = task.project_id
-                        && let Some(project) = store.get_project(project_id)
-                    {
-                        println!("  Project: {}", project.name);
+            // Offer to create a missing project or area instead of failing. Not
+            // with --stdin: the titles already took stdin.
+            let missing_project = project.as_ref().filter(|name| {
+                matches!(
+                    find_project_id(&store, name),
+                    Err(NameLookupError::NotFound)
+                )
+            });
+            let create_project = create_project
+                || (!stdin && missing_project.is_some_and(|name| offer_to_create("Project", name)));
+            let area_name = area.as_ref().or(area_override.as_ref());
+            let missing_area = area_name.filter(|name| {
+                matches!(find_area_id(&store, name), Err(NameLookupError::NotFound))
+            });
+            let create_area = create_area
+                || (!stdin && missing_area.is_some_and(|name| offer_to_create("Area", name)));
+            let new_project = missing_project.filter(|_| create_project).cloned();
+            let new_area = missing_area.filter(|_| create_area).cloned();
+
+            // Build parameters, one set per title sharing the same flags
+            let fetch_title = fetch_title || config.fetch_url_titles;
+            let params: Vec<_> = captures
+                .into_iter()
+                .map(|capture| {
+                    let when = match capture.when {
+                        Some(when_str) if !when_given => When::from_command_flags(
+                            false,
+                            false,
+                            false,
+                            false,
+                            false,
+                            Some(when_str),
+                        )
+                        .unwrap_or_else(|e| exit_with_when_error(e)),
+                        _ => when.clone(),
+                    };
+                    let project = project.clone().or(capture.project);
+
+                    let mut tags = tag.clone();
+                    for captured in capture.tags {
+                        if !tags.iter().any(|t| t.eq_ignore_ascii_case(&captured)) {
+                            tags.push(captured);
+                        }
                     }
-                }
-                Err(AddTaskError::ProjectNotFound(name)) => {
-                    eprintln!("Error: Project '{}' not found", name);
 
-                    // Suggest existing projects if any
-                    let projects: Vec<_> = store.projects.values().collect();
-                    if !projects.is_empty() {
-                        eprintln!("\nAvailable projects:");
-                        for project in projects {
-                            eprintln!("  - {}", project.name);
+                    // New tasks go to the workspace's area unless filed elsewhere
+                    let mut area = area.clone();
+                    if let Some(workspace) = &workspace {
+                        workspace.add_default_tags(&mut tags);
+                        if area.is_none() && project.is_none() && area_override.is_none() {
+                            area = workspace.default_area().map(str::to_string);
                         }
-                    } else {
-                        eprintln!("\nNo projects exist yet. Create one first or omit --project.");
                     }
-                    std::process::exit(1);
-                }
-                Err(AddTaskError::AmbiguousProjectName(names)) => {
-                    eprintln!("Error: Project name is ambiguous. Multiple projects found:");
-                    for name in names {
-                        eprintln!("  - {}", name);
+
+                    AddTaskParameters {
+                        notes: with_link_notes(
+                            notes.clone(),
+                            &capture.title,
+                            url.clone(),
+                            fetch_title,
+                        ),
+                        title: capture.title,
+                        when,
+                        deadline: deadline.clone().or(capture.deadline),
+                        project,
+                        area,
+                        area_override: area_override.clone(),
+                        tags,
+                        context: context.clone().or(capture.context),
+                        energy,
+                        reminder: remind.clone(),
+                        defer_until: defer.clone(),
+                        estimate: estimate.clone(),
+                        create_project,
+                        create_area,
+                        id,
+                        idempotency_key: idempotency_key.clone(),
                     }
-                    eprintln!("\nPlease be more specific.");
-                    std::process::exit(1);
-                }
-                Err(AddTaskError::AreaNotFound(name)) => {
-                    eprintln!("Error: Area '{}' not found", name);
+                })
+                .collect();
 
-                    // Suggest existing areas if any
-                    let areas: Vec<_> = store.areas.values().collect();
-                    if !areas.is_empty() {
-                        eprintln!("\nAvailable areas:");
-                        for area in areas {
-                            eprintln!("  - {}", area.name);
-                        }
-                    } else {
-                        eprintln!("\nNo areas exist yet. Create one first or omit --area.");
+            // Call service
+            let result = if stdin {
+                add_tasks(&mut store, &storage, params)
+            } else {
+                let params = params
+                    .into_iter()
+                    .next()
+                    .expect("one title without --stdin");
+                match add_task(&mut store, &storage, params) {
+                    Ok(AddedTask::Existing(task)) => {
+                        println!("✓ Task already added: {}", task.title);
+                        println!("  #{}", task.task_number);
+                        return;
                     }
-                    std::process::exit(1);
+                    result => result.map(|added| vec![added.task().clone()]),
                 }
-                Err(AddTaskError::AmbiguousAreaName(names)) => {
-                    eprintln!("Error: Area name is ambiguous. Multiple areas found:");
-                    for name in names {
-                        eprintln!("  - {}", name);
-                    }
-                    eprintln!("\nPlease be more specific.");
-                    std::process::exit(1);
+            };
+
+            if result.is_ok() {
+                if let Some(name) = &new_project {
+                    println!("✓ Project created: {}", name);
                 }
-                Err(AddTaskError::InvalidDeadline(date_str, error)) => {
-                    eprintln!("Error: Invalid deadline '{}': {}", date_str, error);
-                    eprintln!("\nExpected format: YYYY-MM-DD (e.g., 2025-03-01)");
-                    std::process::exit(1);
+                if let Some(name) = &new_area {
+                    println!("✓ Area created: {}", name);
                 }
-                Err(AddTaskError::Storage(e)) => {
-                    eprintln!("Error: Failed to save task: {}", e);
-                    std::process::exit(1);
+            }
+
+            match result {
+                Ok(tasks) if stdin => {
+                    println!(
+                        "✓ {} {} added",
+                        tasks.len(),
+                        if tasks.len() == 1 { "task" } else { "tasks" }
+                    );
+                    for task in tasks {
+                        println!("  #{}  {}", task.task_number, task.title);
+                    }
                 }
+                Ok(tasks) => {
+                    for task in tasks {
+                        println!("✓ Task added: {}", task.title);
+                        println!("  #{}", task.task_number);
+                        if let Some(project_id) = task.project_id
+                            && let Some(project) = store.get_project(project_id)
+                        {
+                            println!("  Project: {}", project.name);
+                        }
+                    }
+                }
+                Err(e) => exit_with_add_error(&store, e),
             }
         }
-        Some(Commands::Done {
-            task_number_or_fuzzy_name,
+        Some(Commands::Edit {
+            task_number,
+            title,
+            mut notes,
+            mut clear_notes,
+            notes_editor,
+            deadline,
+            clear_deadline,
+            remind,
+            clear_reminder,
         }) => {
-            // Build parameters
-            let params = CompleteTaskParameters {
-                task_number_or_fuzzy_name,
-            };
+            if notes_editor {
+                let current = match find_task(&store, &task_number) {
+                    Ok(task) => task.notes.clone().unwrap_or_default(),
+                    Err(NameLookupError::NotFound) => {
+                        eprintln!("Error: Task '{}' not found", task_number);
+                        std::process::exit(1);
+                    }
+                    Err(NameLookupError::Ambiguous(titles)) => {
+                        eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                        for title in titles {
+                            eprintln!("  - {}", title);
+                        }
+                        eprintln!("\nPlease be more specific or use the task number.");
+                        std::process::exit(1);
+                    }
+                };
 
-            // Call service
-            match complete_task(&mut store, &storage, params) {
+                let edited = editor::edit_text(&current).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+                let edited = edited.trim_end();
+
+                if edited == current.trim_end() {
+                    if title.is_none()
+                        && deadline.is_none()
+                        && !clear_deadline
+                        && remind.is_none()
+                        && !clear_reminder
+                    {
+                        println!("✓ Notes unchanged");
+                        return;
+                    }
+                } else if edited.trim().is_empty() {
+                    clear_notes = true;
+                } else {
+                    notes = Some(edited.to_string());
+                }
+            }
+
+            let params = EditTaskParameters {
+                task_number_or_fuzzy_name: task_number,
+                title,
+                notes,
+                clear_notes,
+                deadline,
+                clear_deadline,
+                reminder: remind,
+                clear_reminder,
+            };
+
+            match edit_task(&mut store, &storage, params) {
                 Ok(task) => {
-                    println!("✓ Task completed: {}", task.title);
+                    println!("✓ Task updated: {}", task.title);
                     println!("  #{}", task.task_number);
                 }
-                Err(CompleteTaskError::TaskNotFound(identifier)) => {
+                Err(EditTaskError::TaskNotFound(identifier)) => {
                     eprintln!("Error: Task '{}' not found", identifier);
                     std::process::exit(1);
                 }
-                Err(CompleteTaskError::AmbiguousTaskName(titles)) => {
+                Err(EditTaskError::AmbiguousTaskName(titles)) => {
                     eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
                     for title in titles {
                         eprintln!("  - {}", title);
@@ -684,518 +2495,4228 @@ fn main() {
                     eprintln!("\nPlease be more specific or use the task number.");
                     std::process::exit(1);
                 }
-                Err(CompleteTaskError::Storage(e)) => {
-                    eprintln!("Error: Failed to save task: {}", e);
+                Err(EditTaskError::EmptyTitle) => {
+                    eprintln!("Error: Task title cannot be empty");
                     std::process::exit(1);
                 }
-            }
-        }
-        Some(Commands::Move {
-            task_number,
-            today,
-            evening,
-            someday,
-            anytime,
-            when,
-            deadline,
-            project,
-            area,
-            tag,
-            notes,
-        }) => {
-            todo!()
-        }
-        Some(Commands::Area(AreaCommands::New { name })) => {
-            let params = CreateAreaParameters { name };
-            match create_area(&mut store, &storage, params) {
-                Ok(area) => {
-                    println!("✓ Area {} created with slug {}", area.name, area.slug);
+                Err(EditTaskError::InvalidDeadline(date_str, error)) => {
+                    exit_with_deadline_error(&date_str, &error);
+                }
+                Err(EditTaskError::InvalidReminder(reminder)) => {
+                    exit_with_reminder_error(&reminder);
                 }
-                Err(CreateAreaError::AreaAlreadyExists(name)) => {
-                    eprintln!("Error: Area with name '{}' already exists", name);
+                Err(EditTaskError::NothingToEdit) => {
+                    eprintln!("Error: Nothing to edit");
+                    eprintln!(
+                        "\nUse at least one of: --title, --notes, --clear-notes, --deadline, --clear-deadline, --remind, --clear-reminder"
+                    );
                     std::process::exit(1);
                 }
-                Err(CreateAreaError::Storage(e)) => {
-                    eprintln!("Error: Failed to create area: {}", e);
+                Err(EditTaskError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
                     std::process::exit(1);
                 }
             }
         }
-        Some(Commands::Area(AreaCommands::Delete { name })) => {
-            let params = DeleteAreaParameters { name };
+        Some(Commands::Note { task_number, text }) => {
+            let params = AppendNoteParameters {
+                task_number_or_fuzzy_name: task_number,
+                text,
+            };
 
-            match delete_area(&mut store, &storage, params) {
-                Ok(result) => {
-                    println!("✓ Area deleted: {}", result.area.name);
-                    if result.cascaded_projects_count > 0 {
-                        println!(
-                            "  └─ {} project(s) also deleted",
-                            result.cascaded_projects_count
-                        );
-                    }
-                    if result.cascaded_tasks_count > 0 {
-                        println!("  └─ {} task(s) also deleted", result.cascaded_tasks_count);
-                    }
+            match append_note(&mut store, &storage, params) {
+                Ok(task) => {
+                    println!("✓ Note added: {}", task.title);
+                    println!("  #{}", task.task_number);
                 }
-                Err(DeleteAreaError::AreaNotFound(name)) => {
-                    eprintln!("Error: Area '{}' not found", name);
-
-                    let areas: Vec<_> = store.get_active_areas().collect();
-                    if !areas.is_empty() {
-                        eprintln!("\nAvailable areas:");
-                        for area in areas {
-                            eprintln!("  - {}", area.name);
-                        }
+                Err(AppendNoteError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
+                    std::process::exit(1);
+                }
+                Err(AppendNoteError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
                     }
+                    eprintln!("\nPlease be more specific or use the task number.");
                     std::process::exit(1);
                 }
-                Err(DeleteAreaError::Storage(e)) => {
-                    eprintln!("Error: Failed to delete area: {}", e);
+                Err(AppendNoteError::EmptyNote) => {
+                    eprintln!("Error: Note cannot be empty");
+                    std::process::exit(1);
+                }
+                Err(AppendNoteError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
                     std::process::exit(1);
                 }
             }
         }
-        Some(Commands::Area(AreaCommands::List)) => {
-            // Collect all active areas
-            let mut areas: Vec<_> = store.get_active_areas().collect();
-
-            if areas.is_empty() {
-                println!("No areas found");
-            } else {
-                // Sort alphabetically by name (case-insensitive)
-                areas.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-
-                println!(
-                    "{} ({} {})\n",
-                    "AREAS".cyan(),
-                    areas.len(),
-                    if areas.len() == 1 { "area" } else { "areas" }
-                );
-
-                for area in areas {
-                    // Count active projects in this area
-                    let project_count = store
-                        .get_projects_for_area(area.id)
-                        .filter(|p| p.deleted_at.is_none())
-                        .count();
-
-                    // Count active tasks - includes both direct tasks and tasks within projects
-                    let direct_task_count = store
-                        .get_tasks_for_area(area.id)
-                        .filter(|t| t.deleted_at.is_none())
-                        .count();
-
-                    let project_task_count: usize = store
-                        .get_projects_for_area(area.id)
-                        .filter(|p| p.deleted_at.is_none())
-                        .map(|p| {
-                            store
-                                .get_tasks_for_project(p.id)
-                                .filter(|t| t.deleted_at.is_none())
-                                .count()
-                        })
-                        .sum();
-
-                    let total_task_count = direct_task_count + project_task_count;
-
-                    // Display area name
-                    println!("{} {}", "•".green(), area.name.bold());
-
-                    // Display counts
-                    println!(
-                        "    {} {} {} {}",
-                        project_count.to_string().dimmed(),
-                        if project_count == 1 {
-                            "project"
-                        } else {
-                            "projects"
-                        },
-                        "•".dimmed(),
-                        format!(
-                            "{} {}",
-                            total_task_count,
-                            if total_task_count == 1 {
-                                "task"
-                            } else {
-                                "tasks"
-                            }
-                        )
-                        .dimmed()
-                    );
+        Some(Commands::Attach {
+            task_number,
+            target,
+            label,
+        }) => {
+            let params = AttachParameters {
+                task_number_or_fuzzy_name: task_number,
+                target,
+                label,
+            };
 
-                    // Display separator
-                    println!("    {}", "─".repeat(30).dimmed());
-                    println!();
+            match attach(&mut store, &storage, params) {
+                Ok(task) => {
+                    println!("✓ Attached to: {}", task.title);
+                    println!("  #{}", task.task_number);
                 }
-            }
-        }
-        Some(Commands::Project(ProjectCommands::New { name, area })) => {
-            let params = CreateProjectParameters { name, area };
-            match create_project(&mut store, &storage, params) {
-                Ok(project) => {
-                    println!(
-                        "✓ Project {} created with slug {}",
-                        project.name, project.slug
-                    );
+                Err(AttachError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
+                    std::process::exit(1);
                 }
-                Err(CreateProjectError::AreaNotFound(area)) => {
-                    eprintln!("Error: Area with name '{}' not found", area);
+                Err(AttachError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
+                    }
+                    eprintln!("\nPlease be more specific or use the task number.");
                     std::process::exit(1);
                 }
-                Err(CreateProjectError::ProjectAlreadyExists(name)) => {
-                    eprintln!("Error: Project with name '{}' already exists", name);
+                Err(AttachError::InvalidTarget(target)) => {
+                    eprintln!("Error: '{}' is neither a URL nor an existing file", target);
+                    eprintln!("\nURLs need a scheme, e.g. https://example.com");
                     std::process::exit(1);
                 }
-                Err(CreateProjectError::Storage(e)) => {
-                    eprintln!("Error: Failed to create project: {}", e);
+                Err(AttachError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
                     std::process::exit(1);
                 }
             }
         }
-        Some(Commands::Project(ProjectCommands::Delete { name })) => {
-            let params = DeleteProjectParameters { name };
-
-            match delete_project(&mut store, &storage, params) {
-                Ok(result) => {
-                    println!("✓ Project deleted: {}", result.project.name);
-                    if result.cascaded_tasks_count > 0 {
-                        println!("  └─ {} task(s) also deleted", result.cascaded_tasks_count);
+        Some(Commands::Open { task_number, index }) => {
+            let task = match find_task(&store, &task_number) {
+                Ok(task) => task,
+                Err(NameLookupError::NotFound) => {
+                    eprintln!("Error: Task '{}' not found", task_number);
+                    std::process::exit(1);
+                }
+                Err(NameLookupError::Ambiguous(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
                     }
+                    eprintln!("\nPlease be more specific or use the task number.");
+                    std::process::exit(1);
                 }
-                Err(DeleteProjectError::ProjectNotFound(name)) => {
-                    eprintln!("Error: Project '{}' not found", name);
+            };
 
-                    let projects: Vec<_> = store.get_active_projects().collect();
-                    if !projects.is_empty() {
-                        eprintln!("\nAvailable projects:");
-                        for project in projects {
-                            eprintln!("  - {}", project.name);
-                        }
+            let Some(attachment) = index.checked_sub(1).and_then(|i| task.attachments.get(i))
+            else {
+                if task.attachments.is_empty() {
+                    eprintln!("Error: Task '{}' has no attachments", task.title);
+                    eprintln!(
+                        "\nAdd one with: tdo attach {} <url-or-file>",
+                        task.task_number
+                    );
+                } else {
+                    eprintln!(
+                        "Error: Task '{}' has {} attachment(s); there is no #{}",
+                        task.title,
+                        task.attachments.len(),
+                        index
+                    );
+                }
+                std::process::exit(1);
+            };
+
+            if let Err(e) = open_with_system(&attachment.target) {
+                eprintln!("Error: Failed to open '{}': {}", attachment.target, e);
+                std::process::exit(1);
+            }
+            println!(
+                "✓ Opened: {}",
+                attachment.label.as_ref().unwrap_or(&attachment.target)
+            );
+        }
+        Some(Commands::Snooze {
+            task_number,
+            duration,
+            clear,
+        }) => {
+            let params = SnoozeTaskParameters {
+                task_number_or_fuzzy_name: task_number,
+                duration,
+                clear,
+            };
+
+            match snooze_task(&mut store, &storage, params) {
+                Ok(SnoozeOutcome::Rescheduled { task, from }) => {
+                    println!("✓ Task snoozed: {}", task.title);
+                    if let When::Scheduled { date, .. } = task.when {
+                        println!(
+                            "  {} → {}",
+                            ui::describe_when(&from),
+                            date.strftime("%a %b %d")
+                        );
                     }
+                }
+                Ok(SnoozeOutcome::Silenced(task)) => {
+                    if let Some(until) = task.snoozed_until {
+                        let until = jiff::Zoned::new(until, jiff::tz::TimeZone::system());
+                        println!("✓ Task snoozed: {}", task.title);
+                        println!("  until {}", until.strftime("%a %b %d, %H:%M"));
+                    }
+                }
+                Ok(SnoozeOutcome::Cleared(task)) => {
+                    println!("✓ Snooze cleared: {}", task.title);
+                }
+                Err(SnoozeTaskError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
                     std::process::exit(1);
                 }
-                Err(DeleteProjectError::AmbiguousProjectName(names)) => {
-                    eprintln!("Error: Project name is ambiguous. Multiple projects found:");
-                    for name in names {
-                        eprintln!("  - {}", name);
+                Err(SnoozeTaskError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
                     }
-                    eprintln!("\nPlease be more specific.");
+                    eprintln!("\nPlease be more specific or use the task number.");
                     std::process::exit(1);
                 }
-                Err(DeleteProjectError::ProjectAlreadyDeleted(name)) => {
-                    eprintln!("Error: Project '{}' is already deleted", name);
+                Err(SnoozeTaskError::InvalidDuration(duration_str, error)) => {
+                    eprintln!(
+                        "Error: Invalid snooze duration '{}': {}",
+                        duration_str, error
+                    );
+                    eprintln!("\nExamples: 30m, 2h, 1d, 1w, monday");
                     std::process::exit(1);
                 }
-                Err(DeleteProjectError::Storage(e)) => {
-                    eprintln!("Error: Failed to delete project: {}", e);
+                Err(SnoozeTaskError::NotScheduled(title)) => {
+                    eprintln!(
+                        "Error: '{}' is not in Today or scheduled, so there is no date to push forward",
+                        title
+                    );
+                    eprintln!(
+                        "\nGive a duration like 2h or 1d to silence it, or schedule it with `tdo move --when`"
+                    );
+                    std::process::exit(1);
+                }
+                Err(SnoozeTaskError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
                     std::process::exit(1);
                 }
             }
         }
-        Some(Commands::Project(ProjectCommands::List)) => {
-            // Collect all active projects
-            let mut projects: Vec<_> = store.get_active_projects().collect();
-
-            if projects.is_empty() {
-                println!("No projects found");
-            } else {
-                // Sort alphabetically by name (case-insensitive)
-                projects.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-
-                println!(
-                    "{} ({} {})\n",
-                    "PROJECTS".cyan(),
-                    projects.len(),
-                    if projects.len() == 1 {
-                        "project"
-                    } else {
-                        "projects"
-                    }
-                );
-
-                for project in projects {
-                    // Count active tasks in this project
-                    let task_count = store
-                        .get_tasks_for_project(project.id)
-                        .filter(|t| t.deleted_at.is_none())
-                        .count();
-
-                    // Display project name
-                    println!("{} {}", "•".green(), project.name.bold());
+        Some(Commands::Log {
+            title,
+            at,
+            project,
+            create_project,
+            area,
+            create_area,
+            tag,
+            notes,
+        }) => {
+            let now = jiff::Zoned::now();
+            let completed_at = match at {
+                Some(at) => parse_past_instant(&at, &now).unwrap_or_else(|| {
+                    eprintln!("Error: Invalid time '{}'", at);
+                    eprintln!(
+                        "\nUse a moment that has passed: \"yesterday\", \"9:30\", \"friday 17:00\" or \"2025-03-01 17:00\""
+                    );
+                    std::process::exit(1);
+                }),
+                None => now.timestamp(),
+            };
 
-                    // Display area if project belongs to one
-                    if let Some(area_id) = project.area_id {
-                        if let Some(area) = store.get_area(area_id) {
-                            println!("    {} {}", "Area:".dimmed(), area.name.blue());
-                        }
-                    }
+            let params = LogTaskParameters {
+                task: AddTaskParameters {
+                    title,
+                    notes,
+                    when: When::Inbox,
+                    deadline: None,
+                    project,
+                    area,
+                    area_override: None,
+                    tags: tag,
+                    context: None,
+                    energy: None,
+                    reminder: None,
+                    defer_until: None,
+                    estimate: None,
+                    create_project,
+                    create_area,
+                    id: None,
+                    idempotency_key: None,
+                },
+                completed_at,
+            };
 
-                    // Display task count
+            match log_task(&mut store, &storage, params) {
+                Ok(task) => {
+                    println!("✓ Logged: {}", task.title);
                     println!(
-                        "    {} {}",
-                        task_count.to_string().dimmed(),
-                        if task_count == 1 { "task" } else { "tasks" }.dimmed()
+                        "  #{} · {}",
+                        task.task_number,
+                        ui::format_timestamp(completed_at)
                     );
-
-                    // Display separator
-                    println!("    {}", "─".repeat(30).dimmed());
-                    println!();
                 }
+                Err(e) => exit_with_add_error(&store, e),
             }
         }
-        Some(Commands::Project(ProjectCommands::View { slug })) => {
-            // Find project by slug (case-insensitive)
-            let project = store
-                .get_active_projects()
-                .find(|p| p.slug.to_lowercase() == slug.to_lowercase());
+        Some(Commands::Done {
+            task_numbers_or_fuzzy_names,
+        }) => {
+            // Build parameters
+            let params = CompleteTasksParameters {
+                task_numbers_or_fuzzy_names,
+            };
 
-            match project {
-                None => {
-                    eprintln!("Error: Project '{}' not found", slug);
+            // Call service
+            let completions = match complete_tasks(&mut store, &storage, params) {
+                Ok(completions) => completions,
+                Err(e) => {
+                    eprintln!("Error: Failed to save tasks: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            let mut completed = Vec::new();
+            let mut failed = false;
+
+            for completion in completions {
+                match completion.result {
+                    Ok(task) => {
+                        println!("✓ Task completed: {}", task.title);
+                        println!("  #{}", task.task_number);
+                        completed.push(task);
+                    }
+                    Err(CompleteTaskError::TaskNotFound(identifier)) => {
+                        eprintln!("Error: Task '{}' not found", identifier);
+                        failed = true;
+                    }
+                    Err(CompleteTaskError::AmbiguousTaskName(titles)) => {
+                        eprintln!(
+                            "Error: Task name '{}' is ambiguous. Multiple tasks found:",
+                            completion.identifier
+                        );
+                        for title in titles {
+                            eprintln!("  - {}", title);
+                        }
+                        eprintln!("\nPlease be more specific or use the task number.");
+                        failed = true;
+                    }
+                    Err(CompleteTaskError::TaskAlreadyCompleted(title, completed_at)) => {
+                        eprintln!(
+                            "Error: Task '{}' was already completed on {}",
+                            title,
+                            ui::format_timestamp(completed_at)
+                        );
+                        eprintln!(
+                            "\nUse `tdo reopen {}` first to complete it again.",
+                            completion.identifier
+                        );
+                        failed = true;
+                    }
+                    Err(CompleteTaskError::Storage(e)) => {
+                        eprintln!("Error: Failed to save task: {}", e);
+                        failed = true;
+                    }
+                }
+            }
+
+            if !completed.is_empty() {
+                print_completion_summary(&completed, &store);
+                give_completion_feedback(&config.completion_feedback, &completed, &store);
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Duplicate {
+            task_number,
+            today,
+            tomorrow,
+            evening,
+            someday,
+            anytime,
+            when: when_str,
+        }) => {
+            // Keep the original's schedule unless a scheduling flag was given
+            let when = if today || tomorrow || evening || someday || anytime || when_str.is_some() {
+                Some(
+                    When::from_command_flags(today, tomorrow, evening, someday, anytime, when_str)
+                        .unwrap_or_else(|e| exit_with_when_error(e)),
+                )
+            } else {
+                None
+            };
+
+            let params = DuplicateTaskParameters {
+                task_number_or_fuzzy_name: task_number,
+                when,
+            };
+
+            match duplicate_task(&mut store, &storage, params) {
+                Ok(task) => {
+                    println!("✓ Task duplicated: {}", task.title);
+                    println!("  #{}", task.task_number);
+                    if let Some(context) = ui::get_task_context(&task, &store) {
+                        println!("  {}", context);
+                    }
+                }
+                Err(DuplicateTaskError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
+                    std::process::exit(1);
+                }
+                Err(DuplicateTaskError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
+                    }
+                    eprintln!("\nPlease be more specific or use the task number.");
+                    std::process::exit(1);
+                }
+                Err(DuplicateTaskError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Undone {
+            task_number_or_fuzzy_name,
+        }) => {
+            let params = UncompleteTaskParameters {
+                task_number_or_fuzzy_name,
+            };
+
+            match uncomplete_task(&mut store, &storage, params) {
+                Ok(task) => {
+                    println!("✓ Task reopened: {}", task.title);
+                    println!("  #{}", task.task_number);
+                }
+                Err(UncompleteTaskError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
+                    std::process::exit(1);
+                }
+                Err(UncompleteTaskError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple completed tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
+                    }
+                    eprintln!("\nPlease be more specific or use the task number.");
+                    std::process::exit(1);
+                }
+                Err(UncompleteTaskError::TaskNotCompleted(title)) => {
+                    eprintln!("Error: Task '{}' is not completed", title);
+                    std::process::exit(1);
+                }
+                Err(UncompleteTaskError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Delete {
+            task_number_or_fuzzy_name,
+        }) => {
+            let params = DeleteTaskParameters {
+                task_number_or_fuzzy_name,
+            };
+
+            match delete_task(&mut store, &storage, params) {
+                Ok(task) => {
+                    println!("✓ Task moved to trash: {}", task.title);
+                    println!("  #{}", task.task_number);
+                    if let Some(context) = ui::get_task_context(&task, &store) {
+                        println!("  {}", context);
+                    }
+                }
+                Err(DeleteTaskError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
+                    std::process::exit(1);
+                }
+                Err(DeleteTaskError::TaskAlreadyDeleted(title)) => {
+                    eprintln!("Error: Task '{}' is already in the trash", title);
+                    std::process::exit(1);
+                }
+                Err(DeleteTaskError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
+                    }
+                    eprintln!("\nPlease be more specific or use the task number.");
+                    std::process::exit(1);
+                }
+                Err(DeleteTaskError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Evening {
+            task_number_or_fuzzy_name,
+        }) => set_evening_or_exit(&mut store, &storage, task_number_or_fuzzy_name, true),
+        Some(Commands::Morning {
+            task_number_or_fuzzy_name,
+        }) => set_evening_or_exit(&mut store, &storage, task_number_or_fuzzy_name, false),
+        Some(Commands::Start {
+            task_number_or_fuzzy_name,
+        }) => {
+            let params = StartTimerParameters {
+                task_number_or_fuzzy_name,
+            };
+
+            match start_timer(&mut store, &storage, params) {
+                Ok(result) => {
+                    if let Some((stopped, duration)) = result.stopped {
+                        println!(
+                            "✓ Timer stopped: {} ({})",
+                            stopped.title,
+                            ui::format_tracked_time(duration)
+                        );
+                    }
+                    println!("✓ Timer started: {}", result.task.title);
+                    println!("  #{}", result.task.task_number);
+                }
+                Err(StartTimerError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
+                    std::process::exit(1);
+                }
+                Err(StartTimerError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
+                    }
+                    eprintln!("\nPlease be more specific or use the task number.");
+                    std::process::exit(1);
+                }
+                Err(StartTimerError::TaskCompleted(title)) => {
+                    eprintln!("Error: Task '{}' is already completed", title);
+                    std::process::exit(1);
+                }
+                Err(StartTimerError::TimerAlreadyRunning(title)) => {
+                    eprintln!("Error: A timer is already running on task '{}'", title);
+                    eprintln!("\nUse `tdo stop` to stop it");
+                    std::process::exit(1);
+                }
+                Err(StartTimerError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Stop) => match stop_timer(&mut store, &storage) {
+            Ok((task, duration)) => {
+                println!(
+                    "✓ Timer stopped: {} ({})",
+                    task.title,
+                    ui::format_tracked_time(duration)
+                );
+                println!("  #{}", task.task_number);
+            }
+            Err(StopTimerError::NoTimerRunning) => {
+                eprintln!("Error: No timer is running");
+                eprintln!("\nUse `tdo start <id>` to start one");
+                std::process::exit(1);
+            }
+            Err(StopTimerError::Storage(e)) => {
+                eprintln!("Error: Failed to save task: {}", e);
+                std::process::exit(1);
+            }
+        },
+        Some(Commands::Pin {
+            task_number_or_fuzzy_name,
+        }) => set_pinned_or_exit(&mut store, &storage, task_number_or_fuzzy_name, true),
+        Some(Commands::Unpin {
+            task_number_or_fuzzy_name,
+        }) => set_pinned_or_exit(&mut store, &storage, task_number_or_fuzzy_name, false),
+        Some(Commands::Move {
+            task_number,
+            all,
+            from,
+            from_project,
+            from_tag,
+            to,
+            today,
+            tomorrow,
+            evening,
+            someday,
+            anytime,
+            when: when_str,
+            deadline,
+            project,
+            area,
+            area_override,
+            tag,
+            notes,
+            energy,
+            context,
+            defer,
+            estimate,
+        }) => {
+            if !all && (from.is_some() || from_project.is_some() || from_tag.is_some()) {
+                eprintln!("Error: --from, --from-project and --from-tag select tasks for --all");
+                eprintln!("\nUse `tdo move --all --from <list> ...` to move several tasks");
+                std::process::exit(1);
+            }
+
+            // Only reschedule when a scheduling flag was given
+            let when = if let Some(list) = to {
+                Some(list.as_when().unwrap_or_else(|| {
+                    eprintln!("Error: Tasks can't be moved to Upcoming without a date");
+                    eprintln!("\nUse --when <date> or --tomorrow instead");
+                    std::process::exit(1);
+                }))
+            } else if today || tomorrow || evening || someday || anytime || when_str.is_some() {
+                Some(
+                    When::from_command_flags(today, tomorrow, evening, someday, anytime, when_str)
+                        .unwrap_or_else(|e| exit_with_when_error(e)),
+                )
+            } else {
+                None
+            };
+
+            let changes = MoveChanges {
+                when,
+                deadline,
+                project,
+                area,
+                area_override,
+                tags: tag,
+                notes,
+                context,
+                energy,
+                defer_until: defer,
+                estimate,
+            };
+
+            let result = match task_number {
+                Some(task_number) if !all => move_task(
+                    &mut store,
+                    &storage,
+                    MoveTaskParameters {
+                        task_number_or_fuzzy_name: task_number,
+                        changes,
+                    },
+                )
+                .map(|task| vec![task]),
+                _ => move_tasks(
+                    &mut store,
+                    &storage,
+                    MoveTasksParameters {
+                        from,
+                        from_project,
+                        from_tag,
+                        changes,
+                    },
+                ),
+            };
+
+            match result {
+                Ok(tasks) if all => {
+                    if tasks.is_empty() {
+                        println!("✓ No tasks match");
+                    } else {
+                        println!(
+                            "✓ Moved {} {}",
+                            tasks.len(),
+                            if tasks.len() == 1 { "task" } else { "tasks" }
+                        );
+                        for task in &tasks {
+                            println!("  #{} {}", task.task_number, task.title);
+                        }
+                    }
+                }
+                Ok(tasks) => {
+                    let task = &tasks[0];
+                    println!("✓ Task moved: {}", task.title);
+                    println!("  #{}", task.task_number);
+                    if let Some(context) = ui::get_task_context(task, &store) {
+                        println!("  {}", context);
+                    }
+                }
+                Err(MoveTaskError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
+                    std::process::exit(1);
+                }
+                Err(MoveTaskError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
+                    }
+                    eprintln!("\nPlease be more specific or use the task number.");
+                    std::process::exit(1);
+                }
+                Err(MoveTaskError::ProjectNotFound(name)) => {
+                    eprintln!("Error: Project '{}' not found", name);
 
                     let projects: Vec<_> = store.get_active_projects().collect();
                     if !projects.is_empty() {
                         eprintln!("\nAvailable projects:");
-                        for p in projects {
-                            eprintln!("  - {} ({})", p.name, p.slug);
+                        for project in projects {
+                            eprintln!("  - {} ({})", project.name, project.slug);
                         }
                     }
                     std::process::exit(1);
                 }
-                Some(project) => {
-                    // Get tasks for this project
-                    let mut tasks: Vec<_> = store
-                        .get_tasks_for_project(project.id)
-                        .filter(|t| t.completed_at.is_none() && t.deleted_at.is_none())
-                        .collect();
+                Err(MoveTaskError::AmbiguousProjectName(names)) => {
+                    eprintln!("Error: Project name is ambiguous. Multiple projects found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(MoveTaskError::AreaNotFound(name)) => {
+                    eprintln!("Error: Area '{}' not found", name);
+
+                    let areas: Vec<_> = store.get_active_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAvailable areas:");
+                        for area in areas {
+                            eprintln!("  - {} ({})", area.name, area.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(MoveTaskError::AmbiguousAreaName(names)) => {
+                    eprintln!("Error: Area name is ambiguous. Multiple areas found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(MoveTaskError::AreaWithProject) => {
+                    eprintln!("Error: Cannot use --area together with --project");
+                    eprintln!(
+                        "\nTasks in a project belong to the project's area. To surface the task under a different area use --area-override"
+                    );
+                    std::process::exit(1);
+                }
+                Err(MoveTaskError::AreaOverrideWithoutProject) => {
+                    eprintln!("Error: --area-override can only be used for tasks in a project");
+                    eprintln!("\nUse --area to move the task directly into an area");
+                    std::process::exit(1);
+                }
+                Err(MoveTaskError::InvalidDeadline(date_str, error)) => {
+                    exit_with_deadline_error(&date_str, &error);
+                }
+                Err(MoveTaskError::InvalidDeferDate(date_str, error)) => {
+                    eprintln!("Error: Invalid defer date '{}': {}", date_str, error);
+                    eprintln!("\nExpected format: YYYY-MM-DD (e.g., 2025-03-01)");
+                    std::process::exit(1);
+                }
+                Err(MoveTaskError::InvalidEstimate(estimate)) => {
+                    exit_with_estimate_error(&estimate);
+                }
+                Err(MoveTaskError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::New { name, slug })) => {
+            let params = CreateAreaParameters { name, slug };
+            match create_area(&mut store, &storage, params) {
+                Ok(area) => {
+                    println!("✓ Area {} created with slug {}", area.name, area.slug);
+                }
+                Err(CreateAreaError::SlugTaken(slug)) => {
+                    eprintln!("Error: Slug '{}' is already used by another area", slug);
+                    eprintln!("\nPick another --slug, or leave it out to get a numbered one");
+                    std::process::exit(1);
+                }
+                Err(CreateAreaError::InvalidSlug(slug)) => {
+                    eprintln!("Error: Slug '{}' has no letters or digits", slug);
+                    std::process::exit(1);
+                }
+                Err(CreateAreaError::Storage(e)) => {
+                    eprintln!("Error: Failed to create area: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::Order {
+            slug,
+            before,
+            after,
+        })) => {
+            let placement = match (before, after) {
+                (Some(other), _) => AreaPlacement::Before(other),
+                (None, Some(other)) => AreaPlacement::After(other),
+                (None, None) => unreachable!("clap requires --before or --after"),
+            };
+            let params = OrderAreaParameters {
+                slug: slug.clone(),
+                placement,
+            };
+
+            match order_area(&mut store, &storage, params) {
+                Ok(areas) => {
+                    println!("✓ Area order updated");
+                    for (index, area) in areas.iter().enumerate() {
+                        let line = format!("{:>3}. {}", index + 1, area.name);
+                        if area.slug.eq_ignore_ascii_case(&slug) {
+                            println!("  {}", line.bold());
+                        } else {
+                            println!("  {}", line.dimmed());
+                        }
+                    }
+                }
+                Err(OrderAreaError::AreaNotFound(slug)) => {
+                    eprintln!("Error: Area '{}' not found", slug);
+                    eprintln!("\nUse `tdo area list` to see area slugs");
+                    std::process::exit(1);
+                }
+                Err(OrderAreaError::SameArea) => {
+                    eprintln!("Error: An area can't be placed next to itself");
+                    std::process::exit(1);
+                }
+                Err(OrderAreaError::Storage(e)) => {
+                    eprintln!("Error: Failed to save area order: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::MoveProjects { from, to })) => {
+            let params = MoveAreaProjectsParameters { from, to };
+
+            match move_area_projects(&mut store, &storage, params) {
+                Ok(MoveAreaProjectsResult { from, to, projects }) => {
+                    if projects.is_empty() {
+                        println!("No projects to move in {}", from.name);
+                        return;
+                    }
+
+                    println!(
+                        "✓ Moved {} {} from {} to {}",
+                        projects.len(),
+                        if projects.len() == 1 {
+                            "project"
+                        } else {
+                            "projects"
+                        },
+                        from.name,
+                        to.name
+                    );
+                    for project in &projects {
+                        println!("  - {}", project.name);
+                    }
+
+                    let loose_tasks = store
+                        .get_tasks_for_area(from.id)
+                        .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                        .count();
+                    if loose_tasks > 0 {
+                        println!(
+                            "  {}",
+                            format!(
+                                "{} loose {} stayed in {}",
+                                loose_tasks,
+                                if loose_tasks == 1 { "task" } else { "tasks" },
+                                from.name
+                            )
+                            .dimmed()
+                        );
+                    }
+                }
+                Err(MoveAreaProjectsError::AreaNotFound(slug)) => {
+                    eprintln!("Error: Area '{}' not found", slug);
+
+                    let areas: Vec<_> = store.get_active_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAvailable areas:");
+                        for a in areas {
+                            eprintln!("  - {} ({})", a.name, a.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(MoveAreaProjectsError::SameArea) => {
+                    eprintln!("Error: Projects can't be moved to the area they're already in");
+                    std::process::exit(1);
+                }
+                Err(MoveAreaProjectsError::Storage(e)) => {
+                    eprintln!("Error: Failed to save projects: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::Notes { slug, notes, clear })) => {
+            let notes = if clear {
+                None
+            } else if notes.is_some() {
+                notes
+            } else {
+                let current = store
+                    .get_active_areas()
+                    .find(|a| a.slug.eq_ignore_ascii_case(&slug))
+                    .map(|a| a.notes.clone().unwrap_or_default())
+                    .unwrap_or_else(|| {
+                        eprintln!("Error: Area '{}' not found", slug);
+                        std::process::exit(1);
+                    });
+
+                let edited = editor::edit_text(&current).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+                if edited.trim_end() == current.trim_end() {
+                    println!("✓ Notes unchanged");
+                    return;
+                }
+                Some(edited)
+            };
+
+            let params = SetAreaNotesParameters { slug, notes };
+            match set_area_notes(&mut store, &storage, params) {
+                Ok(area) => match &area.notes {
+                    Some(notes) => {
+                        println!("✓ Notes saved: {}", area.name);
+                        ui::render_notes_preview(notes);
+                    }
+                    None => println!("✓ Notes removed: {}", area.name),
+                },
+                Err(SetAreaNotesError::AreaNotFound(slug)) => {
+                    eprintln!("Error: Area '{}' not found", slug);
+
+                    let areas: Vec<_> = store.get_active_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAvailable areas:");
+                        for a in areas {
+                            eprintln!("  - {} ({})", a.name, a.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(SetAreaNotesError::Storage(e)) => {
+                    eprintln!("Error: Failed to save area: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::Edit {
+            slug,
+            color,
+            clear_color,
+            icon,
+            clear_icon,
+        })) => {
+            let params = EditAreaParameters {
+                slug,
+                color,
+                clear_color,
+                icon,
+                clear_icon,
+            };
+
+            match edit_area(&mut store, &storage, params) {
+                Ok(area) => {
+                    println!("✓ Area updated: {}", area.name);
+                    if let Some(label) =
+                        ui::format_label(area.color.as_deref(), area.icon.as_deref())
+                    {
+                        println!("  {}", label);
+                    }
+                }
+                Err(EditAreaError::InvalidColor(color)) => {
+                    exit_with_color_error(&color);
+                }
+                Err(EditAreaError::AreaNotFound(slug)) => {
+                    eprintln!("Error: Area '{}' not found", slug);
+
+                    let areas: Vec<_> = store.get_active_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAvailable areas:");
+                        for a in areas {
+                            eprintln!("  - {} ({})", a.name, a.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(EditAreaError::Storage(e)) => {
+                    eprintln!("Error: Failed to save area: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::Rename { area, new_name })) => {
+            let params = RenameAreaParameters { area, new_name };
+            match rename_area(&mut store, &storage, params) {
+                Ok(result) => {
+                    println!(
+                        "✓ Area renamed: {} → {}",
+                        result.previous_name, result.area.name
+                    );
+                    println!("  slug: {}", result.area.slug);
+
+                    // Workspaces name their areas in config.json, which isn't rewritten
+                    for (name, workspace) in &config.workspaces {
+                        if workspace
+                            .areas
+                            .iter()
+                            .any(|a| a.eq_ignore_ascii_case(&result.previous_name))
+                        {
+                            println!(
+                                "{}",
+                                format!(
+                                    "⚠ Workspace '{}' still lists '{}' in config.json",
+                                    name, result.previous_name
+                                )
+                                .yellow()
+                            );
+                        }
+                    }
+                }
+                Err(RenameAreaError::AreaNotFound(name)) => {
+                    eprintln!("Error: Area '{}' not found", name);
+
+                    let areas: Vec<_> = store.get_active_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAvailable areas:");
+                        for a in areas {
+                            eprintln!("  - {} ({})", a.name, a.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(RenameAreaError::AmbiguousAreaName(names)) => {
+                    eprintln!("Error: Area name is ambiguous. Multiple areas found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(RenameAreaError::EmptyName) => {
+                    eprintln!("Error: The new name is empty");
+                    std::process::exit(1);
+                }
+                Err(RenameAreaError::Storage(e)) => {
+                    eprintln!("Error: Failed to save area: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::Delete { name, yes })) => {
+            if !yes && let Some((area_name, task_count)) = area_cascade(&store, &name) {
+                confirm_cascade("area", &area_name, task_count, config.confirm_cascade_over);
+            }
+
+            let params = DeleteAreaParameters { name };
+
+            match delete_area(&mut store, &storage, params) {
+                Ok(result) => {
+                    println!("✓ Area deleted: {}", result.area.name);
+                    if result.cascaded_projects_count > 0 {
+                        println!(
+                            "  └─ {} project(s) also deleted",
+                            result.cascaded_projects_count
+                        );
+                    }
+                    if result.cascaded_tasks_count > 0 {
+                        println!("  └─ {} task(s) also deleted", result.cascaded_tasks_count);
+                    }
+                }
+                Err(DeleteAreaError::AreaNotFound(name)) => {
+                    eprintln!("Error: Area '{}' not found", name);
+
+                    let areas: Vec<_> = store.get_active_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAvailable areas:");
+                        for area in areas {
+                            eprintln!("  - {} ({})", area.name, area.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(DeleteAreaError::Storage(e)) => {
+                    eprintln!("Error: Failed to delete area: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::List { archived })) => {
+            // Collect all active areas; archived ones are only counted at the
+            // end, unless they were asked for
+            let (archived_areas, current): (Vec<_>, Vec<_>) = store
+                .get_active_areas()
+                .partition(|a| a.archived_at.is_some());
+            let (mut areas, hidden_archived) = if archived {
+                (archived_areas, 0)
+            } else {
+                (current, archived_areas.len())
+            };
+
+            if areas.is_empty() {
+                if archived {
+                    println!("No archived areas");
+                } else {
+                    println!("No areas found");
+                }
+            } else {
+                // Manual order first, then alphabetically (case-insensitive)
+                areas.sort_by_key(|a| a.display_order());
+
+                println!(
+                    "{} ({} {})\n",
+                    if archived { "ARCHIVED AREAS" } else { "AREAS" }.cyan(),
+                    areas.len(),
+                    if areas.len() == 1 { "area" } else { "areas" }
+                );
+
+                for area in areas {
+                    // Count active projects in this area, leaving out incubated and archived ones
+                    let project_count = store
+                        .get_projects_for_area(area.id)
+                        .filter(|p| {
+                            p.deleted_at.is_none()
+                                && p.incubated_at.is_none()
+                                && p.archived_at.is_none()
+                        })
+                        .count();
+
+                    // Count active tasks - includes direct tasks, tasks within projects and overrides
+                    let total_task_count = store
+                        .get_tasks_in_area(area.id)
+                        .filter(|t| t.deleted_at.is_none())
+                        .count();
+
+                    // Display area name
+                    println!("{} {}", "•".green(), area.name.bold());
+
+                    // Display counts
+                    println!(
+                        "    {} {} {} {}",
+                        project_count.to_string().dimmed(),
+                        if project_count == 1 {
+                            "project"
+                        } else {
+                            "projects"
+                        },
+                        "•".dimmed(),
+                        format!(
+                            "{} {}",
+                            total_task_count,
+                            if total_task_count == 1 {
+                                "task"
+                            } else {
+                                "tasks"
+                            }
+                        )
+                        .dimmed()
+                    );
+
+                    if let Some(archived_at) = area.archived_at {
+                        println!(
+                            "    {}",
+                            format!(
+                                "archived {}",
+                                ui::get_local_date(archived_at).strftime("%b %d, %Y")
+                            )
+                            .dimmed()
+                        );
+                    }
+
+                    // Display separator
+                    println!("    {}", "─".repeat(30).dimmed());
+                    println!();
+                }
+            }
+            if hidden_archived > 0 {
+                println!(
+                    "{}",
+                    format!(
+                        "{} archived {} not shown (see `tdo area list --archived`)",
+                        hidden_archived,
+                        if hidden_archived == 1 {
+                            "area"
+                        } else {
+                            "areas"
+                        }
+                    )
+                    .dimmed()
+                );
+            }
+        }
+        Some(Commands::Area(
+            command @ (AreaCommands::Archive { .. } | AreaCommands::Unarchive { .. }),
+        )) => {
+            let (query, unarchive) = match command {
+                AreaCommands::Unarchive { area } => (area, true),
+                AreaCommands::Archive { area } => (area, false),
+                _ => unreachable!("only archive and unarchive reach this arm"),
+            };
+            let params = ArchiveAreaParameters {
+                area: query,
+                unarchive,
+            };
+
+            match archive_area(&mut store, &storage, params) {
+                Ok(area) if unarchive => {
+                    println!("✓ Area unarchived: {}", area.name);
+                }
+                Ok(area) => {
+                    let projects = store
+                        .get_projects_for_area(area.id)
+                        .filter(|p| p.deleted_at.is_none() && p.completed_at.is_none())
+                        .count();
+                    let open_tasks = store
+                        .get_tasks_in_area(area.id)
+                        .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                        .count();
+                    println!("✓ Area archived: {}", area.name);
+                    if projects + open_tasks > 0 {
+                        println!(
+                            "  {}",
+                            format!(
+                                "{} {} and {} open {} kept, out of the lists and working views",
+                                projects,
+                                if projects == 1 { "project" } else { "projects" },
+                                open_tasks,
+                                if open_tasks == 1 { "task" } else { "tasks" }
+                            )
+                            .dimmed()
+                        );
+                    }
+                    println!(
+                        "  {}",
+                        format!("Bring it back with `tdo area unarchive {}`", area.slug).dimmed()
+                    );
+                }
+                Err(ArchiveAreaError::AreaNotFound(name)) => {
+                    eprintln!("Error: Area '{}' not found", name);
+
+                    let areas: Vec<_> = store.get_active_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAvailable areas:");
+                        for a in areas {
+                            eprintln!("  - {} ({})", a.name, a.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(ArchiveAreaError::AmbiguousAreaName(names)) => {
+                    eprintln!("Error: Area name is ambiguous. Multiple areas found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(ArchiveAreaError::AlreadyArchived(name)) => {
+                    eprintln!("Error: Area '{}' is already archived", name);
+                    std::process::exit(1);
+                }
+                Err(ArchiveAreaError::NotArchived(name)) => {
+                    eprintln!("Error: Area '{}' is not archived", name);
+                    std::process::exit(1);
+                }
+                Err(ArchiveAreaError::Storage(e)) => {
+                    eprintln!("Error: Failed to save area: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::New {
+            name,
+            area,
+            deadline,
+            slug,
+            tasks,
+        })) => {
+            let params = CreateProjectParameters {
+                name,
+                area,
+                deadline,
+                slug,
+                tasks,
+            };
+            match create_project(&mut store, &storage, params) {
+                Ok(CreateProjectResult { project, tasks }) => {
+                    println!(
+                        "✓ Project {} created with slug {}",
+                        project.name, project.slug
+                    );
+                    if let Some(deadline) = project.deadline {
+                        println!("  ⚑ {}", deadline.strftime("%a, %b %d %Y"));
+                    }
+                    if !tasks.is_empty() {
+                        println!();
+                        for task in &tasks {
+                            ui::render_task_line(task, &store, false);
+                        }
+                    }
+                }
+                Err(CreateProjectError::InvalidDeadline(date_str, error)) => {
+                    exit_with_deadline_error(&date_str, &error);
+                }
+                Err(CreateProjectError::AreaNotFound(area)) => {
+                    eprintln!("Error: Area with name '{}' not found", area);
+                    std::process::exit(1);
+                }
+                Err(CreateProjectError::SlugTaken(slug)) => {
+                    eprintln!("Error: Slug '{}' is already used by another project", slug);
+                    eprintln!("\nPick another --slug, or leave it out to get a numbered one");
+                    std::process::exit(1);
+                }
+                Err(CreateProjectError::InvalidSlug(slug)) => {
+                    eprintln!("Error: Slug '{}' has no letters or digits", slug);
+                    std::process::exit(1);
+                }
+                Err(CreateProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to create project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Rename { project, new_name })) => {
+            let params = RenameProjectParameters { project, new_name };
+            match rename_project(&mut store, &storage, params) {
+                Ok(result) => {
+                    println!(
+                        "✓ Project renamed: {} → {}",
+                        result.previous_name, result.project.name
+                    );
+                    println!("  slug: {}", result.project.slug);
+                }
+                Err(RenameProjectError::ProjectNotFound(name)) => {
+                    eprintln!("Error: Project '{}' not found", name);
+
+                    let projects: Vec<_> = store.get_active_projects().collect();
+                    if !projects.is_empty() {
+                        eprintln!("\nAvailable projects:");
+                        for p in projects {
+                            eprintln!("  - {} ({})", p.name, p.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(RenameProjectError::AmbiguousProjectName(names)) => {
+                    eprintln!("Error: Project name is ambiguous. Multiple projects found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(RenameProjectError::EmptyName) => {
+                    eprintln!("Error: The new name is empty");
+                    std::process::exit(1);
+                }
+                Err(RenameProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Delete { name, yes })) => {
+            if !yes && let Some((project_name, task_count)) = project_cascade(&store, &name) {
+                confirm_cascade(
+                    "project",
+                    &project_name,
+                    task_count,
+                    config.confirm_cascade_over,
+                );
+            }
+
+            let params = DeleteProjectParameters { name };
+
+            match delete_project(&mut store, &storage, params) {
+                Ok(result) => {
+                    println!("✓ Project deleted: {}", result.project.name);
+                    if result.cascaded_tasks_count > 0 {
+                        println!("  └─ {} task(s) also deleted", result.cascaded_tasks_count);
+                    }
+                }
+                Err(DeleteProjectError::ProjectNotFound(name)) => {
+                    eprintln!("Error: Project '{}' not found", name);
+
+                    let projects: Vec<_> = store.get_active_projects().collect();
+                    if !projects.is_empty() {
+                        eprintln!("\nAvailable projects:");
+                        for project in projects {
+                            eprintln!("  - {} ({})", project.name, project.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(DeleteProjectError::AmbiguousProjectName(names)) => {
+                    eprintln!("Error: Project name is ambiguous. Multiple projects found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(DeleteProjectError::ProjectAlreadyDeleted(name)) => {
+                    eprintln!("Error: Project '{}' is already deleted", name);
+                    std::process::exit(1);
+                }
+                Err(DeleteProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to delete project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::List { archived })) => {
+            // Collect all active projects; incubated and archived ones are only
+            // counted at the end, unless archived ones were asked for
+            let (archived_projects, current): (Vec<_>, Vec<_>) = store
+                .get_active_projects()
+                .partition(|p| store.is_project_archived(p));
+            let (incubated, mut projects, hidden_archived): (Vec<_>, Vec<_>, _) = if archived {
+                (vec![], archived_projects, 0)
+            } else {
+                let (incubated, projects) =
+                    current.into_iter().partition(|p| p.incubated_at.is_some());
+                (incubated, projects, archived_projects.len())
+            };
+
+            if projects.is_empty() {
+                if archived {
+                    println!("No archived projects");
+                } else {
+                    println!("No projects found");
+                }
+            } else {
+                // Manual order first, then alphabetically (case-insensitive),
+                // then by slug for projects sharing a name
+                projects.sort_by_key(|p| (p.display_order(), p.slug.clone()));
+
+                println!(
+                    "{} ({} {})\n",
+                    if archived {
+                        "ARCHIVED PROJECTS"
+                    } else {
+                        "PROJECTS"
+                    }
+                    .cyan(),
+                    projects.len(),
+                    if projects.len() == 1 {
+                        "project"
+                    } else {
+                        "projects"
+                    }
+                );
+
+                // Projects sharing a name are told apart by their slugs
+                let shares_name = |name: &str| {
+                    store
+                        .get_active_projects()
+                        .filter(|p| p.name.eq_ignore_ascii_case(name))
+                        .count()
+                        > 1
+                };
+
+                for project in projects {
+                    // Count active tasks in this project
+                    let task_count = store
+                        .get_tasks_for_project(project.id)
+                        .filter(|t| t.deleted_at.is_none())
+                        .count();
+
+                    // Display project name
+                    if shares_name(&project.name) {
+                        println!(
+                            "{} {} {}",
+                            "•".green(),
+                            project.name.bold(),
+                            format!("({})", project.slug).dimmed()
+                        );
+                    } else {
+                        println!("{} {}", "•".green(), project.name.bold());
+                    }
+
+                    // Display area if project belongs to one
+                    if let Some(area_id) = project.area_id
+                        && let Some(area) = store.get_area(area_id)
+                    {
+                        println!("    {} {}", "Area:".dimmed(), area.name.blue());
+                    }
+
+                    // Display tags if the project has any
+                    if !project.tags.is_empty() {
+                        println!(
+                            "    {} {}",
+                            "Tags:".dimmed(),
+                            project
+                                .tags
+                                .iter()
+                                .map(|t| format!("#{}", t))
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                                .blue()
+                        );
+                    }
+
+                    // Display task count
+                    println!(
+                        "    {} {}",
+                        task_count.to_string().dimmed(),
+                        if task_count == 1 { "task" } else { "tasks" }.dimmed()
+                    );
+
+                    // Display the nearest deadline among open tasks
+                    if let Some(deadline) = store.get_next_project_deadline(project.id) {
+                        println!("    {}", ui::format_next_due(deadline));
+                    }
+
+                    // Display the projects it waits on
+                    if let Some(waiting) =
+                        ui::format_waiting_on(&store.get_blocking_projects(project.id))
+                    {
+                        println!("    {}", waiting);
+                    }
+
+                    if let Some(archived_at) = project.archived_at {
+                        println!(
+                            "    {}",
+                            format!(
+                                "archived {}",
+                                ui::get_local_date(archived_at).strftime("%b %d, %Y")
+                            )
+                            .dimmed()
+                        );
+                    } else if let Some(area) = project.area_id.and_then(|id| store.get_area(id))
+                        && let Some(archived_at) = area.archived_at
+                    {
+                        println!(
+                            "    {}",
+                            format!(
+                                "archived {} with area {}",
+                                ui::get_local_date(archived_at).strftime("%b %d, %Y"),
+                                area.name
+                            )
+                            .dimmed()
+                        );
+                    }
+
+                    // Display separator
+                    println!("    {}", "─".repeat(30).dimmed());
+                    println!();
+                }
+            }
+
+            if !incubated.is_empty() {
+                println!(
+                    "{}",
+                    format!(
+                        "{} incubated {} not shown (see `tdo incubator`)",
+                        incubated.len(),
+                        if incubated.len() == 1 {
+                            "project"
+                        } else {
+                            "projects"
+                        }
+                    )
+                    .dimmed()
+                );
+            }
+            if hidden_archived > 0 {
+                println!(
+                    "{}",
+                    format!(
+                        "{} archived {} not shown (see `tdo project list --archived`)",
+                        hidden_archived,
+                        if hidden_archived == 1 {
+                            "project"
+                        } else {
+                            "projects"
+                        }
+                    )
+                    .dimmed()
+                );
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Duplicate { slug, name })) => {
+            let params = DuplicateProjectParameters { slug, name };
+
+            match duplicate_project(&mut store, &storage, params) {
+                Ok(DuplicateProjectResult {
+                    project,
+                    tasks_count,
+                }) => {
+                    println!(
+                        "✓ Project {} created with slug {}",
+                        project.name, project.slug
+                    );
+                    println!(
+                        "  {}",
+                        format!(
+                            "{} {} copied",
+                            tasks_count,
+                            if tasks_count == 1 { "task" } else { "tasks" }
+                        )
+                        .dimmed()
+                    );
+                }
+                Err(DuplicateProjectError::ProjectNotFound(slug)) => {
+                    eprintln!("Error: Project '{}' not found", slug);
+                    eprintln!("\nUse `tdo project list` to see project slugs");
+                    std::process::exit(1);
+                }
+                Err(DuplicateProjectError::EmptyName) => {
+                    eprintln!("Error: The new name is empty");
+                    std::process::exit(1);
+                }
+                Err(DuplicateProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Order {
+            slug,
+            before,
+            after,
+        })) => {
+            let placement = match (before, after) {
+                (Some(other), _) => ProjectPlacement::Before(other),
+                (None, Some(other)) => ProjectPlacement::After(other),
+                (None, None) => unreachable!("clap requires --before or --after"),
+            };
+            let params = OrderProjectParameters {
+                slug: slug.clone(),
+                placement,
+            };
+
+            match order_project(&mut store, &storage, params) {
+                Ok(projects) => {
+                    let moved = projects
+                        .iter()
+                        .find(|p| p.slug.eq_ignore_ascii_case(&slug))
+                        .map(|p| p.id);
+                    println!("✓ Project order updated");
+                    for (index, project) in projects.iter().enumerate() {
+                        let line = format!("{:>3}. {}", index + 1, project.name);
+                        if Some(project.id) == moved {
+                            println!("  {}", line.bold());
+                        } else {
+                            println!("  {}", line.dimmed());
+                        }
+                    }
+                }
+                Err(OrderProjectError::ProjectNotFound(slug)) => {
+                    eprintln!("Error: Project '{}' not found", slug);
+                    eprintln!("\nUse `tdo project list` to see project slugs");
+                    std::process::exit(1);
+                }
+                Err(OrderProjectError::SameProject) => {
+                    eprintln!("Error: A project can't be placed next to itself");
+                    std::process::exit(1);
+                }
+                Err(OrderProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project order: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Archive { project, unarchive })) => {
+            let params = ArchiveProjectParameters { project, unarchive };
+
+            match archive_project(&mut store, &storage, params) {
+                Ok(project) if unarchive => {
+                    println!("✓ Project unarchived: {}", project.name);
+                }
+                Ok(project) => {
+                    let open_tasks = store
+                        .get_tasks_for_project(project.id)
+                        .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                        .count();
+                    println!("✓ Project archived: {}", project.name);
+                    if open_tasks > 0 {
+                        println!(
+                            "  {}",
+                            format!(
+                                "{} open {} kept, out of the working views",
+                                open_tasks,
+                                if open_tasks == 1 { "task" } else { "tasks" }
+                            )
+                            .dimmed()
+                        );
+                    }
+                    println!(
+                        "  {}",
+                        format!(
+                            "Bring it back with `tdo project archive {} --unarchive`",
+                            project.slug
+                        )
+                        .dimmed()
+                    );
+                }
+                Err(ArchiveProjectError::ProjectNotFound(name)) => {
+                    eprintln!("Error: Project '{}' not found", name);
+                    eprintln!("\nUse `tdo project list` to see project slugs");
+                    std::process::exit(1);
+                }
+                Err(ArchiveProjectError::AmbiguousProjectName(names)) => {
+                    eprintln!("Error: Project name is ambiguous. Multiple projects found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(ArchiveProjectError::AlreadyArchived(name)) => {
+                    eprintln!("Error: Project '{}' is already archived", name);
+                    std::process::exit(1);
+                }
+                Err(ArchiveProjectError::NotArchived(name)) => {
+                    eprintln!("Error: Project '{}' is not archived", name);
+                    std::process::exit(1);
+                }
+                Err(ArchiveProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Edit {
+            slug,
+            tag,
+            untag,
+            deadline,
+            clear_deadline,
+            color,
+            clear_color,
+            icon,
+            clear_icon,
+        })) => {
+            let params = EditProjectParameters {
+                slug,
+                add_tags: tag,
+                remove_tags: untag,
+                deadline,
+                clear_deadline,
+                color,
+                clear_color,
+                icon,
+                clear_icon,
+            };
+
+            match edit_project(&mut store, &storage, params) {
+                Ok(project) => {
+                    println!("✓ Project updated: {}", project.name);
+                    if let Some(tags) = ui::format_tags(&project.tags) {
+                        println!("  {}", tags);
+                    }
+                    if let Some(deadline) = project.deadline {
+                        println!("  ⚑ {}", deadline.strftime("%a, %b %d %Y"));
+                    }
+                    if let Some(label) =
+                        ui::format_label(project.color.as_deref(), project.icon.as_deref())
+                    {
+                        println!("  {}", label);
+                    }
+                }
+                Err(EditProjectError::InvalidDeadline(date_str, error)) => {
+                    exit_with_deadline_error(&date_str, &error);
+                }
+                Err(EditProjectError::InvalidColor(color)) => {
+                    exit_with_color_error(&color);
+                }
+                Err(EditProjectError::ProjectNotFound(slug)) => {
+                    eprintln!("Error: Project '{}' not found", slug);
+
+                    let projects: Vec<_> = store.get_active_projects().collect();
+                    if !projects.is_empty() {
+                        eprintln!("\nAvailable projects:");
+                        for p in projects {
+                            eprintln!("  - {} ({})", p.name, p.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(EditProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Notes { slug, notes, clear })) => {
+            let notes = if clear {
+                None
+            } else if notes.is_some() {
+                notes
+            } else {
+                let current = store
+                    .get_active_projects()
+                    .find(|p| p.slug.to_lowercase() == slug.to_lowercase())
+                    .map(|p| p.notes.clone().unwrap_or_default())
+                    .unwrap_or_else(|| {
+                        eprintln!("Error: Project '{}' not found", slug);
+                        std::process::exit(1);
+                    });
+
+                let edited = editor::edit_text(&current).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+                if edited.trim_end() == current.trim_end() {
+                    println!("✓ Notes unchanged");
+                    return;
+                }
+                Some(edited)
+            };
+
+            let params = SetProjectNotesParameters { slug, notes };
+            match set_project_notes(&mut store, &storage, params) {
+                Ok(project) => match &project.notes {
+                    Some(notes) => {
+                        println!("✓ Notes saved: {}", project.name);
+                        ui::render_notes_preview(notes);
+                    }
+                    None => println!("✓ Notes removed: {}", project.name),
+                },
+                Err(SetProjectNotesError::ProjectNotFound(slug)) => {
+                    eprintln!("Error: Project '{}' not found", slug);
+
+                    let projects: Vec<_> = store.get_active_projects().collect();
+                    if !projects.is_empty() {
+                        eprintln!("\nAvailable projects:");
+                        for p in projects {
+                            eprintln!("  - {} ({})", p.name, p.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(SetProjectNotesError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::View { slug, all })) => {
+            // Find project by slug (case-insensitive)
+            let project = store
+                .get_active_projects()
+                .find(|p| p.slug.to_lowercase() == slug.to_lowercase());
+
+            match project {
+                None => {
+                    eprintln!("Error: Project '{}' not found", slug);
+
+                    let projects: Vec<_> = store.get_active_projects().collect();
+                    if !projects.is_empty() {
+                        eprintln!("\nAvailable projects:");
+                        for p in projects {
+                            eprintln!("  - {} ({})", p.name, p.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Some(project) => {
+                    // Get tasks for this project
+                    let mut tasks: Vec<_> = store
+                        .get_tasks_for_project(project.id)
+                        .filter(|t| t.completed_at.is_none() && t.deleted_at.is_none())
+                        .collect();
+
+                    tasks.sort_by_key(|t| t.task_number);
+
+                    // Completed tasks for --all, most recently completed first
+                    let mut completed: Vec<_> = store
+                        .get_tasks_for_project(project.id)
+                        .filter(|t| all && t.completed_at.is_some() && t.deleted_at.is_none())
+                        .collect();
+                    completed.sort_by_key(|t| std::cmp::Reverse(t.completed_at));
+                    let render_completed = || {
+                        if completed.is_empty() {
+                            return;
+                        }
+                        ui::render_section_header(&format!("Completed — {}", completed.len()));
+                        for task in &completed {
+                            ui::render_task_line_with_completion_date(task, &store, false);
+                        }
+                    };
+
+                    // Display header with project name and area if applicable
+                    let header = if let Some(area_id) = project.area_id {
+                        if let Some(area) = store.get_area(area_id) {
+                            format!("{} ({})", project.name, area.name)
+                        } else {
+                            project.name.clone()
+                        }
+                    } else {
+                        project.name.clone()
+                    };
+
+                    let archived_note = project.archived_at.map(|at| {
+                        format!(
+                            "archived {} · `tdo project archive {} --unarchive` brings it back",
+                            ui::get_local_date(at).strftime("%b %d"),
+                            project.slug
+                        )
+                    });
+
+                    if tasks.is_empty() {
+                        println!("No tasks in project '{}'", header);
+                        if let Some(note) = &archived_note {
+                            println!("  {}", note.dimmed());
+                        }
+                        if let Some(notes) = &project.notes {
+                            ui::render_notes_preview(notes);
+                        }
+                        render_completed();
+                    } else {
+                        ui::render_view_header(&header, tasks.len());
+                        if let Some(note) = &archived_note {
+                            println!("  {}", note.dimmed());
+                        }
+                        if let Some(notes) = &project.notes {
+                            ui::render_notes_preview(notes);
+                        }
+                        if let Some(deadline) = project.deadline {
+                            let days = ui::days_until(deadline);
+                            let label = format!(
+                                "⚑ project due {} ({})",
+                                deadline.strftime("%b %d"),
+                                ui::format_days_until(days)
+                            );
+                            if days <= 1 {
+                                println!("  {}", ui::alert(&label));
+                            } else {
+                                println!("  {}", label.dimmed());
+                            }
+                        }
+                        if let Some(waiting) =
+                            ui::format_waiting_on(&store.get_blocking_projects(project.id))
+                        {
+                            println!(
+                                "  {} {}",
+                                waiting,
+                                "(hidden from Today and Anytime until then)".dimmed()
+                            );
+                        }
+
+                        // The next action leads, apart from the rest
+                        let next_action = store.get_next_action(project.id);
+                        if let Some(next) = next_action {
+                            ui::render_section_header("Next action");
+                            ui::render_task_line(next, &store, ui::is_overdue(next));
+                            if tasks.len() > 1 {
+                                ui::render_section_header("Other tasks");
+                            }
+                        }
+
+                        for task in &tasks {
+                            if next_action.is_some_and(|next| next.id == task.id) {
+                                continue;
+                            }
+                            let is_overdue = ui::is_overdue(task);
+                            ui::render_task_line(task, &store, is_overdue);
+                        }
+                        render_completed();
+
+                        if config.show_footer {
+                            ui::render_view_footer(&tasks, &store);
+                        }
+                    }
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Next {
+            task_number_or_fuzzy_name,
+        })) => {
+            let params = SetNextActionParameters {
+                task_number_or_fuzzy_name,
+            };
+
+            match set_next_action(&mut store, &storage, params) {
+                Ok((project, task)) => {
+                    println!("✓ Next action for {}: {}", project.name, task.title);
+                    println!("  #{}", task.task_number);
+                }
+                Err(SetNextActionError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task '{}' not found", identifier);
+                    std::process::exit(1);
+                }
+                Err(SetNextActionError::AmbiguousTaskName(titles)) => {
+                    eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+                    for title in titles {
+                        eprintln!("  - {}", title);
+                    }
+                    eprintln!("\nPlease be more specific or use the task number.");
+                    std::process::exit(1);
+                }
+                Err(SetNextActionError::NotInProject(title)) => {
+                    eprintln!("Error: Task '{}' is not in a project", title);
+                    eprintln!("\nAdd it to one first: tdo move <id> -p <project-slug>");
+                    std::process::exit(1);
+                }
+                Err(SetNextActionError::TaskCompleted(title)) => {
+                    eprintln!("Error: Task '{}' is already completed", title);
+                    std::process::exit(1);
+                }
+                Err(SetNextActionError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Block { slug, on })) => {
+            let params = BlockProjectParameters { slug, on_slug: on };
+
+            match block_project(&mut store, &storage, params) {
+                Ok((project, blocker)) => {
+                    println!("✓ {} now starts after {}", project.name, blocker.name);
+                    println!(
+                        "  {}",
+                        "Its tasks stay out of Today and Anytime until then".dimmed()
+                    );
+                }
+                Err(BlockProjectError::ProjectNotFound(slug)) => {
+                    eprintln!("Error: Project '{}' not found", slug);
+                    eprintln!("\nUse `tdo project list` to see project slugs");
+                    std::process::exit(1);
+                }
+                Err(BlockProjectError::SameProject) => {
+                    eprintln!("Error: A project can't wait on itself");
+                    std::process::exit(1);
+                }
+                Err(BlockProjectError::Cycle(project, blocker)) => {
+                    eprintln!(
+                        "Error: '{}' already waits on '{}', so this would make them wait on each other",
+                        blocker, project
+                    );
+                    std::process::exit(1);
+                }
+                Err(BlockProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Unblock { slug, on })) => {
+            let params = UnblockProjectParameters { slug, on_slug: on };
+
+            match unblock_project(&mut store, &storage, params) {
+                Ok(project) => {
+                    println!("✓ Project updated: {}", project.name);
+                    match ui::format_waiting_on(&store.get_blocking_projects(project.id)) {
+                        Some(waiting) => println!("  {}", waiting),
+                        None => println!("  {}", "No longer waiting on other projects".dimmed()),
+                    }
+                }
+                Err(UnblockProjectError::ProjectNotFound(slug)) => {
+                    eprintln!("Error: Project '{}' not found", slug);
+                    eprintln!("\nUse `tdo project list` to see project slugs");
+                    std::process::exit(1);
+                }
+                Err(UnblockProjectError::NotBlockedOn(project, blocker)) => {
+                    eprintln!("Error: '{}' isn't waiting on '{}'", project, blocker);
+                    std::process::exit(1);
+                }
+                Err(UnblockProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Project(ProjectCommands::Export { slug, format })) => {
+            let Some(project) = store
+                .get_active_projects()
+                .find(|p| p.slug.to_lowercase() == slug.to_lowercase())
+            else {
+                eprintln!("Error: Project '{}' not found", slug);
+
+                let projects: Vec<_> = store.get_active_projects().collect();
+                if !projects.is_empty() {
+                    eprintln!("\nAvailable projects:");
+                    for p in projects {
+                        eprintln!("  - {} ({})", p.name, p.slug);
+                    }
+                }
+                std::process::exit(1);
+            };
+
+            print!("{}", export::export_project(format, project, &store));
+        }
+        Some(Commands::Area(AreaCommands::View {
+            slug,
+            tag,
+            today: today_only,
+        })) => {
+            // Find area by slug (case-insensitive)
+            let area = store
+                .get_active_areas()
+                .find(|a| a.slug.to_lowercase() == slug.to_lowercase());
+
+            match area {
+                None => {
+                    eprintln!("Error: Area '{}' not found", slug);
+
+                    let areas: Vec<_> = store.get_active_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAvailable areas:");
+                        for a in areas {
+                            eprintln!("  - {} ({})", a.name, a.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Some(area) => {
+                    // Get projects in this area, leaving out incubated and archived ones
+                    let mut projects: Vec<_> = store
+                        .get_projects_for_area(area.id)
+                        .filter(|p| {
+                            p.deleted_at.is_none()
+                                && p.incubated_at.is_none()
+                                && p.archived_at.is_none()
+                        })
+                        .collect();
+
+                    projects.sort_by_key(|p| p.display_order());
+
+                    // Open tasks filed in the area itself, outside any project
+                    let mut loose_tasks: Vec<_> = store
+                        .get_tasks_for_area(area.id)
+                        .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                        .collect();
+                    loose_tasks.sort_by_key(|t| t.task_number);
+
+                    let archived_note = area.archived_at.map(|at| {
+                        format!(
+                            "archived {} · `tdo area unarchive {}` brings it back",
+                            ui::get_local_date(at).strftime("%b %d"),
+                            area.slug
+                        )
+                    });
+
+                    if tag.is_some() || today_only {
+                        // Only the matching tasks, grouped by project
+                        let today = jiff::Zoned::now().date();
+                        let matches = |task: &Task, project: Option<&Project>| {
+                            tag.as_deref().is_none_or(|tag| {
+                                task.has_tag(tag) || project.is_some_and(|p| p.has_tag(tag))
+                            }) && (!today_only || task.when.is_today(today) || ui::is_overdue(task))
+                        };
+
+                        let project_tasks: Vec<_> = projects
+                            .iter()
+                            .map(|project| {
+                                let mut tasks: Vec<_> = store
+                                    .get_tasks_for_project(project.id)
+                                    .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                                    .filter(|t| matches(t, Some(project)))
+                                    .collect();
+                                tasks.sort_by_key(|t| t.task_number);
+                                (project, tasks)
+                            })
+                            .filter(|(_, tasks)| !tasks.is_empty())
+                            .collect();
+                        loose_tasks.retain(|t| matches(t, None));
+
+                        let mut filters = vec![];
+                        if let Some(tag) = &tag {
+                            filters.push(format!("#{}", tag.trim_start_matches('#')));
+                        }
+                        if today_only {
+                            filters.push("today".to_string());
+                        }
+
+                        let count = project_tasks
+                            .iter()
+                            .map(|(_, tasks)| tasks.len())
+                            .sum::<usize>()
+                            + loose_tasks.len();
+                        if count == 0 {
+                            println!(
+                                "No tasks in area '{}' matching {}",
+                                area.name,
+                                filters.join(" · ")
+                            );
+                            if let Some(note) = &archived_note {
+                                println!("  {}", note.dimmed());
+                            }
+                            return;
+                        }
+
+                        ui::render_view_header(
+                            &format!("{} · {}", area.name, filters.join(" · ")),
+                            count,
+                        );
+                        if let Some(note) = &archived_note {
+                            println!("  {}", note.dimmed());
+                        }
+                        for (project, tasks) in &project_tasks {
+                            ui::render_section_header(&format!(
+                                "{} — {}",
+                                project.name,
+                                tasks.len()
+                            ));
+                            for task in tasks {
+                                ui::render_task_line(task, &store, ui::is_overdue(task));
+                            }
+                        }
+                        if !loose_tasks.is_empty() {
+                            ui::render_section_header(&format!(
+                                "Loose tasks — {}",
+                                loose_tasks.len()
+                            ));
+                            for task in &loose_tasks {
+                                ui::render_task_line(task, &store, ui::is_overdue(task));
+                            }
+                        }
+                    } else if projects.is_empty() && loose_tasks.is_empty() {
+                        println!("No projects or tasks in area '{}'", area.name);
+                        if let Some(note) = &archived_note {
+                            println!("  {}", note.dimmed());
+                        }
+                        if let Some(notes) = &area.notes {
+                            ui::render_notes_preview(notes);
+                        }
+                    } else {
+                        let mut counts = vec![];
+                        if !projects.is_empty() {
+                            counts.push(format!(
+                                "{} {}",
+                                projects.len(),
+                                if projects.len() == 1 {
+                                    "project"
+                                } else {
+                                    "projects"
+                                }
+                            ));
+                        }
+                        if !loose_tasks.is_empty() {
+                            counts.push(format!(
+                                "{} loose {}",
+                                loose_tasks.len(),
+                                if loose_tasks.len() == 1 {
+                                    "task"
+                                } else {
+                                    "tasks"
+                                }
+                            ));
+                        }
+                        println!("\n  {} ({})\n", area.name.cyan().bold(), counts.join(" · "));
+                        if let Some(note) = &archived_note {
+                            println!("  {}\n", note.dimmed());
+                        }
+                        if let Some(notes) = &area.notes {
+                            ui::render_notes_preview(notes);
+                            println!();
+                        }
+
+                        for project in projects {
+                            // Count active tasks in this project
+                            let task_count = store
+                                .get_tasks_for_project(project.id)
+                                .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                                .count();
+
+                            println!("  {} {}", "•".green(), project.name.bold());
+                            println!(
+                                "    {} {}",
+                                task_count.to_string().dimmed(),
+                                if task_count == 1 { "task" } else { "tasks" }.dimmed()
+                            );
+                            if let Some(deadline) = store.get_next_project_deadline(project.id) {
+                                println!("    {}", ui::format_next_due(deadline));
+                            }
+                            if let Some(waiting) =
+                                ui::format_waiting_on(&store.get_blocking_projects(project.id))
+                            {
+                                println!("    {}", waiting);
+                            }
+                            println!();
+                        }
+
+                        if !loose_tasks.is_empty() {
+                            ui::render_section_header(&format!(
+                                "Loose tasks — {}",
+                                loose_tasks.len()
+                            ));
+                            for task in &loose_tasks {
+                                ui::render_task_line(task, &store, ui::is_overdue(task));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Some(Commands::Area(AreaCommands::Deadlines { slug })) => {
+            use jiff::civil::Date;
+            use std::collections::BTreeMap;
+
+            let Some(area) = store
+                .get_active_areas()
+                .find(|a| a.slug.eq_ignore_ascii_case(&slug))
+            else {
+                eprintln!("Error: Area '{}' not found", slug);
+
+                let areas: Vec<_> = store.get_active_areas().collect();
+                if !areas.is_empty() {
+                    eprintln!("\nAvailable areas:");
+                    for a in areas {
+                        eprintln!("  - {} ({})", a.name, a.slug);
+                    }
+                }
+                std::process::exit(1);
+            };
+
+            let today = jiff::Zoned::now().date();
+            let is_archived_project = |project_id: Option<Uuid>| {
+                project_id
+                    .and_then(|id| store.get_project(id))
+                    .is_some_and(|p| p.archived_at.is_some())
+            };
+
+            // Open tasks of the area with a deadline, past ones included,
+            // including those in its projects
+            let tasks: Vec<_> = store
+                .get_tasks_in_area(area.id)
+                .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                .filter(|t| t.deadline.is_some() && !is_archived_project(t.project_id))
+                .collect();
+
+            let projects: Vec<_> = store
+                .get_projects_for_area(area.id)
+                .filter(|p| {
+                    p.deleted_at.is_none() && p.completed_at.is_none() && p.archived_at.is_none()
+                })
+                .filter(|p| p.deadline.is_some())
+                .collect();
+
+            if tasks.is_empty() && projects.is_empty() {
+                println!("No deadlines in area '{}'", area.name);
+                return;
+            }
+
+            let mut grouped: BTreeMap<Date, (Vec<&Project>, Vec<&Task>)> = BTreeMap::new();
+            for project in projects {
+                if let Some(deadline) = project.deadline {
+                    grouped.entry(deadline).or_default().0.push(project);
+                }
+            }
+            for task in &tasks {
+                if let Some(deadline) = task.deadline {
+                    grouped.entry(deadline).or_default().1.push(task);
+                }
+            }
+
+            ui::render_view_header(&format!("{} · Deadlines", area.name), tasks.len());
+
+            // Display by date: project deadlines first, then the tasks
+            for (date, (mut projects, mut tasks)) in grouped {
+                if date < today {
+                    ui::render_section_header(&format!(
+                        "{} — overdue",
+                        ui::format_past_date_header(date)
+                    ));
+                } else {
+                    ui::render_section_header(&ui::format_date_header(date));
+                }
+                projects.sort_by_key(|p| p.display_order());
+                for project in projects {
+                    println!(
+                        "      ⚑  {} {}",
+                        project.name.bold(),
+                        "project deadline".dimmed()
+                    );
+                }
+                tasks.sort_by_key(|t| t.task_number);
+                for task in tasks {
+                    ui::render_task_line(task, &store, ui::is_overdue(task));
+                }
+            }
+        }
+        Some(Commands::Tag(TagCommands::List)) => {
+            // Collect all unique tags from active tasks and projects
+            use std::collections::HashMap;
+
+            let mut tag_counts: HashMap<String, (usize, usize)> = HashMap::new();
+
+            for task in store
+                .get_active_tasks()
+                .filter(|t| t.completed_at.is_none())
+            {
+                for tag in &task.tags {
+                    tag_counts.entry(tag.clone()).or_default().0 += 1;
+                }
+            }
+
+            for project in store
+                .get_active_projects()
+                .filter(|p| p.completed_at.is_none())
+            {
+                for tag in &project.tags {
+                    tag_counts.entry(tag.clone()).or_default().1 += 1;
+                }
+            }
+
+            if tag_counts.is_empty() {
+                println!("No tags found");
+            } else {
+                let mut tags: Vec<_> = tag_counts.iter().collect();
+                tags.sort_by_key(|(tag, _)| tag.to_lowercase());
+
+                println!(
+                    "{} ({} {})\n",
+                    "TAGS".cyan(),
+                    tags.len(),
+                    if tags.len() == 1 { "tag" } else { "tags" }
+                );
+
+                for (tag, (task_count, project_count)) in tags {
+                    let mut counts = Vec::new();
+                    if *task_count > 0 {
+                        counts.push(format!(
+                            "{} {}",
+                            task_count,
+                            if *task_count == 1 { "task" } else { "tasks" }
+                        ));
+                    }
+                    if *project_count > 0 {
+                        counts.push(format!(
+                            "{} {}",
+                            project_count,
+                            if *project_count == 1 {
+                                "project"
+                            } else {
+                                "projects"
+                            }
+                        ));
+                    }
+
+                    println!(
+                        "  {} {} {}",
+                        "•".green(),
+                        tag.bold(),
+                        format!("({})", counts.join(", ")).dimmed()
+                    );
+                }
+            }
+        }
+        Some(Commands::Tag(TagCommands::View { name })) => {
+            let has_tag = |tags: &[String]| {
+                tags.iter()
+                    .any(|tag| tag.to_lowercase() == name.to_lowercase())
+            };
+
+            // Projects with this tag (case-insensitive)
+            let mut projects: Vec<_> = store
+                .get_active_projects()
+                .filter(|p| p.completed_at.is_none() && has_tag(&p.tags))
+                .collect();
+            projects.sort_by_key(|p| p.name.to_lowercase());
+
+            let tagged_project_ids: Vec<_> = projects.iter().map(|p| p.id).collect();
+
+            // Tasks with this tag, plus every open task of a tagged project
+            let tasks: Vec<_> = store
+                .get_active_tasks()
+                .filter(|t| t.completed_at.is_none())
+                .filter(|t| {
+                    has_tag(&t.tags)
+                        || t.project_id
+                            .is_some_and(|id| tagged_project_ids.contains(&id))
+                })
+                .collect();
+
+            if tasks.is_empty() && projects.is_empty() {
+                println!("No tasks or projects with tag '{}'", name);
+
+                // Suggest available tags
+                use std::collections::HashSet;
+                let available_tags: HashSet<_> = store
+                    .get_active_tasks()
+                    .filter(|t| t.completed_at.is_none())
+                    .flat_map(|t| &t.tags)
+                    .chain(store.get_active_projects().flat_map(|p| &p.tags))
+                    .collect();
+
+                if !available_tags.is_empty() {
+                    println!("\nAvailable tags:");
+                    for tag in available_tags {
+                        println!("  - {}", tag);
+                    }
+                }
+            } else {
+                ui::render_view_header(&format!("#{}", name), tasks.len());
+
+                if !projects.is_empty() {
+                    let names: Vec<_> = projects.iter().map(|p| p.name.as_str()).collect();
+                    println!("  {}", format!("Projects: {}", names.join(", ")).dimmed());
+                }
+
+                render_tasks_by_when(&tasks, &store);
+
+                if config.show_footer {
+                    ui::render_view_footer(&tasks, &store);
+                }
+            }
+        }
+        Some(Commands::Context(ContextCommands::List)) => {
+            // Collect all contexts from active tasks
+            use std::collections::HashMap;
+
+            let mut context_counts: HashMap<String, usize> = HashMap::new();
+
+            for task in store
+                .get_active_tasks()
+                .filter(|t| t.completed_at.is_none())
+            {
+                if let Some(context) = &task.context {
+                    *context_counts.entry(context.to_lowercase()).or_insert(0) += 1;
+                }
+            }
+
+            if context_counts.is_empty() {
+                println!("No contexts found");
+            } else {
+                let mut contexts: Vec<_> = context_counts.iter().collect();
+                contexts.sort_by_key(|(context, _)| context.to_string());
+
+                println!(
+                    "{} ({} {})\n",
+                    "CONTEXTS".cyan(),
+                    contexts.len(),
+                    if contexts.len() == 1 {
+                        "context"
+                    } else {
+                        "contexts"
+                    }
+                );
+
+                for (context, count) in contexts {
+                    println!(
+                        "  {} {} {}",
+                        "•".green(),
+                        format!("@{}", context).bold(),
+                        format!("({} {})", count, if *count == 1 { "task" } else { "tasks" })
+                            .dimmed()
+                    );
+                }
+            }
+        }
+        Some(Commands::Context(ContextCommands::View { name })) => {
+            // Find tasks in this context (case-insensitive)
+            let mut tasks: Vec<_> = store
+                .get_active_tasks()
+                .filter(|t| t.completed_at.is_none() && t.has_context(&name))
+                .collect();
+
+            let context_name = format!("@{}", normalize_context(&name));
+
+            if tasks.is_empty() {
+                println!("No tasks in context '{}'", context_name);
+
+                // Suggest available contexts
+                use std::collections::BTreeSet;
+                let available_contexts: BTreeSet<_> = store
+                    .get_active_tasks()
+                    .filter(|t| t.completed_at.is_none())
+                    .filter_map(|t| t.context.as_ref())
+                    .map(|c| c.to_lowercase())
+                    .collect();
+
+                if !available_contexts.is_empty() {
+                    println!("\nAvailable contexts:");
+                    for context in available_contexts {
+                        println!("  - @{}", context);
+                    }
+                }
+            } else {
+                tasks.sort_by_key(|t| t.task_number);
+                ui::render_view_header(&context_name, tasks.len());
+                for task in &tasks {
+                    let is_overdue = ui::is_overdue(task);
+                    ui::render_task_line(task, &store, is_overdue);
+                }
+                if config.show_footer {
+                    ui::render_view_footer(&tasks, &store);
+                }
+            }
+        }
+        Some(Commands::Context(ContextCommands::Named(_))) => {
+            unreachable!("`tdo context <name>` is rewritten to `view` after parsing")
+        }
+        Some(Commands::Restore(RestoreCommands::Task { task_number })) => {
+            let params = RestoreTaskParameters { task_number };
+
+            match restore_task(&mut store, &storage, params) {
+                Ok(task) => {
+                    println!("✓ Task restored: {}", task.title);
+                    println!("  #{}", task.task_number);
+
+                    // The task comes back into a container that may still be trashed
+                    if let Some(project) = task.project_id.and_then(|id| store.get_project(id))
+                        && project.deleted_at.is_some()
+                    {
+                        println!(
+                            "  {}",
+                            format!(
+                                "Project '{}' is still in the trash (tdo restore project \"{}\")",
+                                project.name, project.name
+                            )
+                            .yellow()
+                        );
+                    }
+                    if let Some(area) = task.area_id.and_then(|id| store.get_area(id))
+                        && area.deleted_at.is_some()
+                    {
+                        println!(
+                            "  {}",
+                            format!(
+                                "Area '{}' is still in the trash (tdo restore area \"{}\")",
+                                area.name, area.name
+                            )
+                            .yellow()
+                        );
+                    }
+                }
+                Err(RestoreTaskError::TaskNotFound(identifier)) => {
+                    eprintln!("Error: Task #{} not found", identifier);
+                    print_trashed_tasks(&store);
+                    std::process::exit(1);
+                }
+                Err(RestoreTaskError::TaskNotDeleted(title)) => {
+                    eprintln!("Error: Task '{}' is not in the trash", title);
+                    std::process::exit(1);
+                }
+                Err(RestoreTaskError::Storage(e)) => {
+                    eprintln!("Error: Failed to save task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Restore(RestoreCommands::Project {
+            name,
+            without_tasks,
+            ..
+        })) => {
+            let params = RestoreProjectParameters {
+                name,
+                with_tasks: !without_tasks,
+            };
+
+            match restore_project(&mut store, &storage, params) {
+                Ok(RestoreProjectResult {
+                    project,
+                    restored_tasks_count,
+                }) => {
+                    println!("✓ Project restored: {}", project.name);
+                    if restored_tasks_count > 0 {
+                        println!(
+                            "  {} {} restored with it",
+                            restored_tasks_count,
+                            if restored_tasks_count == 1 {
+                                "task"
+                            } else {
+                                "tasks"
+                            }
+                        );
+                    }
+
+                    let trashed_tasks = store
+                        .get_tasks_for_project(project.id)
+                        .filter(|t| t.deleted_at.is_some())
+                        .count();
+                    if trashed_tasks > 0 {
+                        let hint = if without_tasks {
+                            " (see `tdo trash`)"
+                        } else {
+                            ""
+                        };
+                        println!(
+                            "  {}",
+                            format!(
+                                "{} {} of this project {} still in the trash{}",
+                                trashed_tasks,
+                                if trashed_tasks == 1 { "task" } else { "tasks" },
+                                if trashed_tasks == 1 { "is" } else { "are" },
+                                hint
+                            )
+                            .dimmed()
+                        );
+                    }
+
+                    if let Some(area) = project.area_id.and_then(|id| store.get_area(id))
+                        && area.deleted_at.is_some()
+                    {
+                        println!(
+                            "  {}",
+                            format!(
+                                "Area '{}' is still in the trash (tdo restore area \"{}\")",
+                                area.name, area.name
+                            )
+                            .yellow()
+                        );
+                    }
+                }
+                Err(RestoreProjectError::ProjectNotFound(name)) => {
+                    eprintln!("Error: No deleted project matching '{}'", name);
+
+                    let projects: Vec<_> = store.get_deleted_projects().collect();
+                    if !projects.is_empty() {
+                        eprintln!("\nProjects in the trash:");
+                        for project in projects {
+                            eprintln!("  - {} ({})", project.name, project.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(RestoreProjectError::ProjectNotDeleted(name)) => {
+                    eprintln!("Error: Project '{}' is not in the trash", name);
+                    std::process::exit(1);
+                }
+                Err(RestoreProjectError::AmbiguousProjectName(names)) => {
+                    eprintln!("Error: Project name is ambiguous. Multiple deleted projects found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(RestoreProjectError::Storage(e)) => {
+                    eprintln!("Error: Failed to save project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Restore(RestoreCommands::Area {
+            name,
+            without_contents,
+            ..
+        })) => {
+            let params = RestoreAreaParameters {
+                name,
+                with_contents: !without_contents,
+            };
+
+            match restore_area(&mut store, &storage, params) {
+                Ok(RestoreAreaResult {
+                    area,
+                    restored_projects_count,
+                    restored_tasks_count,
+                }) => {
+                    println!("✓ Area restored: {}", area.name);
+                    if restored_projects_count + restored_tasks_count > 0 {
+                        println!(
+                            "  {} {} and {} {} restored with it",
+                            restored_projects_count,
+                            if restored_projects_count == 1 {
+                                "project"
+                            } else {
+                                "projects"
+                            },
+                            restored_tasks_count,
+                            if restored_tasks_count == 1 {
+                                "task"
+                            } else {
+                                "tasks"
+                            }
+                        );
+                    }
+
+                    let trashed_projects = store
+                        .get_projects_for_area(area.id)
+                        .filter(|p| p.deleted_at.is_some())
+                        .count();
+                    let trashed_tasks = store
+                        .get_tasks_in_area(area.id)
+                        .filter(|t| t.deleted_at.is_some())
+                        .count();
+                    if trashed_projects + trashed_tasks > 0 {
+                        let hint = if without_contents {
+                            " (see `tdo trash`)"
+                        } else {
+                            ""
+                        };
+                        println!(
+                            "  {}",
+                            format!(
+                                "{} {} and {} {} of this area are still in the trash{}",
+                                trashed_projects,
+                                if trashed_projects == 1 {
+                                    "project"
+                                } else {
+                                    "projects"
+                                },
+                                trashed_tasks,
+                                if trashed_tasks == 1 { "task" } else { "tasks" },
+                                hint
+                            )
+                            .dimmed()
+                        );
+                    }
+                }
+                Err(RestoreAreaError::AreaNotFound(name)) => {
+                    eprintln!("Error: No deleted area matching '{}'", name);
+
+                    let areas: Vec<_> = store.get_deleted_areas().collect();
+                    if !areas.is_empty() {
+                        eprintln!("\nAreas in the trash:");
+                        for area in areas {
+                            eprintln!("  - {} ({})", area.name, area.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(RestoreAreaError::AreaNotDeleted(name)) => {
+                    eprintln!("Error: Area '{}' is not in the trash", name);
+                    std::process::exit(1);
+                }
+                Err(RestoreAreaError::AmbiguousAreaName(names)) => {
+                    eprintln!("Error: Area name is ambiguous. Multiple deleted areas found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(RestoreAreaError::Storage(e)) => {
+                    eprintln!("Error: Failed to save area: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Import(ImportCommands::Markdown { file, project })) => {
+            let content = match std::fs::read_to_string(&file) {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("Error: Failed to read '{}': {}", file.display(), e);
+                    std::process::exit(1);
+                }
+            };
+
+            let params = ImportMarkdownParameters { content, project };
+
+            match import_markdown(&mut store, &storage, params) {
+                Ok(result) => {
+                    let completed_count = result
+                        .tasks
+                        .iter()
+                        .filter(|t| t.completed_at.is_some())
+                        .count();
+                    println!(
+                        "✓ Imported {} {} from {}",
+                        result.tasks.len(),
+                        if result.tasks.len() == 1 {
+                            "task"
+                        } else {
+                            "tasks"
+                        },
+                        file.display()
+                    );
+                    if completed_count > 0 {
+                        println!("  {} already completed", completed_count);
+                    }
+                    for project in &result.created_projects {
+                        println!("  Created project: {} ({})", project.name, project.slug);
+                    }
+                }
+                Err(ImportMarkdownError::NothingToImport) => {
+                    eprintln!(
+                        "Error: No `- [ ]` or `- [x]` items found in '{}'",
+                        file.display()
+                    );
+                    std::process::exit(1);
+                }
+                Err(ImportMarkdownError::ProjectNotFound(name)) => {
+                    eprintln!("Error: Project '{}' not found", name);
+                    let projects: Vec<_> = store.get_active_projects().collect();
+                    if !projects.is_empty() {
+                        eprintln!("\nAvailable projects:");
+                        for p in projects {
+                            eprintln!("  - {} ({})", p.name, p.slug);
+                        }
+                    }
+                    std::process::exit(1);
+                }
+                Err(ImportMarkdownError::AmbiguousProjectName(names)) => {
+                    eprintln!("Error: Project name is ambiguous. Multiple projects found:");
+                    for name in names {
+                        eprintln!("  - {}", name);
+                    }
+                    eprintln!("\nPlease be more specific or use the slug.");
+                    std::process::exit(1);
+                }
+                Err(ImportMarkdownError::Storage(e)) => {
+                    eprintln!("Error: Failed to save imported tasks: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Backup(BackupCommands::Verify)) => {
+            let verifications = match storage.verify_backups() {
+                Ok(verifications) => verifications,
+                Err(e) => {
+                    eprintln!("Error: Failed to read backups: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            if verifications.is_empty() {
+                println!("No backups found");
+            } else {
+                println!(
+                    "{} ({} {})\n",
+                    "BACKUPS".cyan(),
+                    verifications.len(),
+                    if verifications.len() == 1 {
+                        "backup"
+                    } else {
+                        "backups"
+                    }
+                );
+
+                let mut usable_count = 0;
+
+                for verification in &verifications {
+                    let name = verification
+                        .path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| verification.path.display().to_string());
+
+                    match &verification.result {
+                        Ok((backup, issues)) if issues.is_empty() => {
+                            usable_count += 1;
+                            println!("  {} {}", ui::success("✓"), name.bold());
+                            println!(
+                                "    {}",
+                                format!(
+                                    "{} tasks • {} projects • {} areas",
+                                    backup.tasks.len(),
+                                    backup.projects.len(),
+                                    backup.areas.len()
+                                )
+                                .dimmed()
+                            );
+                        }
+                        Ok((_, issues)) => {
+                            println!("  {} {}", ui::alert("✗"), name.bold());
+                            for issue in issues {
+                                println!("    {} {}", "•".dimmed(), issue);
+                            }
+                        }
+                        Err(e) => {
+                            println!("  {} {}", ui::alert("✗"), name.bold());
+                            println!("    {} {}", "•".dimmed(), e);
+                        }
+                    }
+                }
+
+                println!(
+                    "\n{} of {} backups usable as restore points",
+                    usable_count,
+                    verifications.len()
+                );
+
+                if usable_count < verifications.len() {
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Doctor { fix }) => {
+            let params = DoctorParameters { fix };
+
+            match run_doctor(&mut store, &storage, params) {
+                Ok(report) => {
+                    if report.integrity_issues.is_empty() && report.orphans.is_empty() {
+                        println!("✓ No problems found");
+                        return;
+                    }
+
+                    if !report.integrity_issues.is_empty() {
+                        ui::render_section_header(&format!(
+                            "Integrity issues ({})",
+                            report.integrity_issues.len()
+                        ));
+                        for issue in &report.integrity_issues {
+                            println!("  {} {}", ui::alert("•"), issue);
+                        }
+                    }
+
+                    if !report.orphans.is_empty() {
+                        ui::render_section_header(&format!("Orphans ({})", report.orphans.len()));
+                        for orphan in &report.orphans {
+                            println!("  {} {}", "•".yellow(), orphan);
+                        }
+                    }
+
+                    println!();
+                    if fix {
+                        println!("✓ {} reference(s) repaired", report.repaired_count);
+                    } else {
+                        println!(
+                            "Run `tdo doctor --fix` to detach orphans from deleted projects and areas"
+                        );
+                    }
+                }
+                Err(DoctorError::Storage(e)) => {
+                    eprintln!("Error: Failed to save repaired store: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Selftest) => {
+            let checks = selftest::run_selftest(&data_dir, &store);
+            let mut failed = 0;
+
+            for check in &checks {
+                let (glyph, message) = match &check.outcome {
+                    selftest::CheckOutcome::Pass(message) => (ui::success("✓"), message),
+                    selftest::CheckOutcome::Warn(message) => ("!".yellow(), message),
+                    selftest::CheckOutcome::Fail(message) => {
+                        failed += 1;
+                        (ui::alert("✗"), message)
+                    }
+                };
+                println!("  {} {}", glyph, check.name.bold());
+                println!("    {}", message.dimmed());
+            }
+
+            println!();
+            if failed == 0 {
+                println!("✓ All checks passed");
+            } else {
+                eprintln!("Error: {} of {} checks failed", failed, checks.len());
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Schema) => {
+            let schema = schema::store_schema();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&schema).expect("schema serializes")
+            );
+        }
+        Some(Commands::Groom { apply }) => {
+            if config.groom_rules.is_empty() {
+                println!("No grooming rules configured");
+                println!(
+                    "{}",
+                    format!("Add `groom_rules` to {}", config_path.display()).dimmed()
+                );
+                return;
+            }
+
+            let params = GroomParameters {
+                rules: config.groom_rules.clone(),
+                apply,
+            };
+
+            match groom(&mut store, &storage, params) {
+                Ok(changes) => {
+                    if changes.is_empty() {
+                        println!("✓ Nothing to groom");
+                        return;
+                    }
+
+                    for change in &changes {
+                        let actions: Vec<_> = change
+                            .actions
+                            .iter()
+                            .map(|action| match action {
+                                GroomAction::AddTag(tag) => format!("+#{}", tag),
+                                GroomAction::MoveTo(GroomList::Inbox) => "→ Inbox".to_string(),
+                                GroomAction::MoveTo(GroomList::Anytime) => "→ Anytime".to_string(),
+                                GroomAction::MoveTo(GroomList::Someday) => "→ Someday".to_string(),
+                            })
+                            .collect();
+                        println!(
+                            "  {} {}  {}",
+                            format!("#{}", change.task.task_number).dimmed(),
+                            change.task.title,
+                            actions.join(", ").cyan()
+                        );
+                    }
+
+                    println!();
+                    if apply {
+                        println!("✓ Groomed {} task(s)", changes.len());
+                    } else {
+                        println!(
+                            "{} task(s) would change. Run `tdo groom --apply` to save",
+                            changes.len()
+                        );
+                    }
+                }
+                Err(GroomError::Storage(e)) => {
+                    eprintln!("Error: Failed to save groomed tasks: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Autoschedule {
+            per_day,
+            tag,
+            skip_weekends,
+        }) => {
+            let params = AutoscheduleParameters {
+                per_day,
+                tag,
+                start: jiff::Zoned::now()
+                    .date()
+                    .tomorrow()
+                    .expect("tomorrow should be valid"),
+                skip_weekends: skip_weekends || config.autoschedule_skip_weekends,
+            };
+
+            match autoschedule(&mut store, &storage, params) {
+                Ok(scheduled) => {
+                    if scheduled.is_empty() {
+                        println!("✓ No Anytime tasks to schedule");
+                        return;
+                    }
+
+                    let mut current_date = None;
+                    for task in &scheduled {
+                        if let When::Scheduled { date, .. } = task.when
+                            && current_date != Some(date)
+                        {
+                            ui::render_section_header(&ui::format_date_header(date));
+                            current_date = Some(date);
+                        }
+                        ui::render_task_line(task, &store, true);
+                    }
+                    println!("\n✓ Scheduled {} task(s)", scheduled.len());
+                }
+                Err(AutoscheduleError::InvalidPerDay) => {
+                    eprintln!("Error: --per-day must be at least 1");
+                    std::process::exit(1);
+                }
+                Err(AutoscheduleError::Storage(e)) => {
+                    eprintln!("Error: Failed to save scheduled tasks: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Plan(PlanCommands::Week { start, print })) => {
+            let today = jiff::Zoned::now().date();
+            let start = match start {
+                Some(start_str) => start_str.parse::<jiff::civil::Date>().unwrap_or_else(|e| {
+                    eprintln!("Error: Invalid start date '{}': {}", start_str, e);
+                    eprintln!("\nExpected format: YYYY-MM-DD (e.g., 2025-03-01)");
+                    std::process::exit(1);
+                }),
+                None => today,
+            };
+
+            let original = plan::week_plan_markdown(&store, start, today);
+            if print {
+                print!("{}", original);
+                return;
+            }
+
+            let edited = editor::edit_text(&original).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            if edited.trim_end() == original.trim_end() {
+                println!("✓ Plan unchanged");
+                return;
+            }
+
+            let params = ApplyWeekPlanParameters { original, edited };
+            match apply_week_plan(&mut store, &storage, params) {
+                Ok(result) => {
+                    if result.rescheduled.is_empty() && result.created.is_empty() {
+                        println!("✓ Nothing to schedule");
+                    }
+                    if !result.rescheduled.is_empty() {
+                        ui::render_section_header(&format!(
+                            "Rescheduled ({})",
+                            result.rescheduled.len()
+                        ));
+                        for task in &result.rescheduled {
+                            ui::render_task_line(task, &store, false);
+                        }
+                    }
+                    if !result.created.is_empty() {
+                        ui::render_section_header(&format!("Added ({})", result.created.len()));
+                        for task in &result.created {
+                            ui::render_task_line(task, &store, false);
+                        }
+                    }
+                    for number in result.unknown_numbers {
+                        eprintln!(
+                            "Warning: #{} is not an open task; left out of the plan",
+                            number
+                        );
+                    }
+                }
+                Err(ApplyWeekPlanError::Storage(e)) => {
+                    eprintln!("Error: Failed to save plan: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Undo) => {
+            let record = match storage.last_undoable() {
+                Ok(Some(record)) => record,
+                Ok(None) => {
+                    println!("✓ Nothing to undo");
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error: Failed to read undo log: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let command_line = record.command_line();
+
+            let unchanged = storage.is_unchanged_since(&record).unwrap_or_else(|e| {
+                eprintln!("Error: Failed to read store: {}", e);
+                std::process::exit(1);
+            });
+            if !unchanged {
+                eprintln!(
+                    "Error: The store was changed outside tdo after `{}`, so it can't be undone",
+                    command_line
+                );
+                std::process::exit(1);
+            }
+
+            let previous = match storage.store_before(&record) {
+                Ok(Some(previous)) => previous,
+                Ok(None) => {
+                    eprintln!(
+                        "Error: No backup holds the store from before `{}`",
+                        command_line
+                    );
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("Error: Failed to load backup: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            let changes = store.changes_to(&previous);
+            if let Err(e) = storage.save_undo(&previous, &record) {
+                eprintln!("Error: Failed to save store: {}", e);
+                std::process::exit(1);
+            }
+
+            println!("✓ Undone: {}", command_line);
+            println!(
+                "  {}",
+                format!("saved {}", ui::format_timestamp(record.saved_at)).dimmed()
+            );
+            for change in &changes {
+                println!("  {} {}", "•".dimmed(), change);
+            }
+        }
+        Some(Commands::Recover {
+            replay,
+            rollback,
+            discard,
+        }) => {
+            let record = match storage.pending_intent() {
+                Ok(Some(record)) => record,
+                Ok(None) => {
+                    println!("✓ Nothing to recover");
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error: Failed to read journal: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let command_line = record.command_line();
+
+            let applied = storage.intent_was_applied(&record).unwrap_or_else(|e| {
+                eprintln!("Error: Failed to read store: {}", e);
+                std::process::exit(1);
+            });
+
+            let clear_intent = || {
+                if let Err(e) = storage.clear_intent() {
+                    eprintln!("Error: Failed to clear journal: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            if discard {
+                clear_intent();
+                println!("✓ Discarded: {}", command_line);
+            } else if replay {
+                if applied {
+                    eprintln!("Error: `{}` already reached the store", command_line);
+                    eprintln!("Use --rollback to undo it, or --discard to keep it");
+                    std::process::exit(1);
+                }
+
+                clear_intent();
+                println!("{}", format!("↻ Replaying: {}", command_line).dimmed());
+
+                let status = std::env::current_exe()
+                    .and_then(|exe| std::process::Command::new(exe).args(&record.args).status());
+                match status {
+                    Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+                    Err(e) => {
+                        eprintln!("Error: Failed to run `{}`: {}", command_line, e);
+                        std::process::exit(1);
+                    }
+                }
+            } else if rollback {
+                if !applied {
+                    eprintln!(
+                        "Error: `{}` never reached the store; there is nothing to roll back",
+                        command_line
+                    );
+                    eprintln!("Use --replay to run it again, or --discard to drop it");
+                    std::process::exit(1);
+                }
+
+                let backup = match storage.backup_before(&record) {
+                    Ok(Some(backup)) => backup,
+                    Ok(None) => {
+                        eprintln!(
+                            "Error: No backup holds the store from before `{}`",
+                            command_line
+                        );
+                        eprintln!("Use --discard to keep the store as it is");
+                        std::process::exit(1);
+                    }
+                    Err(e) => {
+                        eprintln!("Error: Failed to read backups: {}", e);
+                        std::process::exit(1);
+                    }
+                };
+
+                let previous = storage.load_backup(&backup).unwrap_or_else(|e| {
+                    eprintln!("Error: Failed to load backup: {}", e);
+                    std::process::exit(1);
+                });
+                if let Err(e) = storage.save(&previous) {
+                    eprintln!("Error: Failed to save store: {}", e);
+                    std::process::exit(1);
+                }
+
+                clear_intent();
+                println!("✓ Rolled back: {}", command_line);
+            } else {
+                println!("{} {}\n", "INTERRUPTED".yellow(), command_line.bold());
+                println!(
+                    "  Started {}",
+                    ui::format_timestamp(record.started_at).dimmed()
+                );
+
+                if applied {
+                    println!("  The change reached the store, but the save didn't finish.\n");
+                    println!(
+                        "Run `tdo recover --rollback` to undo it, or `tdo recover --discard` to keep it"
+                    );
+                } else {
+                    println!("  The change never reached the store.\n");
+                    println!(
+                        "Run `tdo recover --replay` to run it again, or `tdo recover --discard` to drop it"
+                    );
+                }
+            }
+        }
+        None => {
+            // Default: show today view (same as `tdo today`)
+            show_today(&store, &config, &TodayViewOptions::default());
+            print_inbox_nudge(&store, &config, "tdo inbox");
+        }
+    }
+}
+
+/// Name of the project `name` fuzzy-matches and how many tasks deleting it
+/// would take along; `None` when it doesn't match exactly one project
+fn project_cascade(store: &Store, name: &str) -> Option<(String, usize)> {
+    let mut matches = store
+        .get_active_projects()
+        .filter(|p| p.name.to_lowercase().contains(&name.to_lowercase()));
+    let project = matches.next()?;
+    if matches.next().is_some() {
+        return None;
+    }
+
+    let task_count = store
+        .get_tasks_for_project(project.id)
+        .filter(|t| t.deleted_at.is_none())
+        .count();
+    Some((project.name.clone(), task_count))
+}
+
+/// Same as `project_cascade` for an area, counting the tasks of its projects
+/// and the ones directly in it
+fn area_cascade(store: &Store, name: &str) -> Option<(String, usize)> {
+    let mut matches = store
+        .get_active_areas()
+        .filter(|a| a.name.to_lowercase().contains(&name.to_lowercase()));
+    let area = matches.next()?;
+    if matches.next().is_some() {
+        return None;
+    }
+
+    let project_tasks = store
+        .get_projects_for_area(area.id)
+        .filter(|p| p.deleted_at.is_none())
+        .flat_map(|p| store.get_tasks_for_project(p.id))
+        .filter(|t| t.deleted_at.is_none())
+        .count();
+    let direct_tasks = store
+        .get_tasks_for_area(area.id)
+        .filter(|t| t.deleted_at.is_none())
+        .count();
+    Some((area.name.clone(), project_tasks + direct_tasks))
+}
+
+/// Make the user type `name` before a delete that cascades to more than
+/// `threshold` tasks. When not interactive, `--yes` is required.
+fn confirm_cascade(kind: &str, name: &str, task_count: usize, threshold: Option<usize>) {
+    if threshold.is_none_or(|threshold| task_count <= threshold) {
+        return;
+    }
+
+    let warning = format!(
+        "Deleting {} '{}' would also delete {} tasks",
+        kind, name, task_count
+    );
+
+    if !ui::is_interactive() {
+        eprintln!("Error: {}", warning);
+        eprintln!("\nRe-run with --yes to confirm");
+        std::process::exit(1);
+    }
+
+    println!("{}", format!("⚠ {}", warning).yellow());
+    print!("Type the {} name to confirm: ", kind);
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() || answer.trim() != name {
+        eprintln!("Error: Name didn't match; nothing was deleted");
+        std::process::exit(1);
+    }
+}
+
+/// "3 tasks, 1 project" for the items a purge removes
+fn purge_summary(result: &PurgeResult) -> String {
+    let plural = |count: usize, noun: &str| {
+        format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+    };
+    [
+        (result.tasks.len(), "task"),
+        (result.projects.len(), "project"),
+        (result.areas.len(), "area"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, noun)| plural(count, noun))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Ask whether to create a project or area an add refers to but that doesn't
+/// exist. When not interactive, the add fails as before.
+fn offer_to_create(kind: &str, name: &str) -> bool {
+    if !ui::is_interactive() {
+        return false;
+    }
+
+    print!("{} '{}' doesn't exist. Create it? [y/N] ", kind, name);
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Ask before permanently deleting, refusing when there is no one to ask
+fn confirm_purge(summary: &str) {
+    if !ui::is_interactive() {
+        eprintln!("Error: This would permanently delete {}", summary);
+        eprintln!("\nRe-run with --yes to confirm");
+        std::process::exit(1);
+    }
+
+    println!(
+        "{}",
+        format!("⚠ This will permanently delete {}", summary).yellow()
+    );
+    print!("Continue? [y/N] ");
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    let confirmed = std::io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes");
+    if !confirmed {
+        println!("Nothing was deleted");
+        std::process::exit(0);
+    }
+}
+
+/// Subcommand path used to label journal entries, e.g. `project new`
+fn operation_name(matches: &ArgMatches) -> String {
+    let mut names = vec![];
+    let mut current = matches;
+    while let Some((name, sub_matches)) = current.subcommand() {
+        names.push(name);
+        current = sub_matches;
+    }
+
+    if names.is_empty() {
+        "today".to_string()
+    } else {
+        names.join(" ")
+    }
+}
+
+/// Point at `tdo recover` when the previous command didn't finish saving
+fn warn_about_interrupted_command(storage: &JsonFileStorage) {
+    match storage.pending_intent() {
+        Ok(Some(record)) => eprintln!(
+            "{}",
+            format!(
+                "⚠ `{}` was interrupted while saving. Run `tdo recover` to replay or roll it back.",
+                record.command_line()
+            )
+            .yellow()
+        ),
+        Ok(None) => {}
+        Err(e) => eprintln!("{}", format!("⚠ Failed to read journal: {}", e).yellow()),
+    }
+}
+
+/// Whether the command only reads the store to show it, so it can be handed
+/// a store scoped to a workspace
+fn is_workspace_view(command: &Option<Commands>) -> bool {
+    shows_task_list(command)
+        || matches!(
+            command,
+            Some(
+                Commands::Logbook { .. }
+                    | Commands::Badge { .. }
+                    | Commands::Dashboard
+                    | Commands::Project(ProjectCommands::List { .. })
+                    | Commands::Area(AreaCommands::List { .. })
+                    | Commands::Tag(TagCommands::List)
+                    | Commands::Context(ContextCommands::List)
+            )
+        )
+}
+
+/// Whether the command renders a list of open tasks
+fn shows_task_list(command: &Option<Commands>) -> bool {
+    matches!(
+        command,
+        None | Some(
+            Commands::Today { .. }
+                | Commands::Inbox { .. }
+                | Commands::Tomorrow { .. }
+                | Commands::Upcoming { .. }
+                | Commands::Now
+                | Commands::NextActions
+                | Commands::Anytime { .. }
+                | Commands::Someday { .. }
+                | Commands::All
+                | Commands::Project(ProjectCommands::View { .. })
+                | Commands::Area(AreaCommands::View { .. })
+                | Commands::Area(AreaCommands::Deadlines { .. })
+                | Commands::Dashboard
+                | Commands::Tag(TagCommands::View { .. })
+                | Commands::Context(ContextCommands::View { .. })
+        )
+    )
+}
+
+/// Filters applied to the Today view
+#[derive(Default)]
+struct TodayViewOptions<'a> {
+    /// Only show tasks in this context
+    context: Option<&'a str>,
+    /// Only show the evening bucket
+    evening_only: bool,
+}
+
+/// Render the Today view: overdue tasks, today's tasks and the evening bucket
+/// Suggest processing the Inbox when it's over the configured limit
+fn print_inbox_nudge(store: &Store, config: &Config, suggestion: &str) {
+    let Some(limit) = config.inbox_limit else {
+        return;
+    };
+
+    let inbox_count = store
+        .get_active_tasks()
+        .filter(|t| matches!(t.when, When::Inbox) && t.completed_at.is_none())
+        .count();
+
+    if inbox_count > limit {
+        println!(
+            "\n{}",
+            format!(
+                "Inbox has {} items — consider processing it with `{}`",
+                inbox_count, suggestion
+            )
+            .yellow()
+        );
+    }
+}
+
+fn show_today(store: &Store, config: &Config, options: &TodayViewOptions) {
+    let today = jiff::Zoned::now().date();
+    let context = options.context;
+
+    // Collect today tasks
+    let mut today_regular: Vec<_> = store
+        .get_active_tasks()
+        .filter(|t| t.when.is_today(today) && !t.when.is_evening())
+        .filter(|t| t.completed_at.is_none() && !t.is_deferred())
+        .filter(|t| !store.is_task_on_hold(t))
+        .filter(|t| context.is_none_or(|c| t.has_context(c)))
+        .filter(|_| !options.evening_only)
+        .collect();
+
+    let mut today_evening: Vec<_> = store
+        .get_active_tasks()
+        .filter(|t| t.when.is_today(today) && t.when.is_evening())
+        .filter(|t| t.completed_at.is_none() && !t.is_deferred())
+        .filter(|t| !store.is_task_on_hold(t))
+        .filter(|t| context.is_none_or(|c| t.has_context(c)))
+        .collect();
+
+    // Collect overdue tasks
+    let mut overdue_tasks: Vec<_> = store
+        .get_active_tasks()
+        .filter(|t| {
+            if let When::Scheduled { date, .. } = t.when {
+                date < today && t.completed_at.is_none() && !t.is_deferred()
+            } else {
+                false
+            }
+        })
+        .filter(|t| !store.is_task_on_hold(t))
+        .filter(|t| context.is_none_or(|c| t.has_context(c)))
+        .filter(|_| !options.evening_only)
+        .collect();
+
+    // Pinned tasks first, then by task number
+    today_regular.sort_by_key(|t| (!t.pinned, t.task_number));
+    today_evening.sort_by_key(|t| (!t.pinned, t.task_number));
+    // Most overdue first, so old items don't sink below recent ones
+    overdue_tasks.sort_by_key(|t| (std::cmp::Reverse(ui::days_overdue(t)), t.task_number));
+
+    // Projects have no context, so their deadlines only show in the full view
+    let projects_due: Vec<_> = match config.project_deadline_warning_days {
+        Some(days) if context.is_none() && !options.evening_only => {
+            store.get_projects_due_within(today, days)
+        }
+        _ => vec![],
+    };
+
+    let total = today_regular.len() + today_evening.len() + overdue_tasks.len();
+
+    if total == 0 {
+        if options.evening_only {
+            println!("No tasks for this evening");
+        } else {
+            println!("No tasks for today");
+        }
+        render_project_deadline_warnings(&projects_due, store);
+        return;
+    }
+
+    // Total estimate of everything on the list, so an overcommitted day shows up front
+    let estimated: jiff::SignedDuration = overdue_tasks
+        .iter()
+        .chain(&today_regular)
+        .chain(&today_evening)
+        .filter_map(|t| t.estimate)
+        .sum();
+    let header = if estimated.is_zero() {
+        format!("Today ({})", today.strftime("%b %d"))
+    } else {
+        format!(
+            "Today ({}) · ~{} estimated",
+            today.strftime("%b %d"),
+            ui::format_estimate(estimated)
+        )
+    };
+    ui::render_view_header(&header, total);
+    render_project_deadline_warnings(&projects_due, store);
+
+    let has_overdue = !overdue_tasks.is_empty();
+
+    // Show overdue first if any
+    if has_overdue {
+        ui::render_section_header(&format!("Overdue — {}", overdue_tasks.len()));
+        for task in &overdue_tasks {
+            ui::render_task_line(task, store, true);
+        }
+    }
+
+    let render_regular = |with_header: bool| {
+        if today_regular.is_empty() {
+            return;
+        }
+        if with_header {
+            ui::render_section_header(&format!("Today — {}", today_regular.len()));
+        }
+        for task in &today_regular {
+            ui::render_task_line(task, store, false);
+        }
+    };
+
+    let render_evening = || {
+        if today_evening.is_empty() {
+            return;
+        }
+        ui::render_section_header(&format!("Evening — {}", today_evening.len()));
+        for task in &today_evening {
+            ui::render_task_line(task, store, false);
+        }
+    };
 
-                    tasks.sort_by_key(|t| t.task_number);
+    if config.evening_first {
+        // Regular tasks need their own header once they no longer lead the view
+        render_evening();
+        render_regular(true);
+    } else {
+        // Regular tasks only need a header when they follow another section
+        render_regular(has_overdue || !projects_due.is_empty());
+        render_evening();
+    }
 
-                    // Display header with project name and area if applicable
-                    let header = if let Some(area_id) = project.area_id {
-                        if let Some(area) = store.get_area(area_id) {
-                            format!("{} ({})", project.name, area.name)
-                        } else {
-                            project.name.clone()
-                        }
-                    } else {
-                        project.name.clone()
-                    };
+    if config.show_footer {
+        let shown: Vec<_> = overdue_tasks
+            .iter()
+            .chain(&today_regular)
+            .chain(&today_evening)
+            .copied()
+            .collect();
+        ui::render_view_footer(&shown, store);
+    }
+}
 
-                    if tasks.is_empty() {
-                        println!("No tasks in project '{}'", header);
-                    } else {
-                        ui::render_view_header(&header, tasks.len());
-                        for task in tasks {
-                            let is_overdue = ui::is_overdue(task);
-                            ui::render_task_line(task, &store, is_overdue);
-                        }
-                    }
-                }
-            }
-        }
-        Some(Commands::Area(AreaCommands::View { slug })) => {
-            // Find area by slug (case-insensitive)
-            let area = store
-                .get_active_areas()
-                .find(|a| a.slug.to_lowercase() == slug.to_lowercase());
+/// List projects whose deadline is close, with the open tasks they have left
+fn render_project_deadline_warnings(projects: &[&Project], store: &Store) {
+    if projects.is_empty() {
+        return;
+    }
 
-            match area {
-                None => {
-                    eprintln!("Error: Area '{}' not found", slug);
+    ui::render_section_header(&format!("Project deadlines — {}", projects.len()));
+    for project in projects {
+        let Some(deadline) = project.deadline else {
+            continue;
+        };
+        let open_tasks = store
+            .get_tasks_for_project(project.id)
+            .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+            .count();
+        let days = ui::days_until(deadline);
+        let due = format!(
+            "⚑ {} ({})",
+            deadline.strftime("%b %d"),
+            ui::format_days_until(days)
+        );
+        println!(
+            "      {}  {}  {}",
+            project.name.bold(),
+            if days <= 1 {
+                ui::alert(&due)
+            } else {
+                due.yellow()
+            },
+            format!(
+                "· {} {} left",
+                open_tasks,
+                if open_tasks == 1 { "task" } else { "tasks" }
+            )
+            .dimmed()
+        );
+    }
+}
 
-                    let areas: Vec<_> = store.get_active_areas().collect();
-                    if !areas.is_empty() {
-                        eprintln!("\nAvailable areas:");
-                        for a in areas {
-                            eprintln!("  - {} ({})", a.name, a.slug);
-                        }
-                    }
-                    std::process::exit(1);
-                }
-                Some(area) => {
-                    // Get projects in this area
-                    let mut projects: Vec<_> = store
-                        .get_projects_for_area(area.id)
-                        .filter(|p| p.deleted_at.is_none())
-                        .collect();
+/// Render tasks in sections by schedule, overdue tasks first
+fn render_tasks_by_when(tasks: &[&Task], store: &Store) {
+    let (mut overdue, rest): (Vec<&Task>, Vec<&Task>) =
+        tasks.iter().copied().partition(|t| ui::is_overdue(t));
+    overdue.sort_by_key(|t| (std::cmp::Reverse(ui::days_overdue(t)), t.task_number));
+
+    let mut sections: [(&str, Vec<&Task>); 5] = [
+        ("Today", vec![]),
+        ("Scheduled", vec![]),
+        ("Anytime", vec![]),
+        ("Someday", vec![]),
+        ("Inbox", vec![]),
+    ];
+    for task in rest {
+        let index = match task.when {
+            When::Today { .. } => 0,
+            When::Scheduled { .. } => 1,
+            When::Anytime => 2,
+            When::Someday => 3,
+            When::Inbox => 4,
+        };
+        sections[index].1.push(task);
+    }
 
-                    projects.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    if !overdue.is_empty() {
+        ui::render_section_header(&format!("Overdue — {}", overdue.len()));
+        for task in overdue {
+            ui::render_task_line(task, store, true);
+        }
+    }
 
-                    if projects.is_empty() {
-                        println!("No projects in area '{}'", area.name);
-                    } else {
-                        println!(
-                            "\n  {} ({} {})\n",
-                            area.name.cyan().bold(),
-                            projects.len(),
-                            if projects.len() == 1 {
-                                "project"
-                            } else {
-                                "projects"
-                            }
-                        );
+    for (name, mut section) in sections {
+        if section.is_empty() {
+            continue;
+        }
 
-                        for project in projects {
-                            // Count active tasks in this project
-                            let task_count = store
-                                .get_tasks_for_project(project.id)
-                                .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
-                                .count();
+        // Scheduled tasks read best in date order
+        section.sort_by_key(|t| match t.when {
+            When::Scheduled { date, .. } => (Some(date), t.task_number),
+            _ => (None, t.task_number),
+        });
 
-                            println!("  {} {}", "•".green(), project.name.bold());
-                            println!(
-                                "    {} {}",
-                                task_count.to_string().dimmed(),
-                                if task_count == 1 { "task" } else { "tasks" }.dimmed()
-                            );
-                            println!();
-                        }
-                    }
-                }
-            }
+        ui::render_section_header(&format!("{} — {}", name, section.len()));
+        for task in section {
+            ui::render_task_line(task, store, false);
         }
-        Some(Commands::Tag(TagCommands::List)) => {
-            // Collect all unique tags from active tasks
-            use std::collections::HashMap;
+    }
+}
 
-            let mut tag_counts: HashMap<String, usize> = HashMap::new();
+/// Flip a Today task between the regular list and the evening bucket
+fn set_evening_or_exit(
+    store: &mut Store,
+    storage: &impl Storage,
+    task_number_or_fuzzy_name: String,
+    evening: bool,
+) {
+    let params = SetEveningParameters {
+        task_number_or_fuzzy_name,
+        evening,
+    };
 
-            for task in store
-                .get_active_tasks()
-                .filter(|t| t.completed_at.is_none())
-            {
-                for tag in &task.tags {
-                    *tag_counts.entry(tag.clone()).or_insert(0) += 1;
-                }
+    match set_evening(store, storage, params) {
+        Ok(task) => {
+            match (&task.when, evening) {
+                (When::Scheduled { date, .. }, true) => println!(
+                    "✓ Task moved to the evening of {}: {}",
+                    date.strftime("%a %b %d"),
+                    task.title
+                ),
+                (When::Scheduled { date, .. }, false) => println!(
+                    "✓ Task moved out of the evening of {}: {}",
+                    date.strftime("%a %b %d"),
+                    task.title
+                ),
+                (_, true) => println!("✓ Task moved to this evening: {}", task.title),
+                (_, false) => println!("✓ Task moved to today: {}", task.title),
+            }
+            println!("  #{}", task.task_number);
+        }
+        Err(SetEveningError::TaskNotFound(identifier)) => {
+            eprintln!("Error: Task '{}' not found", identifier);
+            std::process::exit(1);
+        }
+        Err(SetEveningError::AmbiguousTaskName(titles)) => {
+            eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+            for title in titles {
+                eprintln!("  - {}", title);
+            }
+            eprintln!("\nPlease be more specific or use the task number.");
+            std::process::exit(1);
+        }
+        Err(SetEveningError::NotScheduledForADay(title)) => {
+            eprintln!("Error: Task '{}' is not scheduled for a day", title);
+            if evening {
+                eprintln!(
+                    "\nUse `tdo move <id> --today --evening` (or --when <date> --evening) to schedule it"
+                );
             }
+            std::process::exit(1);
+        }
+        Err(SetEveningError::Storage(e)) => {
+            eprintln!("Error: Failed to save task: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
 
-            if tag_counts.is_empty() {
-                println!("No tags found");
+fn set_pinned_or_exit(
+    store: &mut Store,
+    storage: &impl Storage,
+    task_number_or_fuzzy_name: String,
+    pinned: bool,
+) {
+    let params = SetPinnedParameters {
+        task_number_or_fuzzy_name,
+        pinned,
+    };
+
+    match set_pinned(store, storage, params) {
+        Ok(task) => {
+            if pinned {
+                println!("✓ Task pinned: {}", task.title);
             } else {
-                let mut tags: Vec<_> = tag_counts.iter().collect();
-                tags.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
+                println!("✓ Task unpinned: {}", task.title);
+            }
+            println!("  #{}", task.task_number);
+        }
+        Err(SetPinnedError::TaskNotFound(identifier)) => {
+            eprintln!("Error: Task '{}' not found", identifier);
+            std::process::exit(1);
+        }
+        Err(SetPinnedError::AmbiguousTaskName(titles)) => {
+            eprintln!("Error: Task name is ambiguous. Multiple tasks found:");
+            for title in titles {
+                eprintln!("  - {}", title);
+            }
+            eprintln!("\nPlease be more specific or use the task number.");
+            std::process::exit(1);
+        }
+        Err(SetPinnedError::Storage(e)) => {
+            eprintln!("Error: Failed to save task: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
 
-                println!(
-                    "{} ({} {})\n",
-                    "TAGS".cyan(),
-                    tags.len(),
-                    if tags.len() == 1 { "tag" } else { "tags" }
-                );
+/// Follow completions with what's left: remaining Today tasks and projects that are now done
+/// Follow up completed tasks with the feedback picked in the config. A failing
+/// hook only warns, since the tasks are already completed.
+fn give_completion_feedback(feedback: &CompletionFeedback, completed: &[Task], store: &Store) {
+    match feedback {
+        CompletionFeedback::Off => {}
+        CompletionFeedback::Bell => {
+            print!("\x07");
+            let _ = std::io::stdout().flush();
+        }
+        CompletionFeedback::Streak => {
+            let streak = CompletionStreak::from_store(store, jiff::Zoned::now().date());
+            println!("{}", streak.message().dimmed());
+        }
+        CompletionFeedback::Command(command) => {
+            if let Err(e) = hooks::run_hook(command, HookEvent::TaskCompleted, completed) {
+                eprintln!("{}", format!("⚠ {}", e).yellow());
+            }
+        }
+    }
+}
 
-                for (tag, count) in tags {
-                    println!(
-                        "  {} {} {}",
-                        "•".green(),
-                        tag.bold(),
-                        format!("({} {})", count, if *count == 1 { "task" } else { "tasks" })
-                            .dimmed()
-                    );
+fn print_completion_summary(completed: &[Task], store: &Store) {
+    let today = jiff::Zoned::now().date();
+
+    let remaining_today = store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none() && !t.is_deferred())
+        .filter(|t| match t.when {
+            When::Today { .. } => true,
+            When::Scheduled { date, .. } => date <= today,
+            _ => false,
+        })
+        .count();
+
+    println!();
+    if remaining_today == 0 {
+        println!("{}", "Nothing left for today".dimmed());
+    } else {
+        println!(
+            "{}",
+            format!(
+                "{} {} left for today",
+                remaining_today,
+                if remaining_today == 1 {
+                    "task"
+                } else {
+                    "tasks"
                 }
-            }
+            )
+            .dimmed()
+        );
+    }
+
+    let mut project_ids: Vec<_> = completed.iter().filter_map(|t| t.project_id).collect();
+    project_ids.sort();
+    project_ids.dedup();
+
+    for project_id in project_ids {
+        if let Some(project) = store.get_project(project_id)
+            && project.completed_at.is_none()
+            && !store
+                .get_tasks_for_project(project_id)
+                .any(|t| t.completed_at.is_none() && t.deleted_at.is_none())
+        {
+            println!(
+                "{}",
+                format!(
+                    "Project '{}' has no open tasks left — time to wrap it up?",
+                    project.name
+                )
+                .yellow()
+            );
         }
-        Some(Commands::Tag(TagCommands::View { name })) => {
-            // Find tasks with this tag (case-insensitive)
-            let mut tasks: Vec<_> = store
-                .get_active_tasks()
-                .filter(|t| {
-                    t.completed_at.is_none()
-                        && t.tags
-                            .iter()
-                            .any(|tag| tag.to_lowercase() == name.to_lowercase())
-                })
-                .collect();
+    }
+}
 
-            if tasks.is_empty() {
-                println!("No tasks with tag '{}'", name);
+/// List the trashed tasks that can be restored
+fn print_trashed_tasks(store: &Store) {
+    let mut tasks: Vec<_> = store.get_deleted_tasks().collect();
+    if tasks.is_empty() {
+        return;
+    }
 
-                // Suggest available tags
-                use std::collections::HashSet;
-                let available_tags: HashSet<_> = store
-                    .get_active_tasks()
-                    .filter(|t| t.completed_at.is_none())
-                    .flat_map(|t| &t.tags)
-                    .collect();
+    tasks.sort_by_key(|t| t.task_number);
+    eprintln!("\nTasks in the trash:");
+    for task in tasks {
+        eprintln!("  - #{} {}", task.task_number, task.title);
+    }
+}
 
-                if !available_tags.is_empty() {
-                    println!("\nAvailable tags:");
-                    for tag in available_tags {
-                        println!("  - {}", tag);
-                    }
+/// Explain why a task couldn't be added, listing what exists where it helps
+fn exit_with_add_error(store: &Store, error: AddTaskError) -> ! {
+    match error {
+        AddTaskError::ProjectNotFound(name) => {
+            eprintln!("Error: Project '{}' not found", name);
+
+            // Suggest existing projects if any
+            let projects: Vec<_> = store.projects.values().collect();
+            if !projects.is_empty() {
+                eprintln!("\nAvailable projects:");
+                for project in projects {
+                    eprintln!("  - {} ({})", project.name, project.slug);
                 }
             } else {
-                tasks.sort_by_key(|t| t.task_number);
-                ui::render_view_header(&format!("#{}", name), tasks.len());
-                for task in tasks {
-                    let is_overdue = ui::is_overdue(task);
-                    ui::render_task_line(task, &store, is_overdue);
+                eprintln!("\nNo projects exist yet.");
+            }
+            eprintln!("Use --create-project to create it along with the task.");
+            std::process::exit(1);
+        }
+        AddTaskError::AmbiguousProjectName(names) => {
+            eprintln!("Error: Project name is ambiguous. Multiple projects found:");
+            for name in names {
+                eprintln!("  - {}", name);
+            }
+            eprintln!("\nPlease be more specific or use the slug.");
+            std::process::exit(1);
+        }
+        AddTaskError::AreaNotFound(name) => {
+            eprintln!("Error: Area '{}' not found", name);
+
+            // Suggest existing areas if any
+            let areas: Vec<_> = store.areas.values().collect();
+            if !areas.is_empty() {
+                eprintln!("\nAvailable areas:");
+                for area in areas {
+                    eprintln!("  - {} ({})", area.name, area.slug);
                 }
+            } else {
+                eprintln!("\nNo areas exist yet.");
             }
+            eprintln!("Use --create-area to create it along with the task.");
+            std::process::exit(1);
         }
-        None => {
-            // Default: show today view (same as `tdo today`)
-            let today = jiff::Zoned::now().date();
+        AddTaskError::AmbiguousAreaName(names) => {
+            eprintln!("Error: Area name is ambiguous. Multiple areas found:");
+            for name in names {
+                eprintln!("  - {}", name);
+            }
+            eprintln!("\nPlease be more specific or use the slug.");
+            std::process::exit(1);
+        }
+        AddTaskError::AreaWithProject => {
+            eprintln!("Error: Cannot use --area together with --project");
+            eprintln!(
+                "\nTasks in a project belong to the project's area. To surface the task under a different area use:"
+            );
+            eprintln!("  tdo add 'Task' --project <name> --area-override <area>");
+            std::process::exit(1);
+        }
+        AddTaskError::AreaOverrideWithoutProject => {
+            eprintln!("Error: --area-override can only be used with --project");
+            eprintln!("\nUse --area to assign a task directly to an area");
+            std::process::exit(1);
+        }
+        AddTaskError::InvalidDeadline(date_str, error) => {
+            exit_with_deadline_error(&date_str, &error);
+        }
+        AddTaskError::InvalidDeferDate(date_str, error) => {
+            eprintln!("Error: Invalid defer date '{}': {}", date_str, error);
+            eprintln!("\nExpected format: YYYY-MM-DD (e.g., 2025-03-01)");
+            std::process::exit(1);
+        }
+        AddTaskError::InvalidReminder(reminder) => {
+            exit_with_reminder_error(&reminder);
+        }
+        AddTaskError::InvalidEstimate(estimate) => {
+            exit_with_estimate_error(&estimate);
+        }
+        AddTaskError::IdempotencyMismatch(task_number, fields) => {
+            eprintln!(
+                "Error: Task #{} was already added with this id or idempotency key",
+                task_number
+            );
+            eprintln!("\nThis add has a different {}", fields.join(", "));
+            eprintln!("Use a new key to add a different task");
+            std::process::exit(1);
+        }
+        AddTaskError::Storage(e) => {
+            eprintln!("Error: Failed to save task: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
 
-            // Collect today tasks
-            let mut today_regular: Vec<_> = store
-                .get_active_tasks()
-                .filter(|t| matches!(t.when, When::Today { evening: false }))
-                .filter(|t| t.completed_at.is_none())
-                .collect();
+/// Report invalid scheduling flags and exit
+fn exit_with_reminder_error(reminder: &str) -> ! {
+    eprintln!("Error: Invalid reminder '{}'", reminder);
+    eprintln!(
+        "\nExpected a time, optionally after a day: '17:00', 'tomorrow 9:30', 'friday 8:00', '2025-03-01 17:00'"
+    );
+    std::process::exit(1);
+}
 
-            let mut today_evening: Vec<_> = store
-                .get_active_tasks()
-                .filter(|t| matches!(t.when, When::Today { evening: true }))
-                .filter(|t| t.completed_at.is_none())
-                .collect();
+fn exit_with_deadline_error(deadline: &str, error: &str) -> ! {
+    eprintln!("Error: Invalid deadline '{}': {}", deadline, error);
+    eprintln!("\nExpected format: YYYY-MM-DD (e.g., 2025-03-01) or an offset like +3d, +1w, +2m");
+    std::process::exit(1);
+}
 
-            // Collect overdue tasks
-            let mut overdue_tasks: Vec<_> = store
-                .get_active_tasks()
-                .filter(|t| {
-                    if let When::Scheduled { date } = t.when {
-                        date < today && t.completed_at.is_none()
-                    } else {
-                        false
-                    }
-                })
-                .collect();
+fn exit_with_color_error(color: &str) -> ! {
+    eprintln!("Error: Unknown color '{}'", color);
+    eprintln!(
+        "\nUse black, red, green, yellow, blue, magenta, cyan or white, or a bright one (e.g., bright blue)"
+    );
+    std::process::exit(1);
+}
+
+fn exit_with_estimate_error(estimate: &str) -> ! {
+    eprintln!("Error: Invalid estimate '{}'", estimate);
+    eprintln!("\nExpected a duration in hours and minutes: '30m', '1h', '1h30m'");
+    std::process::exit(1);
+}
 
-            // Sort by task number
-            today_regular.sort_by_key(|t| t.task_number);
-            today_evening.sort_by_key(|t| t.task_number);
-            overdue_tasks.sort_by_key(|t| t.task_number);
+/// Show a desktop notification using the platform's command-line notifier
+fn send_desktop_notification(title: &str, body: &str) -> std::io::Result<()> {
+    let status = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {:?} with title {:?}",
+            body.replace('"', "'"),
+            title
+        );
+        std::process::Command::new("osascript")
+            .args(["-e", &script])
+            .status()?
+    } else {
+        std::process::Command::new("notify-send")
+            .args([title, body])
+            .status()?
+    };
 
-            let total = today_regular.len() + today_evening.len() + overdue_tasks.len();
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "notifier exited with {}",
+            status
+        )))
+    }
+}
 
-            if total == 0 {
-                println!("No tasks for today");
-            } else {
-                ui::render_view_header(&format!("Today ({})", today.strftime("%b %d")), total);
+/// Open a URL or file with the platform's default handler
+fn open_with_system(target: &str) -> std::io::Result<()> {
+    let status = if cfg!(target_os = "macos") {
+        std::process::Command::new("open").arg(target).status()?
+    } else if cfg!(target_os = "windows") {
+        // The empty argument is the window title `start` expects before the target
+        std::process::Command::new("cmd")
+            .args(["/C", "start", "", target])
+            .status()?
+    } else {
+        std::process::Command::new("xdg-open")
+            .arg(target)
+            .status()?
+    };
 
-                // Show overdue first if any
-                if !overdue_tasks.is_empty() {
-                    ui::render_section_header("Overdue");
-                    for task in overdue_tasks {
-                        ui::render_task_line(task, &store, true);
-                    }
-                }
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "opener exited with {}",
+            status
+        )))
+    }
+}
 
-                // Show regular today tasks
-                if !today_regular.is_empty() {
-                    for task in today_regular {
-                        ui::render_task_line(task, &store, false);
-                    }
-                }
+fn exit_with_when_error(error: WhenInstantiationError) -> ! {
+    match error {
+        WhenInstantiationError::ScheduleAtIncorrect(date_str) => {
+            eprintln!("Error: Invalid schedule date format: '{}'", date_str);
+            eprintln!(
+                "\nExpected format: YYYY-MM-DD (e.g., 2025-03-01) or relative dates like 'friday', 'next monday'"
+            );
+            std::process::exit(1);
+        }
+        WhenInstantiationError::ConflictingFlags(flags) => {
+            eprintln!("Error: Cannot use multiple scheduling flags together");
+            eprintln!("\nConflicting flags provided: {}", flags.join(", "));
+            eprintln!("\nPlease use only one of:");
+            eprintln!("  --today       Schedule for today");
+            eprintln!("  --tomorrow    Schedule for tomorrow");
+            eprintln!("  --someday     Defer to someday");
+            eprintln!("  --anytime     Available anytime");
+            eprintln!("  --when DATE   Schedule for a specific date");
+            std::process::exit(1);
+        }
+        WhenInstantiationError::EveningWithoutDay => {
+            eprintln!("Error: The --evening flag needs a day: --today, --tomorrow or --when");
+            eprintln!("\nExample: tdo add 'Review PRs' --when friday --evening");
+            std::process::exit(1);
+        }
+    }
+}
 
-                // Show evening tasks
-                if !today_evening.is_empty() {
-                    ui::render_section_header("Evening");
-                    for task in today_evening {
-                        ui::render_task_line(task, &store, false);
-                    }
-                }
+/// Read task titles from stdin, one per line. Blank lines are skipped and
+/// list bullets ("- ", "* ", "- [ ] ") are stripped so pasted notes work as-is.
+fn read_titles_from_stdin() -> Vec<String> {
+    use std::io::BufRead;
+
+    std::io::stdin()
+        .lock()
+        .lines()
+        .map_while(Result::ok)
+        .map(|line| {
+            let line = line.trim();
+            let line = line
+                .strip_prefix("- [ ] ")
+                .or_else(|| line.strip_prefix("- "))
+                .or_else(|| line.strip_prefix("* "))
+                .unwrap_or(line);
+            line.trim().to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Prepend the task's link (and optionally its page title) to the notes
+fn with_link_notes(
+    notes: Option<String>,
+    title: &str,
+    url: Option<String>,
+    fetch_title: bool,
+) -> Option<String> {
+    let mut lines = Vec::new();
+
+    if let Some(link) = url.as_deref().or_else(|| links::find_url(title)) {
+        if fetch_title {
+            match links::fetch_page_title(link) {
+                Ok(page_title) => lines.push(page_title),
+                Err(e) => eprintln!("Warning: Could not fetch title for {}: {}", link, e),
             }
         }
+
+        // Links pasted in the title are already visible, only --url needs saving
+        if url.is_some() {
+            lines.push(link.to_string());
+        }
+    }
+
+    lines.extend(notes);
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
     }
 }
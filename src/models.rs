@@ -1,4 +1,5 @@
 pub mod area;
 pub mod project;
+pub mod schema;
 pub mod store;
 pub mod task;
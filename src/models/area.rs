@@ -1,11 +1,34 @@
 use jiff::Timestamp;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone)]
 pub struct Area {
     pub id: Uuid,
     pub name: String,
     pub slug: String,
+    /// Notes of the area, shown at the top of `area view`
+    pub notes: Option<String>,
+    /// Position set with `tdo area order`; areas without one follow the
+    /// ordered ones alphabetically
+    pub sort_order: Option<u32>,
+    /// Terminal color name the area is shown in, e.g. "green"
+    pub color: Option<String>,
+    /// Emoji or symbol shown before the area name
+    pub icon: Option<String>,
+    /// Set by `tdo area archive`: the area, its projects and their tasks are
+    /// left out of the lists and working views until it's unarchived
+    pub archived_at: Option<Timestamp>,
     pub deleted_at: Option<Timestamp>,
 }
+
+impl Area {
+    /// Key to sort areas by for display: their manual position, then name
+    pub fn display_order(&self) -> (u32, String) {
+        (
+            self.sort_order.unwrap_or(u32::MAX),
+            self.name.to_lowercase(),
+        )
+    }
+}
@@ -1,9 +1,10 @@
 use jiff::Timestamp;
 use jiff::civil::Date;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone)]
 pub struct Project {
     /// UUID of the project
     pub id: Uuid,
@@ -15,8 +16,27 @@ pub struct Project {
     pub area_id: Option<Uuid>,
     /// Notes of the project
     pub notes: Option<String>,
+    /// Tags of the project (e.g., client or team)
+    pub tags: Vec<String>,
     /// Deadline of the project
     pub deadline: Option<Date>,
+    /// Task chosen as the project's next action; the oldest open task when unset
+    pub next_action: Option<Uuid>,
+    /// Projects that must be completed before this one starts
+    pub blocked_by: Vec<Uuid>,
+    /// When the project was shelved for later; its tasks stay out of the
+    /// working views until it is resumed
+    pub incubated_at: Option<Timestamp>,
+    /// When the project was put away without being finished; it leaves the
+    /// lists and views but its tasks are kept
+    pub archived_at: Option<Timestamp>,
+    /// Position set with `tdo project order`; projects without one follow
+    /// the ordered ones alphabetically
+    pub sort_order: Option<u32>,
+    /// Terminal color name the project is shown in, e.g. "blue"
+    pub color: Option<String>,
+    /// Emoji or symbol shown before the project name
+    pub icon: Option<String>,
     /// Completed at timestamp of the project
     pub completed_at: Option<Timestamp>,
     /// Deleted at timestamp of the project
@@ -24,3 +44,21 @@ pub struct Project {
     /// Created at timestamp of the project
     pub created_at: Timestamp,
 }
+
+impl Project {
+    /// Key to sort projects by for display: their manual position, then name
+    pub fn display_order(&self) -> (u32, String) {
+        (
+            self.sort_order.unwrap_or(u32::MAX),
+            self.name.to_lowercase(),
+        )
+    }
+
+    /// Check whether the project carries a tag (case-insensitive, "#" optional)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim_start_matches('#');
+        self.tags
+            .iter()
+            .any(|t| t.trim_start_matches('#').eq_ignore_ascii_case(tag))
+    }
+}
@@ -0,0 +1,129 @@
+use schemars::schema_for;
+use serde_json::{Value, json};
+
+use crate::models::store::{CURRENT_VERSION, StoredStore};
+
+/// JSON Schema (draft 2020-12) of the store file at the current version,
+/// generated from the serde models so it follows them as they change
+pub fn store_schema() -> Value {
+    let mut schema = schema_for!(StoredStore).to_value();
+    schema["title"] = json!("tdo store");
+    // Older files are migrated on load; only the current version is written
+    schema["properties"]["version"] = json!({ "const": CURRENT_VERSION });
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        area::Area,
+        project::Project,
+        task::{Attachment, ChecklistItem, Task, TaskChange, TimeEntry, When},
+    };
+
+    /// Property names of a `$defs` entry, or of the top-level object for `None`
+    fn property_names(schema: &Value, definition: Option<&str>) -> Vec<String> {
+        let object = match definition {
+            Some(name) => &schema["$defs"][name],
+            None => schema,
+        };
+        let mut names: Vec<_> = object["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn key_names(value: &Value) -> Vec<String> {
+        let mut names: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_schema_matches_serialized_models() {
+        let mut task = Task {
+            checklist: vec![ChecklistItem {
+                id: uuid::Uuid::new_v4(),
+                title: "Step".to_string(),
+                completed: false,
+            }],
+            time_entries: vec![TimeEntry {
+                started_at: jiff::Timestamp::now(),
+                stopped_at: None,
+            }],
+            attachments: vec![Attachment {
+                target: "https://example.com".to_string(),
+                label: None,
+            }],
+            ..Task::default()
+        };
+        task.record(TaskChange::Created);
+        let stored = StoredStore {
+            tasks: vec![task],
+            projects: vec![Project::default()],
+            areas: vec![Area::default()],
+            ..StoredStore::default()
+        };
+        let value = serde_json::to_value(&stored).unwrap();
+        let schema = store_schema();
+
+        assert_eq!(key_names(&value), property_names(&schema, None));
+        for (serialized, definition) in [
+            (&value["tasks"][0], "Task"),
+            (&value["tasks"][0]["checklist"][0], "ChecklistItem"),
+            (&value["tasks"][0]["time_entries"][0], "TimeEntry"),
+            (&value["tasks"][0]["history"][0], "TaskEvent"),
+            (&value["tasks"][0]["attachments"][0], "Attachment"),
+            (&value["projects"][0], "Project"),
+            (&value["areas"][0], "Area"),
+        ] {
+            assert_eq!(
+                key_names(serialized),
+                property_names(&schema, Some(definition)),
+                "{}",
+                definition
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_pins_the_current_version() {
+        let schema = store_schema();
+        assert_eq!(schema["properties"]["version"]["const"], CURRENT_VERSION);
+        assert_eq!(schema["title"], "tdo store");
+    }
+
+    #[test]
+    fn test_schema_lists_every_when_variant() {
+        let schema = store_schema();
+        let mut tags: Vec<_> = schema["$defs"]["When"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|variant| variant["properties"]["type"]["const"].clone())
+            .collect();
+        let mut serialized: Vec<_> = [
+            When::Inbox,
+            When::Today { evening: false },
+            When::Someday,
+            When::Anytime,
+            When::Scheduled {
+                date: jiff::civil::date(2025, 3, 1),
+                evening: false,
+            },
+        ]
+        .iter()
+        .map(|when| serde_json::to_value(when).unwrap()["type"].clone())
+        .collect();
+
+        let by_name = |v: &Value| v.as_str().unwrap().to_string();
+        tags.sort_by_key(by_name);
+        serialized.sort_by_key(by_name);
+        assert_eq!(tags, serialized);
+    }
+}
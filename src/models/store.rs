@@ -1,16 +1,25 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use slug::slugify;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
 use uuid::Uuid;
 
-use crate::models::{area::Area, project::Project, task::Task};
+use crate::models::{
+    area::Area,
+    project::Project,
+    task::{Task, When},
+};
 
 /// Current schema version
-pub const CURRENT_VERSION: u32 = 3;
+pub const CURRENT_VERSION: u32 = 25;
 
 /// Storage representation (how data lives on disk as JSON)
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct StoredStore {
+    /// Schema version the file was written at
     pub version: u32,
+    /// Number given to the next task created
     pub next_task_number: u64,
     pub tasks: Vec<Task>,
     pub projects: Vec<Project>,
@@ -29,6 +38,112 @@ impl Default for StoredStore {
     }
 }
 
+/// A referential or numbering problem detected in a store
+#[derive(Debug, Error)]
+pub enum IntegrityIssue {
+    #[error("Task number #{0} is used by more than one task")]
+    DuplicateTaskNumber(u64),
+
+    #[error("Task #{0} has a number beyond the next task number counter")]
+    TaskNumberOutOfRange(u64),
+
+    #[error("Task #{task_number} references missing project {project_id}")]
+    MissingTaskProject { task_number: u64, project_id: Uuid },
+
+    #[error("Task #{task_number} references missing area {area_id}")]
+    MissingTaskArea { task_number: u64, area_id: Uuid },
+
+    #[error("Project '{name}' references missing area {area_id}")]
+    MissingProjectArea { name: String, area_id: Uuid },
+}
+
+impl IntegrityIssue {
+    /// Whether the issue is a reference to an entity that does not exist
+    pub fn is_broken_reference(&self) -> bool {
+        matches!(
+            self,
+            IntegrityIssue::MissingTaskProject { .. }
+                | IntegrityIssue::MissingTaskArea { .. }
+                | IntegrityIssue::MissingProjectArea { .. }
+        )
+    }
+}
+
+/// An active item that still belongs to a deleted project or area
+#[derive(Debug, Error)]
+pub enum Orphan {
+    #[error("Task #{task_number} '{title}' belongs to deleted project '{project}'")]
+    TaskInDeletedProject {
+        task_id: Uuid,
+        task_number: u64,
+        title: String,
+        project: String,
+    },
+
+    #[error("Task #{task_number} '{title}' belongs to deleted area '{area}'")]
+    TaskInDeletedArea {
+        task_id: Uuid,
+        task_number: u64,
+        title: String,
+        area: String,
+    },
+
+    #[error("Project '{name}' belongs to deleted area '{area}'")]
+    ProjectInDeletedArea {
+        project_id: Uuid,
+        name: String,
+        area: String,
+    },
+}
+
+/// A difference between two versions of a store, phrased as what happened
+/// going from the first to the second
+#[derive(Debug, Error)]
+pub enum StoreChange {
+    #[error("Task #{number} '{title}' removed")]
+    TaskRemoved { number: u64, title: String },
+
+    #[error("Task #{number} '{title}' brought back")]
+    TaskAdded { number: u64, title: String },
+
+    #[error("Task #{number} '{title}' marked as completed")]
+    TaskCompleted { number: u64, title: String },
+
+    #[error("Task #{number} '{title}' marked as not completed")]
+    TaskReopened { number: u64, title: String },
+
+    #[error("Task #{number} '{title}' moved to the trash")]
+    TaskDeleted { number: u64, title: String },
+
+    #[error("Task #{number} '{title}' restored from the trash")]
+    TaskRestored { number: u64, title: String },
+
+    #[error("Task #{number} '{title}' changed: {}", .fields.join(", "))]
+    TaskEdited {
+        number: u64,
+        title: String,
+        fields: Vec<String>,
+    },
+
+    #[error("Project '{0}' removed")]
+    ProjectRemoved(String),
+
+    #[error("Project '{0}' brought back")]
+    ProjectAdded(String),
+
+    #[error("Project '{0}' changed")]
+    ProjectEdited(String),
+
+    #[error("Area '{0}' removed")]
+    AreaRemoved(String),
+
+    #[error("Area '{0}' brought back")]
+    AreaAdded(String),
+
+    #[error("Area '{0}' changed")]
+    AreaEdited(String),
+}
+
 /// In-memory representation (how we work with data in the app)
 pub struct Store {
     pub version: u32,
@@ -111,9 +226,24 @@ impl Store {
         self.projects.get(&id)
     }
 
-    /// Get a project by slug
-    pub fn get_project_by_slug(&self, slug: &str) -> Option<&Project> {
-        self.projects.values().find(|p| p.slug == slug)
+    /// Slug for a project called `name`, numbered ("launch-2") when another
+    /// project, trashed ones included, already uses it. `except` is the
+    /// project being renamed, which may keep its own slug.
+    pub fn unique_project_slug(&self, name: &str, except: Option<Uuid>) -> String {
+        unique_slug(name, |slug| {
+            self.projects
+                .values()
+                .any(|p| Some(p.id) != except && p.slug == slug)
+        })
+    }
+
+    /// Slug for an area called `name`, like `unique_project_slug`
+    pub fn unique_area_slug(&self, name: &str, except: Option<Uuid>) -> String {
+        unique_slug(name, |slug| {
+            self.areas
+                .values()
+                .any(|a| Some(a.id) != except && a.slug == slug)
+        })
     }
 
     /// Get an area by ID
@@ -177,6 +307,122 @@ impl Store {
             .filter(move |t| t.project_id == Some(project_id))
     }
 
+    /// Earliest of a project's own deadline and those of its open tasks
+    pub fn get_next_project_deadline(&self, project_id: Uuid) -> Option<jiff::civil::Date> {
+        self.get_tasks_for_project(project_id)
+            .filter(|t| t.completed_at.is_none() && t.deleted_at.is_none())
+            .filter_map(|t| t.deadline)
+            .chain(self.get_project(project_id).and_then(|p| p.deadline))
+            .min()
+    }
+
+    /// The task to do next in a project: the one chosen with `tdo project next`
+    /// while it is still open and in the project, otherwise the oldest open task
+    /// that isn't in Someday or deferred
+    pub fn get_next_action(&self, project_id: Uuid) -> Option<&Task> {
+        let chosen = self
+            .get_project(project_id)
+            .and_then(|p| p.next_action)
+            .and_then(|id| self.get_task(id))
+            .filter(|t| t.project_id == Some(project_id))
+            .filter(|t| t.completed_at.is_none() && t.deleted_at.is_none());
+
+        chosen.or_else(|| {
+            self.get_tasks_for_project(project_id)
+                .filter(|t| t.completed_at.is_none() && t.deleted_at.is_none())
+                .filter(|t| !t.is_deferred() && !matches!(t.when, When::Someday))
+                .min_by_key(|t| (t.created_at, t.task_number))
+        })
+    }
+
+    /// Open projects that must be completed before `project_id` starts
+    pub fn get_blocking_projects(&self, project_id: Uuid) -> Vec<&Project> {
+        self.get_project(project_id)
+            .map(|project| {
+                project
+                    .blocked_by
+                    .iter()
+                    .filter_map(|id| self.get_project(*id))
+                    .filter(|p| p.completed_at.is_none() && p.deleted_at.is_none())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether a task is in a project that is still waiting on another one
+    pub fn is_task_blocked(&self, task: &Task) -> bool {
+        task.project_id
+            .is_some_and(|id| !self.get_blocking_projects(id).is_empty())
+    }
+
+    /// Whether a task is in a project shelved with `tdo incubate`
+    pub fn is_task_incubated(&self, task: &Task) -> bool {
+        task.project_id
+            .and_then(|id| self.get_project(id))
+            .is_some_and(|p| p.incubated_at.is_some())
+    }
+
+    /// Open projects due `days` from `today` or sooner, overdue ones included,
+    /// soonest first. Shelved and archived projects are left out.
+    pub fn get_projects_due_within(&self, today: jiff::civil::Date, days: u32) -> Vec<&Project> {
+        let last_day = today.saturating_add(jiff::Span::new().days(days));
+        let mut projects: Vec<_> = self
+            .get_active_projects()
+            .filter(|p| {
+                p.completed_at.is_none() && p.incubated_at.is_none() && !self.is_project_archived(p)
+            })
+            .filter(|p| p.deadline.is_some_and(|deadline| deadline <= last_day))
+            .collect();
+        projects.sort_by_key(|p| (p.deadline, p.name.to_lowercase()));
+        projects
+    }
+
+    /// Whether a project was put away with `tdo project archive`, or sits in
+    /// an area put away with `tdo area archive`
+    pub fn is_project_archived(&self, project: &Project) -> bool {
+        project.archived_at.is_some()
+            || project
+                .area_id
+                .and_then(|id| self.get_area(id))
+                .is_some_and(|a| a.archived_at.is_some())
+    }
+
+    /// Whether a task is in an archived project or area
+    pub fn is_task_archived(&self, task: &Task) -> bool {
+        task.project_id
+            .and_then(|id| self.get_project(id))
+            .is_some_and(|p| self.is_project_archived(p))
+            || self
+                .get_task_area_id(task)
+                .and_then(|id| self.get_area(id))
+                .is_some_and(|a| a.archived_at.is_some())
+    }
+
+    /// Whether a task is kept out of the working views because its project
+    /// is waiting on another one, was shelved or was archived
+    pub fn is_task_on_hold(&self, task: &Task) -> bool {
+        self.is_task_blocked(task) || self.is_task_incubated(task) || self.is_task_archived(task)
+    }
+
+    /// Whether `project_id` waits on `other_id`, directly or through other projects
+    pub fn project_depends_on(&self, project_id: Uuid, other_id: Uuid) -> bool {
+        let mut pending = vec![project_id];
+        let mut seen = HashSet::new();
+        while let Some(id) = pending.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let Some(project) = self.get_project(id) else {
+                continue;
+            };
+            if project.blocked_by.contains(&other_id) {
+                return true;
+            }
+            pending.extend(&project.blocked_by);
+        }
+        false
+    }
+
     /// Find projects belonging to an area
     pub fn get_projects_for_area(&self, area_id: Uuid) -> impl Iterator<Item = &Project> {
         self.projects
@@ -184,10 +430,255 @@ impl Store {
             .filter(move |p| p.area_id == Some(area_id))
     }
 
+    /// Resolve the area a task is surfaced under: its own area (an override when
+    /// the task is in a project) takes precedence over the project's area
+    pub fn get_task_area_id(&self, task: &Task) -> Option<Uuid> {
+        task.area_id.or_else(|| {
+            task.project_id
+                .and_then(|project_id| self.get_project(project_id))
+                .and_then(|project| project.area_id)
+        })
+    }
+
+    /// Find all tasks surfaced under an area, directly, through a project, or by override
+    pub fn get_tasks_in_area(&self, area_id: Uuid) -> impl Iterator<Item = &Task> {
+        self.tasks
+            .values()
+            .filter(move |t| self.get_task_area_id(t) == Some(area_id))
+    }
+
     /// Find tasks directly belonging to an area (no project)
     pub fn get_tasks_for_area(&self, area_id: Uuid) -> impl Iterator<Item = &Task> {
         self.tasks
             .values()
             .filter(move |t| t.area_id == Some(area_id) && t.project_id.is_none())
     }
+
+    /// Check task numbering and references between tasks, projects and areas
+    pub fn integrity_issues(&self) -> Vec<IntegrityIssue> {
+        let mut issues = Vec::new();
+
+        let mut seen_numbers = HashSet::new();
+        let mut task_numbers: Vec<u64> = self.tasks.values().map(|t| t.task_number).collect();
+        task_numbers.sort();
+        for number in task_numbers {
+            if !seen_numbers.insert(number) {
+                issues.push(IntegrityIssue::DuplicateTaskNumber(number));
+            }
+            if number >= self.next_task_number {
+                issues.push(IntegrityIssue::TaskNumberOutOfRange(number));
+            }
+        }
+
+        for task in self.tasks.values() {
+            if let Some(project_id) = task.project_id
+                && !self.projects.contains_key(&project_id)
+            {
+                issues.push(IntegrityIssue::MissingTaskProject {
+                    task_number: task.task_number,
+                    project_id,
+                });
+            }
+            if let Some(area_id) = task.area_id
+                && !self.areas.contains_key(&area_id)
+            {
+                issues.push(IntegrityIssue::MissingTaskArea {
+                    task_number: task.task_number,
+                    area_id,
+                });
+            }
+        }
+
+        for project in self.projects.values() {
+            if let Some(area_id) = project.area_id
+                && !self.areas.contains_key(&area_id)
+            {
+                issues.push(IntegrityIssue::MissingProjectArea {
+                    name: project.name.clone(),
+                    area_id,
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// What changes going from this store to `other`, tasks by number first,
+    /// then projects and areas
+    pub fn changes_to(&self, other: &Store) -> Vec<StoreChange> {
+        let mut changes = Vec::new();
+
+        let mut tasks = paired(&self.tasks, &other.tasks);
+        tasks.sort_by_key(|(before, after)| before.or(*after).map(|t| t.task_number));
+
+        for pair in tasks {
+            match pair {
+                (Some(task), None) => changes.push(StoreChange::TaskRemoved {
+                    number: task.task_number,
+                    title: task.title.clone(),
+                }),
+                (None, Some(task)) => changes.push(StoreChange::TaskAdded {
+                    number: task.task_number,
+                    title: task.title.clone(),
+                }),
+                (Some(before), Some(after)) => changes.extend(task_changes(before, after)),
+                (None, None) => {}
+            }
+        }
+
+        for (before, after) in paired(&self.projects, &other.projects) {
+            match (before, after) {
+                (Some(project), None) => {
+                    changes.push(StoreChange::ProjectRemoved(project.name.clone()))
+                }
+                (None, Some(project)) => {
+                    changes.push(StoreChange::ProjectAdded(project.name.clone()))
+                }
+                (Some(before), Some(after)) if !same_json(before, after) => {
+                    changes.push(StoreChange::ProjectEdited(after.name.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        for (before, after) in paired(&self.areas, &other.areas) {
+            match (before, after) {
+                (Some(area), None) => changes.push(StoreChange::AreaRemoved(area.name.clone())),
+                (None, Some(area)) => changes.push(StoreChange::AreaAdded(area.name.clone())),
+                (Some(before), Some(after)) if !same_json(before, after) => {
+                    changes.push(StoreChange::AreaEdited(after.name.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        changes
+    }
+
+    /// Find active tasks and projects that reference a deleted project or area
+    pub fn find_orphans(&self) -> Vec<Orphan> {
+        let mut orphans = Vec::new();
+
+        let mut tasks: Vec<_> = self.get_active_tasks().collect();
+        tasks.sort_by_key(|t| t.task_number);
+
+        for task in tasks {
+            if let Some(project) = task.project_id.and_then(|id| self.get_project(id))
+                && project.deleted_at.is_some()
+            {
+                orphans.push(Orphan::TaskInDeletedProject {
+                    task_id: task.id,
+                    task_number: task.task_number,
+                    title: task.title.clone(),
+                    project: project.name.clone(),
+                });
+            }
+            if let Some(area) = task.area_id.and_then(|id| self.get_area(id))
+                && area.deleted_at.is_some()
+            {
+                orphans.push(Orphan::TaskInDeletedArea {
+                    task_id: task.id,
+                    task_number: task.task_number,
+                    title: task.title.clone(),
+                    area: area.name.clone(),
+                });
+            }
+        }
+
+        for project in self.get_active_projects() {
+            if let Some(area) = project.area_id.and_then(|id| self.get_area(id))
+                && area.deleted_at.is_some()
+            {
+                orphans.push(Orphan::ProjectInDeletedArea {
+                    project_id: project.id,
+                    name: project.name.clone(),
+                    area: area.name.clone(),
+                });
+            }
+        }
+
+        orphans
+    }
+}
+
+/// How a task that exists in both stores changed
+fn task_changes(before: &Task, after: &Task) -> Vec<StoreChange> {
+    let number = after.task_number;
+    let title = after.title.clone();
+    let mut changes = Vec::new();
+
+    match (before.completed_at.is_some(), after.completed_at.is_some()) {
+        (false, true) => changes.push(StoreChange::TaskCompleted {
+            number,
+            title: title.clone(),
+        }),
+        (true, false) => changes.push(StoreChange::TaskReopened {
+            number,
+            title: title.clone(),
+        }),
+        _ => {}
+    }
+
+    match (before.deleted_at.is_some(), after.deleted_at.is_some()) {
+        (false, true) => changes.push(StoreChange::TaskDeleted {
+            number,
+            title: title.clone(),
+        }),
+        (true, false) => changes.push(StoreChange::TaskRestored {
+            number,
+            title: title.clone(),
+        }),
+        _ => {}
+    }
+
+    let mut fields = after.changed_fields(before);
+    if after.when != before.when {
+        fields.insert(0, "when".to_string());
+    }
+    if !same_json(&after.checklist, &before.checklist) {
+        fields.push("checklist".to_string());
+    }
+    if !same_json(&after.time_entries, &before.time_entries) {
+        fields.push("tracked time".to_string());
+    }
+    if !fields.is_empty() {
+        changes.push(StoreChange::TaskEdited {
+            number,
+            title,
+            fields,
+        });
+    }
+
+    changes
+}
+
+/// Items of both maps matched by id, ordered by id so the output is stable
+fn paired<'a, T>(
+    before: &'a HashMap<Uuid, T>,
+    after: &'a HashMap<Uuid, T>,
+) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    let mut ids: Vec<_> = before.keys().chain(after.keys()).collect();
+    ids.sort();
+    ids.dedup();
+
+    ids.into_iter()
+        .map(|id| (before.get(id), after.get(id)))
+        .collect()
+}
+
+/// Compare models that don't implement `PartialEq` through their serialized form
+fn same_json<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// `slugify(name)`, with "-2", "-3"... appended until `is_taken` accepts it
+fn unique_slug(name: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    let base = slugify(name);
+    if !is_taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|slug| !is_taken(slug))
+        .expect("some numbered slug is free")
 }
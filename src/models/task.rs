@@ -1,34 +1,57 @@
 // src/model.rs
 
-use jiff::Timestamp;
-use jiff::civil::Date;
+use jiff::civil::{Date, Time, Weekday};
+use jiff::{SignedDuration, Timestamp, Zoned};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone)]
 pub struct Task {
     /// UUID to identify the task
     pub id: Uuid,
     /// User-facing auto-incremental task number
     pub task_number: u64,
+    /// Key supplied by the client that added the task (`tdo add --idempotency-key`),
+    /// so a retried add finds the task instead of creating it again
+    pub idempotency_key: Option<String>,
     /// Title of the task
     pub title: String,
     /// Notes of the task
     pub notes: Option<String>,
     /// The project of this task if it belongs to any
     pub project_id: Option<Uuid>,
-    /// The area of this task if it belongs to any (and no project)
+    /// The area of this task. Without a project this is where the task lives;
+    /// with a project it overrides the project's area
     pub area_id: Option<Uuid>,
     /// Tags of the task
     pub tags: Vec<String>,
+    /// GTD context where the task can be done (e.g. "home", "office"), stored without the "@"
+    pub context: Option<String>,
     /// When the user wants do to this task
     pub when: When,
     /// Deadline for this task
     pub deadline: Option<Date>,
     /// Defered date when to surface again the task
     pub defer_until: Option<Date>,
+    /// How much energy the task requires
+    pub energy: Option<Energy>,
+    /// Reminders and overdue highlighting are suppressed until this time
+    pub snoozed_until: Option<Timestamp>,
+    /// When to remind the user about the task (see `tdo remind`)
+    pub reminder: Option<Zoned>,
+    /// Pinned tasks are listed first in Today and Anytime
+    pub pinned: bool,
+    /// How long the user expects the task to take
+    pub estimate: Option<SignedDuration>,
+    /// Time spent on the task, recorded by `tdo start` / `tdo stop`
+    pub time_entries: Vec<TimeEntry>,
+    /// Append-only log of what happened to the task, oldest first
+    pub history: Vec<TaskEvent>,
     /// Sub tasks of the main task - Modeled as a lighter task called ChecklistItem
     pub checklist: Vec<ChecklistItem>,
+    /// Links and files kept with the task (see `tdo attach` / `tdo open`)
+    pub attachments: Vec<Attachment>,
     /// When the task was completed
     pub completed_at: Option<Timestamp>,
     /// When the task was deleted
@@ -37,7 +60,275 @@ pub struct Task {
     pub created_at: Timestamp,
 }
 
-#[derive(Serialize, Deserialize, Default, Clone)]
+impl Task {
+    /// Check whether the task is currently snoozed
+    pub fn is_snoozed(&self) -> bool {
+        self.snoozed_until
+            .is_some_and(|until| until > Timestamp::now())
+    }
+
+    /// Check whether the task is deferred to a date that hasn't arrived yet
+    pub fn is_deferred(&self) -> bool {
+        self.defer_until
+            .is_some_and(|date| date > Zoned::now().date())
+    }
+
+    /// Append a change to the task's history
+    pub fn record(&mut self, change: TaskChange) {
+        self.history.push(TaskEvent {
+            at: Timestamp::now(),
+            change,
+        });
+    }
+
+    /// Schedule the task for `to`, recording the change if it is one
+    pub fn reschedule(&mut self, to: When) {
+        if self.when != to {
+            self.record(TaskChange::Rescheduled {
+                from: self.when.clone(),
+                to: to.clone(),
+            });
+            self.when = to;
+        }
+    }
+
+    /// Record how this task differs from `before`: a new schedule as `Rescheduled`
+    /// and any other changed fields as one `Edited` event
+    pub fn record_changes_from(&mut self, before: &Task) {
+        if self.when != before.when {
+            self.record(TaskChange::Rescheduled {
+                from: before.when.clone(),
+                to: self.when.clone(),
+            });
+        }
+
+        let fields = self.changed_fields(before);
+        if !fields.is_empty() {
+            self.record(TaskChange::Edited { fields });
+        }
+    }
+
+    /// Add `text` as a new line at the end of the notes, stamped with `at`
+    pub fn append_note(&mut self, text: &str, at: &jiff::Zoned) {
+        let line = format!("[{}] {}", at.strftime("%Y-%m-%d %H:%M"), text);
+        self.notes = Some(match self.notes.take() {
+            Some(notes) if !notes.trim().is_empty() => {
+                format!("{}\n{}", notes.trim_end(), line)
+            }
+            _ => line,
+        });
+    }
+
+    /// Names of the user-editable fields that differ from `before`, other than `when`
+    pub fn changed_fields(&self, before: &Task) -> Vec<String> {
+        [
+            ("title", self.title != before.title),
+            ("notes", self.notes != before.notes),
+            ("project", self.project_id != before.project_id),
+            ("area", self.area_id != before.area_id),
+            ("tags", self.tags != before.tags),
+            ("context", self.context != before.context),
+            ("deadline", self.deadline != before.deadline),
+            ("defer", self.defer_until != before.defer_until),
+            ("energy", self.energy != before.energy),
+            ("reminder", self.reminder != before.reminder),
+            ("pinned", self.pinned != before.pinned),
+            ("estimate", self.estimate != before.estimate),
+            ("attachments", self.attachments != before.attachments),
+        ]
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(field, _)| field.to_string())
+        .collect()
+    }
+
+    /// The timer that is currently running on this task, if any
+    pub fn running_timer(&self) -> Option<&TimeEntry> {
+        self.time_entries.iter().find(|e| e.stopped_at.is_none())
+    }
+
+    /// Stop the running timer at `at`, returning how long it ran
+    pub fn stop_timer(&mut self, at: Timestamp) -> Option<SignedDuration> {
+        let entry = self
+            .time_entries
+            .iter_mut()
+            .find(|e| e.stopped_at.is_none())?;
+        entry.stopped_at = Some(at);
+        Some(entry.duration())
+    }
+
+    /// Total time tracked on the task, counting a running timer up to now
+    pub fn tracked_time(&self) -> SignedDuration {
+        self.time_entries.iter().map(TimeEntry::duration).sum()
+    }
+
+    /// Check whether the task belongs to a context (case-insensitive, "@" optional)
+    pub fn has_context(&self, context: &str) -> bool {
+        self.context
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case(&normalize_context(context)))
+    }
+
+    /// Check whether the task carries a tag (case-insensitive, "#" optional)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim_start_matches('#');
+        self.tags
+            .iter()
+            .any(|t| t.trim_start_matches('#').eq_ignore_ascii_case(tag))
+    }
+}
+
+/// Parse a positive time estimate such as "30m", "1h", "1h30m" or "1h 30m"
+pub fn parse_estimate(input: &str) -> Option<SignedDuration> {
+    input
+        .trim()
+        .parse::<SignedDuration>()
+        .ok()
+        .filter(|duration| duration.is_positive())
+}
+
+/// Parse a deadline given as a date ("2025-03-01") or as an offset from `today`:
+/// "+3d", "+1w" or "+2m". Adding months keeps the day of the month, clamped to
+/// the month's last day. The error explains what was wrong with the input.
+pub fn parse_deadline(input: &str, today: Date) -> Result<Date, String> {
+    let input = input.trim();
+    let Some(offset) = input.strip_prefix('+') else {
+        return input.parse::<Date>().map_err(|e| e.to_string());
+    };
+
+    let Some(unit) = offset.chars().last().filter(|c| c.is_ascii_alphabetic()) else {
+        return Err(
+            "relative deadlines need a unit: d (days), w (weeks) or m (months)".to_string(),
+        );
+    };
+    let number = &offset[..offset.len() - 1];
+    let amount: u32 = number.parse().map_err(|_| {
+        format!(
+            "expected a whole number before '{}', got '{}'",
+            unit, number
+        )
+    })?;
+    let amount = i64::from(amount);
+
+    let span = match unit.to_ascii_lowercase() {
+        'd' => jiff::Span::new().try_days(amount),
+        'w' => jiff::Span::new().try_weeks(amount),
+        'm' => jiff::Span::new().try_months(amount),
+        other => {
+            return Err(format!(
+                "unknown unit '{}', use d (days), w (weeks) or m (months)",
+                other
+            ));
+        }
+    };
+
+    span.and_then(|span| today.checked_add(span))
+        .map_err(|_| format!("{} is too far in the future", input))
+}
+
+/// Parse a schedule date: a date, an offset like "+3d" (see `parse_deadline`),
+/// "tomorrow", or a weekday such as "friday" or "next fri" meaning its next
+/// occurrence after `today`
+pub fn parse_schedule_date(input: &str, today: Date) -> Option<Date> {
+    let input = input.trim().to_lowercase();
+    if input == "tomorrow" {
+        return today.tomorrow().ok();
+    }
+    if let Some(weekday) = parse_weekday(input.strip_prefix("next ").unwrap_or(&input)) {
+        return today.nth_weekday(1, weekday).ok();
+    }
+    parse_deadline(&input, today).ok()
+}
+
+/// Parse a reminder such as "17:00", "today 17:00", "tomorrow 9:30", "friday 8:00"
+/// or "2025-03-01 17:00" into a time in `now`'s timezone. A weekday means its next
+/// occurrence, which is today only if the time hasn't passed yet.
+pub fn parse_reminder(input: &str, now: &Zoned) -> Option<Zoned> {
+    let input = input.trim().to_lowercase();
+    let (day, time) = match input.split_once(char::is_whitespace) {
+        Some((day, time)) => (Some(day.trim()), time.trim()),
+        None => (None, input.as_str()),
+    };
+    let time = parse_time(time)?;
+
+    let today = now.date();
+    let date = match day {
+        None | Some("today") => today,
+        Some("tomorrow") => today.tomorrow().ok()?,
+        Some(day) => match parse_weekday(day) {
+            Some(weekday) if today.weekday() == weekday && time > now.time() => today,
+            Some(weekday) => today.nth_weekday(1, weekday).ok()?,
+            None => day.parse().ok()?,
+        },
+    };
+
+    date.to_datetime(time)
+        .to_zoned(now.time_zone().clone())
+        .ok()
+}
+
+/// Parse when something already happened, for `tdo log --at`: "today",
+/// "yesterday", a weekday meaning its latest occurrence (today included) or a
+/// date, optionally followed by a 24-hour time. A time alone means today and a
+/// day alone keeps the current time of day. Moments after `now` are rejected.
+pub fn parse_past_instant(input: &str, now: &Zoned) -> Option<Timestamp> {
+    let input = input.trim().to_lowercase();
+    let (day, time) = match input.split_once(char::is_whitespace) {
+        Some((day, time)) => (day.trim(), Some(parse_time(time.trim())?)),
+        None => match parse_time(&input) {
+            Some(time) => ("today", Some(time)),
+            None => (input.as_str(), None),
+        },
+    };
+
+    let today = now.date();
+    let date = match day {
+        "today" => today,
+        "yesterday" => today.yesterday().ok()?,
+        day => match parse_weekday(day) {
+            Some(weekday) if today.weekday() == weekday => today,
+            Some(weekday) => today.nth_weekday(-1, weekday).ok()?,
+            None => day.parse().ok()?,
+        },
+    };
+
+    let instant = date
+        .to_datetime(time.unwrap_or_else(|| now.time()))
+        .to_zoned(now.time_zone().clone())
+        .ok()?
+        .timestamp();
+    Some(instant).filter(|instant| *instant <= now.timestamp())
+}
+
+/// Parse "H:MM" or "HH:MM" in 24-hour time
+fn parse_time(time: &str) -> Option<Time> {
+    let (hour, minute) = time.split_once(':')?;
+    if minute.len() != 2 {
+        return None;
+    }
+    Time::new(hour.parse().ok()?, minute.parse().ok()?, 0, 0).ok()
+}
+
+pub fn parse_weekday(day: &str) -> Option<Weekday> {
+    let weekday = match day {
+        "monday" | "mon" => Weekday::Monday,
+        "tuesday" | "tue" => Weekday::Tuesday,
+        "wednesday" | "wed" => Weekday::Wednesday,
+        "thursday" | "thu" => Weekday::Thursday,
+        "friday" | "fri" => Weekday::Friday,
+        "saturday" | "sat" => Weekday::Saturday,
+        "sunday" | "sun" => Weekday::Sunday,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// Normalize a user-supplied context name by trimming whitespace and the leading "@"
+pub fn normalize_context(context: &str) -> String {
+    context.trim().trim_start_matches('@').to_string()
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, PartialEq)]
 #[serde(tag = "type")]
 pub enum When {
     #[default]
@@ -49,9 +340,20 @@ pub enum When {
     Anytime,
     Scheduled {
         date: Date,
+        /// Goes to the Evening part of Today once the date arrives
+        evening: bool,
     },
 }
 
+#[derive(
+    Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq, Eq, Debug, clap::ValueEnum,
+)]
+pub enum Energy {
+    Low,
+    Medium,
+    High,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum WhenInstantiationError {
     #[error("Invalid schedule date format: {0}")]
@@ -60,13 +362,32 @@ pub enum WhenInstantiationError {
     #[error("Conflicting scheduling flags: {}", .0.join(", "))]
     ConflictingFlags(Vec<String>),
 
-    #[error("The --evening flag can only be used with --today")]
-    EveningWithoutToday,
+    #[error("The --evening flag needs a day: --today, --tomorrow or --when")]
+    EveningWithoutDay,
 }
 
 impl When {
+    /// Whether the task is in Today on `today`, either put there or scheduled
+    /// for that date. Earlier dates are overdue rather than in Today.
+    pub fn is_today(&self, today: Date) -> bool {
+        match self {
+            When::Today { .. } => true,
+            When::Scheduled { date, .. } => *date == today,
+            _ => false,
+        }
+    }
+
+    /// Whether the task is meant for the evening of its day
+    pub fn is_evening(&self) -> bool {
+        matches!(
+            self,
+            When::Today { evening: true } | When::Scheduled { evening: true, .. }
+        )
+    }
+
     pub fn from_command_flags(
         today: bool,
+        tomorrow: bool,
         evening: bool,
         someday: bool,
         anytime: bool,
@@ -77,6 +398,9 @@ impl When {
         if today {
             provided_flags.push("--today");
         }
+        if tomorrow {
+            provided_flags.push("--tomorrow");
+        }
         if someday {
             provided_flags.push("--someday");
         }
@@ -95,31 +419,262 @@ impl When {
         }
 
         // Validate --evening usage
-        if evening && !today {
-            return Err(WhenInstantiationError::EveningWithoutToday);
+        if evening && !(today || tomorrow || schedule_at.is_some()) {
+            return Err(WhenInstantiationError::EveningWithoutDay);
         }
 
         // Process the valid flag (existing logic)
         if today {
             Ok(When::Today { evening })
+        } else if tomorrow {
+            let date = jiff::Zoned::now()
+                .date()
+                .tomorrow()
+                .expect("tomorrow should be valid");
+            Ok(When::Scheduled { date, evening })
         } else if someday {
             Ok(When::Someday)
         } else if anytime {
             Ok(When::Anytime)
         } else if let Some(string_date) = schedule_at {
-            string_date
-                .parse()
-                .map(|date| When::Scheduled { date })
-                .map_err(|_| WhenInstantiationError::ScheduleAtIncorrect(string_date))
+            if string_date.trim().eq_ignore_ascii_case("today") {
+                return Ok(When::Today { evening });
+            }
+            parse_schedule_date(&string_date, jiff::Zoned::now().date())
+                .map(|date| When::Scheduled { date, evening })
+                .ok_or(WhenInstantiationError::ScheduleAtIncorrect(string_date))
         } else {
             Ok(When::Inbox)
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+/// An entry in a task's history
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct TaskEvent {
+    pub at: Timestamp,
+    pub change: TaskChange,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(tag = "type")]
+pub enum TaskChange {
+    Created,
+    Rescheduled {
+        from: When,
+        to: When,
+    },
+    /// Names of the fields that changed, e.g. "title" or "deadline"
+    Edited {
+        fields: Vec<String>,
+    },
+    Completed,
+    Reopened,
+    Deleted,
+    Restored,
+}
+
+/// A stretch of time spent on a task
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct TimeEntry {
+    pub started_at: Timestamp,
+    /// `None` while the timer is running
+    pub stopped_at: Option<Timestamp>,
+}
+
+impl TimeEntry {
+    /// How long the entry lasted, or has lasted so far if it's still running
+    pub fn duration(&self) -> SignedDuration {
+        self.stopped_at
+            .unwrap_or_else(Timestamp::now)
+            .duration_since(self.started_at)
+    }
+}
+
+/// A URL or local file kept with a task
+#[derive(Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct Attachment {
+    /// URL, or absolute path of a file
+    pub target: String,
+    /// Shown instead of the target when set
+    pub label: Option<String>,
+}
+
+impl Attachment {
+    /// Whether the target is a URL (`https://…`, `mailto:…`) rather than a file path
+    pub fn is_url(&self) -> bool {
+        self.target.contains("://") || self.target.starts_with("mailto:")
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct ChecklistItem {
     pub id: Uuid,
     pub title: String,
     pub completed: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_estimate() {
+        assert_eq!(parse_estimate("30m"), Some(SignedDuration::from_mins(30)));
+        assert_eq!(parse_estimate("1h30m"), Some(SignedDuration::from_mins(90)));
+        assert_eq!(parse_estimate(" 2h "), Some(SignedDuration::from_hours(2)));
+        assert_eq!(parse_estimate("0m"), None);
+        assert_eq!(parse_estimate("45"), None);
+        assert_eq!(parse_estimate("soon"), None);
+    }
+
+    #[test]
+    fn test_parse_deadline() {
+        let today: Date = "2025-01-31".parse().unwrap();
+        let date = |s: &str| s.parse::<Date>().unwrap();
+
+        assert_eq!(parse_deadline("2025-03-01", today), Ok(date("2025-03-01")));
+        assert_eq!(parse_deadline("+3d", today), Ok(date("2025-02-03")));
+        assert_eq!(parse_deadline("+1w", today), Ok(date("2025-02-07")));
+        assert_eq!(parse_deadline("+1m", today), Ok(date("2025-02-28")));
+        assert_eq!(parse_deadline("+0d", today), Ok(today));
+        assert!(parse_deadline("+3", today).is_err());
+        assert!(parse_deadline("+d", today).is_err());
+        assert!(parse_deadline("+2y", today).is_err());
+        assert!(parse_deadline("+-1d", today).is_err());
+        assert!(parse_deadline("+99999999m", today).is_err());
+        assert!(parse_deadline("next week", today).is_err());
+    }
+
+    #[test]
+    fn test_parse_schedule_date() {
+        // A Friday
+        let today: Date = "2025-01-31".parse().unwrap();
+        let date = |s: &str| Some(s.parse::<Date>().unwrap());
+
+        assert_eq!(parse_schedule_date("2025-03-01", today), date("2025-03-01"));
+        assert_eq!(parse_schedule_date("Tomorrow", today), date("2025-02-01"));
+        assert_eq!(parse_schedule_date("monday", today), date("2025-02-03"));
+        assert_eq!(parse_schedule_date("next mon", today), date("2025-02-03"));
+        // The same weekday means next week's
+        assert_eq!(parse_schedule_date("friday", today), date("2025-02-07"));
+        assert_eq!(parse_schedule_date("+2w", today), date("2025-02-14"));
+        assert_eq!(parse_schedule_date("someday soon", today), None);
+    }
+
+    #[test]
+    fn test_parse_past_instant() {
+        // Friday 2025-03-07 12:00 UTC
+        let now: Zoned = "2025-03-07T12:00[UTC]".parse().unwrap();
+        let at = |input: &str| {
+            parse_past_instant(input, &now)
+                .map(|ts| ts.to_zoned(jiff::tz::TimeZone::UTC).datetime().to_string())
+        };
+
+        assert_eq!(at("yesterday").as_deref(), Some("2025-03-06T12:00:00"));
+        assert_eq!(
+            at("Yesterday 17:30").as_deref(),
+            Some("2025-03-06T17:30:00")
+        );
+        assert_eq!(at("9:15").as_deref(), Some("2025-03-07T09:15:00"));
+        assert_eq!(at("friday 8:00").as_deref(), Some("2025-03-07T08:00:00"));
+        assert_eq!(at("mon").as_deref(), Some("2025-03-03T12:00:00"));
+        assert_eq!(
+            at("2025-02-14 20:00").as_deref(),
+            Some("2025-02-14T20:00:00")
+        );
+        // Not yet happened
+        assert_eq!(at("18:00"), None);
+        assert_eq!(at("2025-03-08"), None);
+        assert_eq!(at("last week"), None);
+    }
+
+    #[test]
+    fn test_timer_entries() {
+        let start: Timestamp = "2025-03-01T09:00:00Z".parse().unwrap();
+        let mut task = Task {
+            time_entries: vec![TimeEntry {
+                started_at: start,
+                stopped_at: Some(start + SignedDuration::from_mins(25)),
+            }],
+            ..Task::default()
+        };
+        assert!(task.running_timer().is_none());
+        assert_eq!(task.stop_timer(start), None);
+
+        let restart = start + SignedDuration::from_hours(1);
+        task.time_entries.push(TimeEntry {
+            started_at: restart,
+            stopped_at: None,
+        });
+        assert!(task.running_timer().is_some());
+        assert_eq!(
+            task.stop_timer(restart + SignedDuration::from_mins(20)),
+            Some(SignedDuration::from_mins(20))
+        );
+        assert_eq!(task.tracked_time(), SignedDuration::from_mins(45));
+    }
+
+    #[test]
+    fn test_append_note() {
+        let at: jiff::Zoned = "2025-03-01T09:30:00+01:00[Europe/Madrid]".parse().unwrap();
+        let mut task = Task::default();
+
+        task.append_note("First", &at);
+        assert_eq!(task.notes.as_deref(), Some("[2025-03-01 09:30] First"));
+
+        task.notes = Some("Existing notes\n".to_string());
+        task.append_note("Second", &at);
+        assert_eq!(
+            task.notes.as_deref(),
+            Some("Existing notes\n[2025-03-01 09:30] Second")
+        );
+    }
+
+    #[test]
+    fn test_record_changes_from() {
+        let before = Task::default();
+        let mut after = before.clone();
+        after.record_changes_from(&before);
+        assert!(after.history.is_empty());
+
+        after.when = When::Today { evening: false };
+        after.title = "Renamed".to_string();
+        after.tags.push("work".to_string());
+        after.record_changes_from(&before);
+
+        assert_eq!(after.history.len(), 2);
+        assert!(matches!(
+            &after.history[0].change,
+            TaskChange::Rescheduled {
+                from: When::Inbox,
+                to: When::Today { evening: false }
+            }
+        ));
+        assert!(matches!(
+            &after.history[1].change,
+            TaskChange::Edited { fields } if fields == &["title", "tags"]
+        ));
+    }
+
+    #[test]
+    fn test_parse_reminder() {
+        // Friday 2025-03-07 12:00 UTC
+        let now: Zoned = "2025-03-07T12:00[UTC]".parse().unwrap();
+        let at = |input: &str| parse_reminder(input, &now).map(|z| z.datetime().to_string());
+
+        assert_eq!(at("17:00").as_deref(), Some("2025-03-07T17:00:00"));
+        assert_eq!(at("today 17:00").as_deref(), Some("2025-03-07T17:00:00"));
+        assert_eq!(at("Tomorrow 9:30").as_deref(), Some("2025-03-08T09:30:00"));
+        assert_eq!(at("friday 18:00").as_deref(), Some("2025-03-07T18:00:00"));
+        assert_eq!(at("fri 8:00").as_deref(), Some("2025-03-14T08:00:00"));
+        assert_eq!(at("mon 8:00").as_deref(), Some("2025-03-10T08:00:00"));
+        assert_eq!(
+            at("2025-04-01 08:15").as_deref(),
+            Some("2025-04-01T08:15:00")
+        );
+        assert_eq!(at("later"), None);
+        assert_eq!(at("today"), None);
+        assert_eq!(at("25:00"), None);
+    }
+}
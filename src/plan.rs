@@ -0,0 +1,212 @@
+use jiff::civil::Date;
+
+use crate::{
+    import::parse_markdown_checklist,
+    models::{
+        store::Store,
+        task::{Task, When},
+    },
+};
+
+/// Days covered by `tdo plan week`
+pub const PLAN_DAYS: i64 = 7;
+
+/// A checklist line under one of the plan's day headings
+#[derive(Debug, PartialEq, Eq)]
+pub struct PlannedItem {
+    /// Set for lines that refer to an existing task with a trailing "(#12)"
+    pub task_number: Option<u64>,
+    /// Text of the line without the task reference
+    pub title: String,
+}
+
+/// The items listed under a day heading
+#[derive(Debug, PartialEq, Eq)]
+pub struct PlannedDay {
+    pub date: Date,
+    pub items: Vec<PlannedItem>,
+}
+
+/// Render the week starting on `start` as Markdown, one section per day.
+/// Each day lists the open tasks scheduled on it (Today's tasks on the current
+/// day) followed by tasks whose deadline falls on it and that aren't already
+/// planned for a day of this week.
+pub fn week_plan_markdown(store: &Store, start: Date, today: Date) -> String {
+    let days: Vec<Date> = start
+        .series(jiff::Span::new().days(1))
+        .take(PLAN_DAYS as usize)
+        .collect();
+    let end = *days.last().expect("a week has days");
+
+    let open: Vec<&Task> = store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none())
+        .collect();
+    let planned_on = |task: &Task| match task.when {
+        When::Today { .. } => Some(today),
+        When::Scheduled { date, .. } => Some(date),
+        _ => None,
+    };
+
+    let mut output = format!(
+        "# Week plan: {} – {}\n\n\
+         <!-- Move lines between days to reschedule tasks. Lines without a (#n) reference\n\
+         \x20    become new tasks on that day; add (#n) to plan any existing task. Tasks\n\
+         \x20    removed from the plan are left as they are. -->\n",
+        start.strftime("%b %d"),
+        end.strftime("%b %d %Y")
+    );
+
+    for day in days {
+        output.push_str(&format!("\n## {}\n", day.strftime("%A %Y-%m-%d")));
+
+        let mut scheduled: Vec<_> = open.iter().filter(|t| planned_on(t) == Some(day)).collect();
+        scheduled.sort_by_key(|t| (!t.pinned, t.task_number));
+        let mut due: Vec<_> = open
+            .iter()
+            .filter(|t| t.deadline == Some(day))
+            .filter(|t| planned_on(t).is_none_or(|date| date < start || date > end))
+            .collect();
+        due.sort_by_key(|t| t.task_number);
+
+        if !scheduled.is_empty() || !due.is_empty() {
+            output.push('\n');
+        }
+        for task in &scheduled {
+            output.push_str(&format!("- [ ] {} (#{})\n", task.title, task.task_number));
+        }
+        for task in &due {
+            output.push_str(&format!(
+                "- [ ] {} ⚑ due (#{})\n",
+                task.title, task.task_number
+            ));
+        }
+    }
+
+    output
+}
+
+/// Read the day sections back from a week plan. Items under headings that
+/// don't end in a date are ignored, as are nested checkboxes.
+pub fn parse_week_plan(content: &str) -> Vec<PlannedDay> {
+    parse_markdown_checklist(content)
+        .into_iter()
+        .filter_map(|section| {
+            let date = section
+                .heading?
+                .split_whitespace()
+                .last()?
+                .parse::<Date>()
+                .ok()?;
+            let items = section
+                .items
+                .into_iter()
+                .map(|item| parse_planned_item(&item.title))
+                .collect();
+            Some(PlannedDay { date, items })
+        })
+        .collect()
+}
+
+/// Split "Title ⚑ due (#12)" into the task number and "Title"
+fn parse_planned_item(line: &str) -> PlannedItem {
+    let reference = line
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once("(#"))
+        .and_then(|(title, number)| Some((title, number.parse::<u64>().ok()?)));
+
+    match reference {
+        Some((title, number)) => PlannedItem {
+            task_number: Some(number),
+            title: title.trim().trim_end_matches("⚑ due").trim().to_string(),
+        },
+        None => PlannedItem {
+            task_number: None,
+            title: line.trim().to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_week_plan_round_trip() {
+        let today = date("2025-03-10");
+        let mut store = Store::default();
+        store.add_task(Task {
+            id: uuid::Uuid::new_v4(),
+            title: "Write report".to_string(),
+            when: When::Today { evening: false },
+            ..Task::default()
+        });
+        store.add_task(Task {
+            id: uuid::Uuid::new_v4(),
+            title: "File taxes".to_string(),
+            when: When::Anytime,
+            deadline: Some(date("2025-03-12")),
+            ..Task::default()
+        });
+        store.add_task(Task {
+            id: uuid::Uuid::new_v4(),
+            title: "Next month".to_string(),
+            when: When::Scheduled {
+                date: date("2025-04-01"),
+                evening: false,
+            },
+            ..Task::default()
+        });
+
+        let markdown = week_plan_markdown(&store, today, today);
+        assert!(markdown.contains("## Monday 2025-03-10\n\n- [ ] Write report (#1)\n"));
+        assert!(markdown.contains("## Wednesday 2025-03-12\n\n- [ ] File taxes ⚑ due (#2)\n"));
+        assert!(!markdown.contains("Next month"));
+
+        // Days without tasks have no items to read back
+        let days = parse_week_plan(&markdown);
+        assert_eq!(days.len(), 2);
+        assert_eq!(
+            days[1],
+            PlannedDay {
+                date: date("2025-03-12"),
+                items: vec![PlannedItem {
+                    task_number: Some(2),
+                    title: "File taxes".to_string(),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_week_plan_new_and_ignored_items() {
+        let days = parse_week_plan(
+            "# Week plan: Mar 10 – Mar 16 2025\n\
+             - [ ] Before any day\n\
+             ## Tuesday 2025-03-11\n\
+             - [ ] Call the bank\n\
+             - [ ] Budget (draft) (#7)\n\
+             ## Notes\n\
+             - [ ] Not a day\n",
+        );
+
+        assert_eq!(days.len(), 1);
+        assert_eq!(
+            days[0].items,
+            vec![
+                PlannedItem {
+                    task_number: None,
+                    title: "Call the bank".to_string(),
+                },
+                PlannedItem {
+                    task_number: Some(7),
+                    title: "Budget (draft)".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{
+    models::{
+        store::{CURRENT_VERSION, Store},
+        task::{Task, When},
+    },
+    storage::{Storage, json::JsonFileStorage},
+};
+
+/// Oldest schema this build still migrates, used to exercise the migration chain
+const OLDEST_STORE: &str = include_str!("storage/fixtures/store_v1.json");
+
+/// Difference between the filesystem's and the system's clock that is worth reporting
+const CLOCK_SKEW_TOLERANCE_SECS: u64 = 5;
+
+pub enum CheckOutcome {
+    Pass(String),
+    Warn(String),
+    Fail(String),
+}
+
+/// Result of a single self-test step
+pub struct Check {
+    pub name: &'static str,
+    pub outcome: CheckOutcome,
+}
+
+/// Exercise save, load, migration and backups against a scratch store created
+/// inside `data_dir` (so it lives on the same filesystem as the real store),
+/// and look for environment problems that make saves fail or go missing.
+/// `store` is the real store, only read to look for timestamps in the future.
+pub fn run_selftest(data_dir: &Path, store: &Store) -> Vec<Check> {
+    let scratch_dir = data_dir.join(format!(".selftest-{}", std::process::id()));
+
+    let mut checks = vec![Check {
+        name: "Data directory is writable",
+        outcome: match fs::create_dir_all(&scratch_dir) {
+            Ok(()) => CheckOutcome::Pass(data_dir.display().to_string()),
+            Err(e) => CheckOutcome::Fail(format!("{}: {}", data_dir.display(), e)),
+        },
+    }];
+
+    if matches!(checks[0].outcome, CheckOutcome::Pass(_)) {
+        checks.push(check_rename(&scratch_dir));
+        checks.extend(check_round_trip(&scratch_dir.join("store.json")));
+        checks.push(check_migration(&scratch_dir.join("old-store.json")));
+        checks.push(check_file_clock(&scratch_dir));
+        let _ = fs::remove_dir_all(&scratch_dir);
+    }
+
+    checks.push(check_store_clock(store));
+    checks
+}
+
+/// Saves replace the store with an atomic rename, which some network and
+/// synced filesystems don't support
+fn check_rename(dir: &Path) -> Check {
+    let from = dir.join("rename-from");
+    let to = dir.join("rename-to");
+
+    let outcome = match fs::write(&from, "tdo").and_then(|()| fs::rename(&from, &to)) {
+        Ok(()) => CheckOutcome::Pass("atomic replace works".to_string()),
+        Err(e) => CheckOutcome::Fail(format!("rename failed: {}", e)),
+    };
+
+    Check {
+        name: "Filesystem supports rename",
+        outcome,
+    }
+}
+
+/// Save a store twice, load it back and verify the backup the second save made
+fn check_round_trip(path: &Path) -> Vec<Check> {
+    let storage = JsonFileStorage::new(path.to_path_buf());
+
+    let mut store = Store::default();
+    store.add_task(Task {
+        id: uuid::Uuid::new_v4(),
+        title: "Self-test task".to_string(),
+        when: When::Today { evening: false },
+        created_at: jiff::Timestamp::now(),
+        ..Task::default()
+    });
+
+    let saved = storage
+        .save(&store)
+        .and_then(|()| storage.save(&store))
+        .and_then(|()| storage.load());
+
+    let round_trip = Check {
+        name: "Save and load round trip",
+        outcome: match &saved {
+            Ok(loaded)
+                if loaded.get_task_by_number(1).map(|t| t.title.as_str())
+                    == Some("Self-test task") =>
+            {
+                CheckOutcome::Pass("saved store loads back unchanged".to_string())
+            }
+            Ok(_) => CheckOutcome::Fail("saved task was missing after loading".to_string()),
+            Err(e) => CheckOutcome::Fail(e.to_string()),
+        },
+    };
+
+    let backups = Check {
+        name: "Backups are written and readable",
+        outcome: match storage.verify_backups() {
+            Ok(verifications) if verifications.is_empty() => {
+                CheckOutcome::Fail("no backup was created on save".to_string())
+            }
+            Ok(verifications) => match verifications.iter().find_map(|v| match &v.result {
+                Ok((_, issues)) => issues.first().map(|issue| issue.to_string()),
+                Err(e) => Some(e.to_string()),
+            }) {
+                Some(problem) => CheckOutcome::Fail(problem),
+                None => CheckOutcome::Pass(format!("{} backup(s) verified", verifications.len())),
+            },
+            Err(e) => CheckOutcome::Fail(e.to_string()),
+        },
+    };
+
+    vec![round_trip, backups]
+}
+
+/// Load a store in the oldest supported schema so every migration runs
+fn check_migration(path: &PathBuf) -> Check {
+    let outcome = match fs::write(path, OLDEST_STORE) {
+        Err(e) => CheckOutcome::Fail(format!("{}: {}", path.display(), e)),
+        Ok(()) => match JsonFileStorage::new(path.clone()).load() {
+            Ok(store) if store.tasks.len() == 3 => {
+                CheckOutcome::Pass(format!("v1 → v{}", CURRENT_VERSION))
+            }
+            Ok(store) => CheckOutcome::Fail(format!(
+                "expected 3 tasks after migrating, found {}",
+                store.tasks.len()
+            )),
+            Err(e) => CheckOutcome::Fail(e.to_string()),
+        },
+    };
+
+    Check {
+        name: "Old stores migrate",
+        outcome,
+    }
+}
+
+/// A file's modification time far from the system clock points at a network
+/// filesystem with a different clock, which confuses backup ordering
+fn check_file_clock(dir: &Path) -> Check {
+    let path = dir.join("clock");
+    let now = SystemTime::now();
+
+    let modified = fs::write(&path, "tdo").and_then(|()| fs::metadata(&path)?.modified());
+    let outcome = match modified {
+        Ok(modified) => {
+            let skew = modified
+                .duration_since(now)
+                .or_else(|_| now.duration_since(modified))
+                .unwrap_or_default()
+                .as_secs();
+            if skew > CLOCK_SKEW_TOLERANCE_SECS {
+                CheckOutcome::Warn(format!("file times are {}s off the system clock", skew))
+            } else {
+                CheckOutcome::Pass("file times match the system clock".to_string())
+            }
+        }
+        Err(e) => CheckOutcome::Warn(format!("could not read file times: {}", e)),
+    };
+
+    Check {
+        name: "Filesystem clock",
+        outcome,
+    }
+}
+
+/// Timestamps in the future mean the clock was ahead when they were written,
+/// or is behind now
+fn check_store_clock(store: &Store) -> Check {
+    let now = jiff::Timestamp::now();
+    let in_future = store
+        .tasks
+        .values()
+        .filter(|t| {
+            [Some(t.created_at), t.completed_at, t.deleted_at]
+                .into_iter()
+                .flatten()
+                .any(|ts| ts > now)
+        })
+        .count();
+
+    let outcome = if in_future == 0 {
+        CheckOutcome::Pass("no timestamps in the future".to_string())
+    } else {
+        CheckOutcome::Warn(format!(
+            "{} task(s) have timestamps in the future; the system clock may be wrong",
+            in_future
+        ))
+    };
+
+    Check {
+        name: "System clock",
+        outcome,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selftest_passes_and_cleans_up() {
+        let data_dir = PathBuf::from("/tmp/test_selftest");
+        let _ = fs::remove_dir_all(&data_dir);
+        fs::create_dir_all(&data_dir).unwrap();
+
+        let checks = run_selftest(&data_dir, &Store::default());
+        for check in &checks {
+            if let CheckOutcome::Fail(message) = &check.outcome {
+                panic!("{} failed: {}", check.name, message);
+            }
+        }
+        assert_eq!(fs::read_dir(&data_dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_future_timestamps_warn() {
+        let mut store = Store::default();
+        store.add_task(Task {
+            created_at: jiff::Timestamp::now() + jiff::SignedDuration::from_hours(48),
+            ..Task::default()
+        });
+
+        let check = check_store_clock(&store);
+        assert!(matches!(check.outcome, CheckOutcome::Warn(_)));
+    }
+}
@@ -1,3 +1,10 @@
+pub mod archive;
 pub mod areas;
+pub mod autoschedule;
+pub mod doctor;
+pub mod groom;
+pub mod import;
+pub mod plan;
 pub mod projects;
 pub mod tasks;
+pub mod trash;
@@ -0,0 +1,66 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    models::{store::Store, task::Task},
+    services::trash::age_cutoff,
+    storage::{Storage, StorageError},
+};
+
+#[derive(Debug, Error)]
+pub enum ArchiveTasksError {
+    #[error("Invalid age '{0}': {1}")]
+    InvalidAge(String, String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct ArchiveTasksParameters {
+    /// Archive tasks completed longer ago than this (e.g., "90d", "6mo")
+    pub older_than: String,
+}
+
+/// Move completed tasks out of the store and into the archive. The archive is
+/// written before the store, so an interruption leaves tasks in both places
+/// rather than in neither; archiving them again is harmless.
+pub fn archive_tasks(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: ArchiveTasksParameters,
+) -> Result<Vec<Task>, ArchiveTasksError> {
+    // 1. Pick completed tasks older than the cutoff, trashed ones included
+    let cutoff = age_cutoff(&parameters.older_than)
+        .map_err(|e| ArchiveTasksError::InvalidAge(parameters.older_than.clone(), e))?;
+    let mut archived: Vec<Task> = store
+        .tasks
+        .values()
+        .filter(|t| t.completed_at.is_some_and(|ts| ts <= cutoff))
+        .cloned()
+        .collect();
+    archived.sort_by_key(|t| t.task_number);
+
+    if archived.is_empty() {
+        return Ok(archived);
+    }
+
+    // 2. Write the archive, then drop the tasks from the store
+    storage.archive(&archived)?;
+
+    let archived_ids: Vec<Uuid> = archived.iter().map(|t| t.id).collect();
+    for id in &archived_ids {
+        store.tasks.remove(id);
+    }
+    for project in store.projects.values_mut() {
+        if project
+            .next_action
+            .is_some_and(|id| archived_ids.contains(&id))
+        {
+            project.next_action = None;
+        }
+    }
+
+    storage.save(store)?;
+
+    Ok(archived)
+}
@@ -1,14 +1,20 @@
 use crate::{
-    models::{area::Area, store::Store},
+    models::{area::Area, project::Project, store::Store, task::TaskChange},
+    services::tasks::{NameLookupError, find_area_id, find_by_slug_or_name},
     storage::{Storage, StorageError},
+    ui::normalize_color,
 };
 use slug::slugify;
 use thiserror::Error;
+use uuid::Uuid;
 
 #[derive(Debug, Error)]
 pub enum CreateAreaError {
-    #[error("Area with name '{}' already exists", .0)]
-    AreaAlreadyExists(String),
+    #[error("Slug '{0}' is already used by another area")]
+    SlugTaken(String),
+
+    #[error("Slug '{0}' has no letters or digits")]
+    InvalidSlug(String),
 
     #[error("Storage error: {0}")]
     Storage(#[from] StorageError),
@@ -16,6 +22,8 @@ pub enum CreateAreaError {
 
 pub struct CreateAreaParameters {
     pub name: String,
+    /// Slug to use instead of one derived from the name
+    pub slug: Option<String>,
 }
 
 pub fn create_area(
@@ -23,9 +31,23 @@ pub fn create_area(
     storage: &impl Storage,
     parameters: CreateAreaParameters,
 ) -> Result<Area, CreateAreaError> {
-    let area_slug = slugify(&parameters.name);
+    // A derived slug is numbered when taken; a chosen one must be free
+    let area_slug = match parameters.slug {
+        Some(requested) => {
+            let slug = slugify(&requested);
+            if slug.is_empty() {
+                return Err(CreateAreaError::InvalidSlug(requested));
+            }
+            if store.areas.values().any(|a| a.slug == slug) {
+                return Err(CreateAreaError::SlugTaken(slug));
+            }
+            slug
+        }
+        None => store.unique_area_slug(&parameters.name, None),
+    };
 
     let area = Area {
+        id: uuid::Uuid::new_v4(),
         name: parameters.name,
         slug: area_slug,
         ..Area::default()
@@ -64,26 +86,9 @@ pub fn delete_area(
     storage: &impl Storage,
     parameters: DeleteAreaParameters,
 ) -> Result<DeleteAreaResult, DeleteAreaError> {
-    // Fuzzy match to find area
-    let matching_areas: Vec<_> = store
-        .get_active_areas()
-        .filter(|a| {
-            a.name
-                .to_lowercase()
-                .contains(&parameters.name.to_lowercase())
-        })
-        .collect();
-
-    let area = match matching_areas.len() {
-        0 => return Err(DeleteAreaError::AreaNotFound(parameters.name)),
-        1 => matching_areas[0],
-        _ => {
-            // If ambiguous, require exact match or fail
-            return Err(DeleteAreaError::AreaNotFound(parameters.name));
-        }
-    };
-
-    let area_id = area.id;
+    // An ambiguous name is reported as not found
+    let area_id = find_area_id(store, &parameters.name)
+        .map_err(|_| DeleteAreaError::AreaNotFound(parameters.name.clone()))?;
     let now = jiff::Timestamp::now();
 
     // Cascade delete: Find all projects in this area
@@ -108,6 +113,7 @@ pub fn delete_area(
         for task_id in task_ids {
             if let Some(task) = store.get_task_mut(task_id) {
                 task.deleted_at = Some(now);
+                task.record(TaskChange::Deleted);
             }
         }
     }
@@ -131,6 +137,7 @@ pub fn delete_area(
     for task_id in direct_task_ids {
         if let Some(task) = store.get_task_mut(task_id) {
             task.deleted_at = Some(now);
+            task.record(TaskChange::Deleted);
         }
     }
 
@@ -149,6 +156,238 @@ pub fn delete_area(
     })
 }
 
+#[derive(Debug, Error)]
+pub enum EditAreaError {
+    #[error("Area '{0}' not found")]
+    AreaNotFound(String),
+
+    #[error("Unknown color '{0}'")]
+    InvalidColor(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct EditAreaParameters {
+    pub slug: String,
+    /// Color name, e.g. "green" (see `ui::normalize_color`)
+    pub color: Option<String>,
+    pub clear_color: bool,
+    /// Emoji or symbol shown before the name
+    pub icon: Option<String>,
+    pub clear_icon: bool,
+}
+
+pub fn edit_area(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: EditAreaParameters,
+) -> Result<Area, EditAreaError> {
+    let area_id = store
+        .get_active_areas()
+        .find(|a| a.slug.eq_ignore_ascii_case(&parameters.slug))
+        .ok_or(EditAreaError::AreaNotFound(parameters.slug))?
+        .id;
+
+    let color = match parameters.color {
+        Some(color) => Some(normalize_color(&color).ok_or(EditAreaError::InvalidColor(color))?),
+        None => None,
+    };
+
+    if let Some(area) = store.get_area_mut(area_id) {
+        if color.is_some() {
+            area.color = color;
+        } else if parameters.clear_color {
+            area.color = None;
+        }
+
+        if let Some(icon) = parameters.icon.filter(|i| !i.trim().is_empty()) {
+            area.icon = Some(icon.trim().to_string());
+        } else if parameters.clear_icon {
+            area.icon = None;
+        }
+    }
+
+    storage.save(store)?;
+
+    Ok(store.get_area(area_id).unwrap().clone())
+}
+
+#[derive(Debug, Error)]
+pub enum SetAreaNotesError {
+    #[error("Area '{0}' not found")]
+    AreaNotFound(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct SetAreaNotesParameters {
+    pub slug: String,
+    /// New notes; `None` or blank text removes them
+    pub notes: Option<String>,
+}
+
+pub fn set_area_notes(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: SetAreaNotesParameters,
+) -> Result<Area, SetAreaNotesError> {
+    let area_id = store
+        .get_active_areas()
+        .find(|a| a.slug.eq_ignore_ascii_case(&parameters.slug))
+        .ok_or(SetAreaNotesError::AreaNotFound(parameters.slug))?
+        .id;
+
+    let notes = parameters
+        .notes
+        .map(|n| n.trim_end().to_string())
+        .filter(|n| !n.trim().is_empty());
+    if let Some(area) = store.get_area_mut(area_id) {
+        area.notes = notes;
+    }
+
+    storage.save(store)?;
+
+    Ok(store.get_area(area_id).unwrap().clone())
+}
+
+#[derive(Debug, Error)]
+pub enum OrderAreaError {
+    #[error("Area '{0}' not found")]
+    AreaNotFound(String),
+
+    #[error("An area can't be placed next to itself")]
+    SameArea,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Where to place an area relative to another one
+pub enum AreaPlacement {
+    Before(String),
+    After(String),
+}
+
+pub struct OrderAreaParameters {
+    pub slug: String,
+    pub placement: AreaPlacement,
+}
+
+/// Move an area right before or after another one, renumbering every active
+/// area in its current display order like `order_project`
+pub fn order_area(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: OrderAreaParameters,
+) -> Result<Vec<Area>, OrderAreaError> {
+    let find = |store: &Store, slug: &str| {
+        store
+            .get_active_areas()
+            .find(|a| a.slug.eq_ignore_ascii_case(slug))
+            .map(|a| a.id)
+    };
+
+    let area_id =
+        find(store, &parameters.slug).ok_or(OrderAreaError::AreaNotFound(parameters.slug))?;
+    let (other_slug, after) = match parameters.placement {
+        AreaPlacement::Before(slug) => (slug, false),
+        AreaPlacement::After(slug) => (slug, true),
+    };
+    let other_id = find(store, &other_slug).ok_or(OrderAreaError::AreaNotFound(other_slug))?;
+    if area_id == other_id {
+        return Err(OrderAreaError::SameArea);
+    }
+
+    let mut areas: Vec<_> = store.get_active_areas().collect();
+    areas.sort_by_key(|a| a.display_order());
+    let mut ids: Vec<Uuid> = areas
+        .iter()
+        .map(|a| a.id)
+        .filter(|id| *id != area_id)
+        .collect();
+    let other_index = ids.iter().position(|id| *id == other_id).unwrap();
+    ids.insert(if after { other_index + 1 } else { other_index }, area_id);
+
+    for (index, id) in ids.iter().enumerate() {
+        if let Some(area) = store.get_area_mut(*id) {
+            area.sort_order = Some(index as u32);
+        }
+    }
+
+    storage.save(store)?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| store.get_area(*id))
+        .cloned()
+        .collect())
+}
+
+#[derive(Debug, Error)]
+pub enum RenameAreaError {
+    #[error("Area '{0}' not found")]
+    AreaNotFound(String),
+
+    #[error("Area name is ambiguous. Multiple areas found: {}", .0.join(", "))]
+    AmbiguousAreaName(Vec<String>),
+
+    #[error("The new name is empty")]
+    EmptyName,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct RenameAreaParameters {
+    /// Slug, or part of the current name
+    pub area: String,
+    pub new_name: String,
+}
+
+pub struct RenameAreaResult {
+    pub previous_name: String,
+    pub area: Area,
+}
+
+/// Give an area a new name and a slug to match. Projects and tasks point at
+/// the area by id, so they follow it without changes.
+pub fn rename_area(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: RenameAreaParameters,
+) -> Result<RenameAreaResult, RenameAreaError> {
+    let new_name = parameters.new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err(RenameAreaError::EmptyName);
+    }
+
+    let by_slug = store
+        .get_active_areas()
+        .find(|a| a.slug.eq_ignore_ascii_case(&parameters.area))
+        .map(|a| a.id);
+    let area_id = match by_slug {
+        Some(id) => id,
+        None => find_area_id(store, &parameters.area).map_err(|e| match e {
+            NameLookupError::NotFound => RenameAreaError::AreaNotFound(parameters.area),
+            NameLookupError::Ambiguous(names) => RenameAreaError::AmbiguousAreaName(names),
+        })?,
+    };
+
+    let slug = store.unique_area_slug(&new_name, Some(area_id));
+    let area = store.get_area_mut(area_id).expect("area was just found");
+    let previous_name = std::mem::replace(&mut area.name, new_name);
+    area.slug = slug;
+
+    storage.save(store)?;
+
+    Ok(RenameAreaResult {
+        previous_name,
+        area: store.get_area(area_id).unwrap().clone(),
+    })
+}
+
 #[derive(Debug, Error)]
 pub enum RestoreAreaError {
     #[error("Area '{0}' not found")]
@@ -157,38 +396,85 @@ pub enum RestoreAreaError {
     #[error("Area '{0}' is not deleted")]
     AreaNotDeleted(String),
 
+    #[error("Area name is ambiguous. Multiple deleted areas found: {}", .0.join(", "))]
+    AmbiguousAreaName(Vec<String>),
+
     #[error("Storage error: {0}")]
     Storage(#[from] StorageError),
 }
 
 pub struct RestoreAreaParameters {
     pub name: String,
+    /// Also restore the projects and tasks trashed along with the area
+    pub with_contents: bool,
+}
+
+pub struct RestoreAreaResult {
+    pub area: Area,
+    pub restored_projects_count: usize,
+    pub restored_tasks_count: usize,
 }
 
 pub fn restore_area(
     store: &mut Store,
     storage: &impl Storage,
     parameters: RestoreAreaParameters,
-) -> Result<Area, RestoreAreaError> {
-    // Find deleted area by name
-    let matching_areas: Vec<_> = store
-        .get_deleted_areas()
-        .filter(|a| {
-            a.name
-                .to_lowercase()
-                .contains(&parameters.name.to_lowercase())
-        })
-        .collect();
+) -> Result<RestoreAreaResult, RestoreAreaError> {
+    // Find deleted area by slug or name
+    let area = find_by_slug_or_name(
+        store.get_deleted_areas(),
+        |a| (&a.slug, &a.name),
+        &parameters.name,
+    )
+    .map_err(|e| match e {
+        NameLookupError::NotFound => {
+            // Point out when the area exists but was never deleted
+            match find_area_id(store, &parameters.name) {
+                Ok(id) => {
+                    RestoreAreaError::AreaNotDeleted(store.get_area(id).unwrap().name.clone())
+                }
+                Err(_) => RestoreAreaError::AreaNotFound(parameters.name.clone()),
+            }
+        }
+        NameLookupError::Ambiguous(names) => RestoreAreaError::AmbiguousAreaName(names),
+    })?;
 
-    let area = match matching_areas.len() {
-        0 => return Err(RestoreAreaError::AreaNotFound(parameters.name)),
-        1 => matching_areas[0],
-        _ => return Err(RestoreAreaError::AreaNotFound(parameters.name)),
+    let area_id = area.id;
+    let deleted_at = area.deleted_at;
+
+    // Projects and tasks cascade-deleted with the area share its deletion
+    // timestamp; ones trashed on their own before keep theirs and stay in the trash
+    let (project_ids_to_restore, task_ids_to_restore) = if parameters.with_contents {
+        let project_ids: Vec<Uuid> = store
+            .get_projects_for_area(area_id)
+            .filter(|p| p.deleted_at.is_some() && p.deleted_at == deleted_at)
+            .map(|p| p.id)
+            .collect();
+        let task_ids: Vec<Uuid> = project_ids
+            .iter()
+            .flat_map(|project_id| store.get_tasks_for_project(*project_id))
+            .chain(store.get_tasks_for_area(area_id))
+            .filter(|t| t.deleted_at.is_some() && t.deleted_at == deleted_at)
+            .map(|t| t.id)
+            .collect();
+        (project_ids, task_ids)
+    } else {
+        (vec![], vec![])
     };
 
-    let area_id = area.id;
+    for task_id in &task_ids_to_restore {
+        if let Some(task) = store.get_task_mut(*task_id) {
+            task.deleted_at = None;
+            task.record(TaskChange::Restored);
+        }
+    }
+
+    for project_id in &project_ids_to_restore {
+        if let Some(project) = store.get_project_mut(*project_id) {
+            project.deleted_at = None;
+        }
+    }
 
-    // Restore area (does NOT auto-restore projects/tasks - user must restore them separately)
     if let Some(area) = store.get_area_mut(area_id) {
         area.deleted_at = None;
     }
@@ -196,5 +482,298 @@ pub fn restore_area(
     // Persist to storage
     storage.save(store)?;
 
+    Ok(RestoreAreaResult {
+        area: store.get_area(area_id).unwrap().clone(),
+        restored_projects_count: project_ids_to_restore.len(),
+        restored_tasks_count: task_ids_to_restore.len(),
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum MoveAreaProjectsError {
+    #[error("Area '{0}' not found")]
+    AreaNotFound(String),
+
+    #[error("Projects can't be moved to the area they're already in")]
+    SameArea,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct MoveAreaProjectsParameters {
+    /// Slug of the area the projects leave
+    pub from: String,
+    /// Slug of the area the projects go to
+    pub to: String,
+}
+
+pub struct MoveAreaProjectsResult {
+    pub from: Area,
+    pub to: Area,
+    /// Projects moved, archived ones included
+    pub projects: Vec<Project>,
+}
+
+/// Move every project of an area to another one. Loose tasks of the area and
+/// projects in the trash stay where they are.
+pub fn move_area_projects(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: MoveAreaProjectsParameters,
+) -> Result<MoveAreaProjectsResult, MoveAreaProjectsError> {
+    let find_area = |slug: &str| {
+        store
+            .get_active_areas()
+            .find(|a| a.slug.eq_ignore_ascii_case(slug))
+            .map(|a| a.id)
+            .ok_or_else(|| MoveAreaProjectsError::AreaNotFound(slug.to_string()))
+    };
+    let from_id = find_area(&parameters.from)?;
+    let to_id = find_area(&parameters.to)?;
+
+    if from_id == to_id {
+        return Err(MoveAreaProjectsError::SameArea);
+    }
+
+    let mut project_ids: Vec<Uuid> = store
+        .get_projects_for_area(from_id)
+        .filter(|p| p.deleted_at.is_none())
+        .map(|p| p.id)
+        .collect();
+    project_ids.sort_by_key(|id| store.get_project(*id).unwrap().display_order());
+
+    for project_id in &project_ids {
+        if let Some(project) = store.get_project_mut(*project_id) {
+            project.area_id = Some(to_id);
+        }
+    }
+
+    if !project_ids.is_empty() {
+        storage.save(store)?;
+    }
+
+    Ok(MoveAreaProjectsResult {
+        from: store.get_area(from_id).unwrap().clone(),
+        to: store.get_area(to_id).unwrap().clone(),
+        projects: project_ids
+            .iter()
+            .map(|id| store.get_project(*id).unwrap().clone())
+            .collect(),
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum ArchiveAreaError {
+    #[error("Area '{0}' not found")]
+    AreaNotFound(String),
+
+    #[error("Area name is ambiguous. Multiple areas found: {}", .0.join(", "))]
+    AmbiguousAreaName(Vec<String>),
+
+    #[error("Area '{0}' is already archived")]
+    AlreadyArchived(String),
+
+    #[error("Area '{0}' is not archived")]
+    NotArchived(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct ArchiveAreaParameters {
+    /// Slug, or part of the name
+    pub area: String,
+    /// Bring an archived area back instead
+    pub unarchive: bool,
+}
+
+/// Put a dormant area away without trashing it, or bring it back. The area,
+/// its projects and their tasks are left out of the lists and working views;
+/// unlike deletion, nothing ends up in the trash.
+pub fn archive_area(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: ArchiveAreaParameters,
+) -> Result<Area, ArchiveAreaError> {
+    let area_id = find_area_id(store, &parameters.area).map_err(|e| match e {
+        NameLookupError::NotFound => ArchiveAreaError::AreaNotFound(parameters.area),
+        NameLookupError::Ambiguous(names) => ArchiveAreaError::AmbiguousAreaName(names),
+    })?;
+    let area = store.get_area_mut(area_id).unwrap();
+
+    match (parameters.unarchive, area.archived_at) {
+        (false, Some(_)) => return Err(ArchiveAreaError::AlreadyArchived(area.name.clone())),
+        (true, None) => return Err(ArchiveAreaError::NotArchived(area.name.clone())),
+        (false, None) => area.archived_at = Some(jiff::Timestamp::now()),
+        (true, Some(_)) => area.archived_at = None,
+    }
+
+    storage.save(store)?;
+
     Ok(store.get_area(area_id).unwrap().clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+    use crate::{models::task::Task, storage::json::JsonFileStorage};
+
+    #[test]
+    fn test_create_area_assigns_a_fresh_id() {
+        let test_dir = PathBuf::from("/tmp/tdo_create_area_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+        let storage = JsonFileStorage::new(test_dir.join("store.json"));
+        let mut store = Store::default();
+
+        let work = create_area(
+            &mut store,
+            &storage,
+            CreateAreaParameters {
+                name: "Work".to_string(),
+                slug: None,
+            },
+        )
+        .unwrap();
+        let home = create_area(
+            &mut store,
+            &storage,
+            CreateAreaParameters {
+                name: "Home".to_string(),
+                slug: None,
+            },
+        )
+        .unwrap();
+
+        assert!(!work.id.is_nil());
+        assert!(!home.id.is_nil());
+        assert_ne!(work.id, home.id);
+        assert_eq!(store.get_active_areas().count(), 2);
+    }
+
+    /// A store with a "Home" area holding a project with a task, a task of its
+    /// own, and a task that was trashed before the area
+    fn store_with_home_area() -> (Store, Uuid, Uuid) {
+        let mut store = Store::default();
+        let area = Area {
+            id: Uuid::new_v4(),
+            name: "Home".to_string(),
+            slug: "home".to_string(),
+            ..Area::default()
+        };
+        let project = Project {
+            id: Uuid::new_v4(),
+            name: "Garden".to_string(),
+            slug: "garden".to_string(),
+            area_id: Some(area.id),
+            ..Project::default()
+        };
+        let earlier_trashed = Task {
+            id: Uuid::new_v4(),
+            title: "Old chore".to_string(),
+            area_id: Some(area.id),
+            deleted_at: Some(jiff::Timestamp::UNIX_EPOCH),
+            ..Task::default()
+        };
+        let earlier_trashed_id = earlier_trashed.id;
+        for task in [
+            Task {
+                id: Uuid::new_v4(),
+                title: "Plant tulips".to_string(),
+                project_id: Some(project.id),
+                ..Task::default()
+            },
+            Task {
+                id: Uuid::new_v4(),
+                title: "Fix the tap".to_string(),
+                area_id: Some(area.id),
+                ..Task::default()
+            },
+            earlier_trashed,
+        ] {
+            store.add_task(task);
+        }
+        let project_id = project.id;
+        store.add_area(area);
+        store.add_project(project);
+        (store, project_id, earlier_trashed_id)
+    }
+
+    fn test_storage(name: &str) -> JsonFileStorage {
+        let test_dir = PathBuf::from(format!("/tmp/tdo_{}_test", name));
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+        JsonFileStorage::new(test_dir.join("store.json"))
+    }
+
+    #[test]
+    fn test_restore_area_brings_back_what_was_deleted_with_it() {
+        let storage = test_storage("restore_area_contents");
+        let (mut store, project_id, earlier_trashed_id) = store_with_home_area();
+        let deleted = delete_area(
+            &mut store,
+            &storage,
+            DeleteAreaParameters {
+                name: "home".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(deleted.cascaded_tasks_count, 2);
+
+        let restored = restore_area(
+            &mut store,
+            &storage,
+            RestoreAreaParameters {
+                name: "home".to_string(),
+                with_contents: true,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(restored.restored_projects_count, 1);
+        assert_eq!(restored.restored_tasks_count, 2);
+        assert!(store.get_project(project_id).unwrap().deleted_at.is_none());
+        assert_eq!(store.get_active_tasks().count(), 2);
+        // Trashed on its own before the area, so it stays in the trash
+        assert!(
+            store
+                .get_task(earlier_trashed_id)
+                .unwrap()
+                .deleted_at
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_restore_area_without_contents_leaves_them_in_the_trash() {
+        let storage = test_storage("restore_area_alone");
+        let (mut store, project_id, _) = store_with_home_area();
+        delete_area(
+            &mut store,
+            &storage,
+            DeleteAreaParameters {
+                name: "home".to_string(),
+            },
+        )
+        .unwrap();
+
+        let restored = restore_area(
+            &mut store,
+            &storage,
+            RestoreAreaParameters {
+                name: "home".to_string(),
+                with_contents: false,
+            },
+        )
+        .unwrap();
+
+        assert!(restored.area.deleted_at.is_none());
+        assert_eq!(restored.restored_projects_count, 0);
+        assert_eq!(restored.restored_tasks_count, 0);
+        assert!(store.get_project(project_id).unwrap().deleted_at.is_some());
+        assert_eq!(store.get_active_tasks().count(), 0);
+    }
+}
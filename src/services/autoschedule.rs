@@ -0,0 +1,110 @@
+use jiff::civil::{Date, Weekday};
+use thiserror::Error;
+
+use crate::{
+    models::{
+        store::Store,
+        task::{Task, When},
+    },
+    storage::{Storage, StorageError},
+};
+
+#[derive(Debug, Error)]
+pub enum AutoscheduleError {
+    #[error("Tasks per day must be at least 1")]
+    InvalidPerDay,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct AutoscheduleParameters {
+    /// Most open tasks scheduled on a single day, counting ones already there
+    pub per_day: usize,
+    /// Only schedule Anytime tasks with this tag
+    pub tag: Option<String>,
+    /// First day to fill
+    pub start: Date,
+    /// Leave Saturdays and Sundays free
+    pub skip_weekends: bool,
+}
+
+/// Spread open Anytime tasks over the days from `start`, filling each day up to
+/// `per_day` tasks. Tasks with the nearest deadline go first, then the oldest.
+/// Returns the rescheduled tasks in the order they were placed.
+pub fn autoschedule(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: AutoscheduleParameters,
+) -> Result<Vec<Task>, AutoscheduleError> {
+    if parameters.per_day == 0 {
+        return Err(AutoscheduleError::InvalidPerDay);
+    }
+
+    let tag = parameters
+        .tag
+        .as_deref()
+        .map(|tag| tag.trim_start_matches('#'));
+
+    let mut backlog: Vec<_> = store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none() && !t.is_deferred())
+        .filter(|t| matches!(t.when, When::Anytime))
+        .filter(|t| tag.is_none_or(|tag| t.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))))
+        .collect();
+    backlog.sort_by_key(|t| {
+        (
+            t.deadline.is_none(),
+            t.deadline,
+            t.created_at,
+            t.task_number,
+        )
+    });
+
+    let mut scheduled = Vec::new();
+    let mut day = parameters.start;
+
+    for task in backlog {
+        // Find the next day with room left, counting tasks scheduled by hand
+        loop {
+            let is_weekend = matches!(day.weekday(), Weekday::Saturday | Weekday::Sunday);
+            let taken = scheduled_on(store, day)
+                + scheduled
+                    .iter()
+                    .filter(
+                        |t: &&Task| matches!(t.when, When::Scheduled { date, .. } if date == day),
+                    )
+                    .count();
+
+            if !(parameters.skip_weekends && is_weekend) && taken < parameters.per_day {
+                break;
+            }
+            day = day.tomorrow().expect("date should be valid");
+        }
+
+        let mut updated_task = task.clone();
+        updated_task.reschedule(When::Scheduled {
+            date: day,
+            evening: false,
+        });
+        scheduled.push(updated_task);
+    }
+
+    if !scheduled.is_empty() {
+        for task in &scheduled {
+            store.tasks.insert(task.id, task.clone());
+        }
+        storage.save(store)?;
+    }
+
+    Ok(scheduled)
+}
+
+/// Open tasks already scheduled on `date`
+fn scheduled_on(store: &Store, date: Date) -> usize {
+    store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none())
+        .filter(|t| matches!(t.when, When::Scheduled { date: day, .. } if day == date))
+        .count()
+}
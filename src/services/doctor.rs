@@ -0,0 +1,120 @@
+use thiserror::Error;
+
+use crate::{
+    models::{
+        store::{Orphan, Store},
+        task::TaskChange,
+    },
+    storage::{Storage, StorageError},
+};
+
+#[derive(Debug, Error)]
+pub enum DoctorError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct DoctorParameters {
+    /// Repair what can be repaired instead of only reporting it
+    pub fix: bool,
+}
+
+pub struct DoctorReport {
+    /// Numbering and reference problems found in the store
+    pub integrity_issues: Vec<String>,
+    /// Active items belonging to deleted projects or areas
+    pub orphans: Vec<Orphan>,
+    /// Number of references cleared when fixing
+    pub repaired_count: usize,
+}
+
+pub fn run_doctor(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: DoctorParameters,
+) -> Result<DoctorReport, DoctorError> {
+    let integrity_issues: Vec<String> = store
+        .integrity_issues()
+        .iter()
+        .map(|issue| issue.to_string())
+        .collect();
+    let orphans = store.find_orphans();
+
+    if !parameters.fix {
+        return Ok(DoctorReport {
+            integrity_issues,
+            orphans,
+            repaired_count: 0,
+        });
+    }
+
+    let mut repaired_count = 0;
+
+    // Reassign orphans to no project / no area
+    for orphan in &orphans {
+        match orphan {
+            Orphan::TaskInDeletedProject { task_id, .. } => {
+                if let Some(task) = store.get_task_mut(*task_id) {
+                    task.project_id = None;
+                    task.record(TaskChange::Edited {
+                        fields: vec!["project".to_string()],
+                    });
+                    repaired_count += 1;
+                }
+            }
+            Orphan::TaskInDeletedArea { task_id, .. } => {
+                if let Some(task) = store.get_task_mut(*task_id) {
+                    task.area_id = None;
+                    task.record(TaskChange::Edited {
+                        fields: vec!["area".to_string()],
+                    });
+                    repaired_count += 1;
+                }
+            }
+            Orphan::ProjectInDeletedArea { project_id, .. } => {
+                if let Some(project) = store.get_project_mut(*project_id) {
+                    project.area_id = None;
+                    repaired_count += 1;
+                }
+            }
+        }
+    }
+
+    // Clear references to projects and areas that no longer exist at all
+    let project_ids: Vec<_> = store.projects.keys().copied().collect();
+    let area_ids: Vec<_> = store.areas.keys().copied().collect();
+
+    for task in store.tasks.values_mut() {
+        if task.project_id.is_some_and(|id| !project_ids.contains(&id)) {
+            task.project_id = None;
+            task.record(TaskChange::Edited {
+                fields: vec!["project".to_string()],
+            });
+            repaired_count += 1;
+        }
+        if task.area_id.is_some_and(|id| !area_ids.contains(&id)) {
+            task.area_id = None;
+            task.record(TaskChange::Edited {
+                fields: vec!["area".to_string()],
+            });
+            repaired_count += 1;
+        }
+    }
+
+    for project in store.projects.values_mut() {
+        if project.area_id.is_some_and(|id| !area_ids.contains(&id)) {
+            project.area_id = None;
+            repaired_count += 1;
+        }
+    }
+
+    if repaired_count > 0 {
+        storage.save(store)?;
+    }
+
+    Ok(DoctorReport {
+        integrity_issues,
+        orphans,
+        repaired_count,
+    })
+}
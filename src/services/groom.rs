@@ -0,0 +1,107 @@
+use thiserror::Error;
+
+use crate::{
+    config::{GroomList, GroomRule},
+    models::{
+        store::Store,
+        task::{Task, When},
+    },
+    storage::{Storage, StorageError},
+    ui,
+};
+
+#[derive(Debug, Error)]
+pub enum GroomError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct GroomParameters {
+    pub rules: Vec<GroomRule>,
+    /// Save the changes instead of only previewing them
+    pub apply: bool,
+}
+
+pub enum GroomAction {
+    AddTag(String),
+    MoveTo(GroomList),
+}
+
+/// A task touched by one or more rules, as it looks after grooming
+pub struct GroomChange {
+    pub task: Task,
+    pub actions: Vec<GroomAction>,
+}
+
+/// Run the aging rules over open tasks, in order, and save the result when applying.
+/// A task's age is counted from its creation.
+pub fn groom(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: GroomParameters,
+) -> Result<Vec<GroomChange>, GroomError> {
+    let mut tasks: Vec<_> = store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none())
+        .collect();
+    tasks.sort_by_key(|t| t.task_number);
+
+    let mut changes = Vec::new();
+
+    for task in tasks {
+        let mut groomed = task.clone();
+        let mut actions = Vec::new();
+
+        for rule in &parameters.rules {
+            if !in_list(&groomed, rule.list)
+                || ui::is_within_days(groomed.created_at, i64::from(rule.older_than_days))
+            {
+                continue;
+            }
+
+            if let Some(tag) = &rule.add_tag
+                && !groomed.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+            {
+                groomed.tags.push(tag.clone());
+                actions.push(GroomAction::AddTag(tag.clone()));
+            }
+
+            if let Some(target) = rule.move_to
+                && target != rule.list
+            {
+                groomed.when = match target {
+                    GroomList::Inbox => When::Inbox,
+                    GroomList::Anytime => When::Anytime,
+                    GroomList::Someday => When::Someday,
+                };
+                actions.push(GroomAction::MoveTo(target));
+            }
+        }
+
+        if !actions.is_empty() {
+            groomed.record_changes_from(task);
+            changes.push(GroomChange {
+                task: groomed,
+                actions,
+            });
+        }
+    }
+
+    if parameters.apply && !changes.is_empty() {
+        for change in &changes {
+            store.tasks.insert(change.task.id, change.task.clone());
+        }
+        storage.save(store)?;
+    }
+
+    Ok(changes)
+}
+
+fn in_list(task: &Task, list: GroomList) -> bool {
+    matches!(
+        (&task.when, list),
+        (When::Inbox, GroomList::Inbox)
+            | (When::Anytime, GroomList::Anytime)
+            | (When::Someday, GroomList::Someday)
+    )
+}
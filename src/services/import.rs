@@ -0,0 +1,142 @@
+use slug::slugify;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    import::{MarkdownItem, parse_markdown_checklist},
+    models::{
+        project::Project,
+        store::Store,
+        task::{ChecklistItem, Task, TaskChange},
+    },
+    services::tasks::{NameLookupError, find_project_id},
+    storage::{Storage, StorageError},
+};
+
+#[derive(Debug, Error)]
+pub enum ImportMarkdownError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Project name is ambiguous. Multiple projects found: {}", .0.join(", "))]
+    AmbiguousProjectName(Vec<String>),
+
+    #[error("No checklist items found")]
+    NothingToImport,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct ImportMarkdownParameters {
+    /// Markdown document to read `- [ ]` / `- [x]` items from
+    pub content: String,
+    /// Put every item in this project instead of one project per heading
+    pub project: Option<String>,
+}
+
+pub struct ImportMarkdownResult {
+    pub tasks: Vec<Task>,
+    /// Projects created for headings that didn't match an existing project
+    pub created_projects: Vec<Project>,
+}
+
+/// Import the checklist items of a Markdown document as tasks, saving once.
+/// Without a project, each heading maps to the project with the same slug, created
+/// if needed; items above the first heading go to the Inbox. Nested checkboxes
+/// become the task's checklist and `[x]` items are imported as completed.
+pub fn import_markdown(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: ImportMarkdownParameters,
+) -> Result<ImportMarkdownResult, ImportMarkdownError> {
+    let sections = parse_markdown_checklist(&parameters.content);
+    if sections.is_empty() {
+        return Err(ImportMarkdownError::NothingToImport);
+    }
+
+    let target_project_id = match parameters.project {
+        Some(project_name) => Some(find_project_id(store, &project_name).map_err(|e| match e {
+            NameLookupError::NotFound => ImportMarkdownError::ProjectNotFound(project_name),
+            NameLookupError::Ambiguous(names) => ImportMarkdownError::AmbiguousProjectName(names),
+        })?),
+        None => None,
+    };
+
+    let now = jiff::Timestamp::now();
+    let mut created_projects = vec![];
+    let mut task_ids = vec![];
+
+    for section in sections {
+        let project_id = match (target_project_id, section.heading) {
+            (Some(project_id), _) => Some(project_id),
+            (None, Some(heading)) => {
+                let slug = slugify(&heading);
+                let existing = store
+                    .get_active_projects()
+                    .find(|p| p.slug == slug)
+                    .map(|p| p.id);
+
+                match existing {
+                    Some(project_id) => Some(project_id),
+                    None => {
+                        let project = Project {
+                            id: Uuid::new_v4(),
+                            slug: store.unique_project_slug(&heading, None),
+                            name: heading,
+                            created_at: now,
+                            ..Project::default()
+                        };
+                        let project_id = project.id;
+                        created_projects.push(project.clone());
+                        store.add_project(project);
+                        Some(project_id)
+                    }
+                }
+            }
+            (None, None) => None,
+        };
+
+        for item in section.items {
+            let task = task_from_item(item, project_id, now);
+            task_ids.push(task.id);
+            store.add_task(task);
+        }
+    }
+
+    storage.save(store)?;
+
+    Ok(ImportMarkdownResult {
+        tasks: task_ids
+            .into_iter()
+            .filter_map(|id| store.get_task(id).cloned())
+            .collect(),
+        created_projects,
+    })
+}
+
+fn task_from_item(item: MarkdownItem, project_id: Option<Uuid>, now: jiff::Timestamp) -> Task {
+    let mut task = Task {
+        id: Uuid::new_v4(),
+        title: item.title,
+        project_id,
+        checklist: item
+            .subitems
+            .into_iter()
+            .map(|subitem| ChecklistItem {
+                id: Uuid::new_v4(),
+                title: subitem.title,
+                completed: subitem.done,
+            })
+            .collect(),
+        completed_at: item.done.then_some(now),
+        created_at: now,
+        ..Task::default()
+    };
+
+    task.record(TaskChange::Created);
+    if item.done {
+        task.record(TaskChange::Completed);
+    }
+    task
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        store::Store,
+        task::{Task, TaskChange, When},
+    },
+    plan::parse_week_plan,
+    storage::{Storage, StorageError},
+};
+
+#[derive(Debug, Error)]
+pub enum ApplyWeekPlanError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct ApplyWeekPlanParameters {
+    /// The plan as generated, to tell which lines were moved
+    pub original: String,
+    /// The plan after editing
+    pub edited: String,
+}
+
+pub struct ApplyWeekPlanResult {
+    /// Existing tasks now planned for a different day
+    pub rescheduled: Vec<Task>,
+    /// Tasks created from lines without a task reference
+    pub created: Vec<Task>,
+    /// Referenced task numbers that aren't open tasks
+    pub unknown_numbers: Vec<u64>,
+}
+
+/// Apply an edited week plan: tasks listed under a different day than in the
+/// original plan are scheduled on that day, and new lines become tasks on their
+/// day. Days up to today schedule for Today. Saves once, and only if anything changed.
+pub fn apply_week_plan(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: ApplyWeekPlanParameters,
+) -> Result<ApplyWeekPlanResult, ApplyWeekPlanError> {
+    let edited = parse_week_plan(&parameters.edited);
+    let original_days: HashMap<u64, _> = parse_week_plan(&parameters.original)
+        .into_iter()
+        .flat_map(|day| {
+            day.items
+                .into_iter()
+                .filter_map(move |item| Some((item.task_number?, day.date)))
+        })
+        .collect();
+
+    let today = jiff::Zoned::now().date();
+    let now = jiff::Timestamp::now();
+    let mut rescheduled_ids: Vec<Uuid> = vec![];
+    let mut created_ids: Vec<Uuid> = vec![];
+    let mut unknown_numbers = vec![];
+
+    for day in edited {
+        let when = if day.date <= today {
+            When::Today { evening: false }
+        } else {
+            When::Scheduled {
+                date: day.date,
+                evening: false,
+            }
+        };
+
+        for item in day.items {
+            let Some(number) = item.task_number else {
+                let mut task = Task {
+                    id: Uuid::new_v4(),
+                    title: item.title,
+                    when: when.clone(),
+                    created_at: now,
+                    ..Task::default()
+                };
+                task.record(TaskChange::Created);
+                created_ids.push(task.id);
+                store.add_task(task);
+                continue;
+            };
+
+            if original_days.get(&number) == Some(&day.date) {
+                continue;
+            }
+
+            let task_id = store
+                .get_task_by_number(number)
+                .filter(|t| t.deleted_at.is_none() && t.completed_at.is_none())
+                .map(|t| t.id);
+            match task_id.and_then(|id| store.get_task_mut(id)) {
+                Some(task) => {
+                    task.reschedule(when.clone());
+                    if !rescheduled_ids.contains(&task.id) {
+                        rescheduled_ids.push(task.id);
+                    }
+                }
+                None => unknown_numbers.push(number),
+            }
+        }
+    }
+
+    if !rescheduled_ids.is_empty() || !created_ids.is_empty() {
+        storage.save(store)?;
+    }
+
+    let tasks = |ids: Vec<Uuid>| -> Vec<Task> {
+        ids.into_iter()
+            .filter_map(|id| store.get_task(id).cloned())
+            .collect()
+    };
+    Ok(ApplyWeekPlanResult {
+        rescheduled: tasks(rescheduled_ids),
+        created: tasks(created_ids),
+        unknown_numbers,
+    })
+}
@@ -1,6 +1,14 @@
 use crate::{
-    models::{project::Project, store::Store},
+    models::{
+        project::Project,
+        store::Store,
+        task::{Task, TaskChange, When, parse_deadline},
+    },
+    services::tasks::{
+        NameLookupError, copy_task, find_by_slug_or_name, find_project_id, find_task,
+    },
     storage::{Storage, StorageError},
+    ui::normalize_color,
 };
 use slug::slugify;
 use thiserror::Error;
@@ -11,8 +19,14 @@ pub enum CreateProjectError {
     #[error("Area with name '{}' not found", .0)]
     AreaNotFound(String),
 
-    #[error("Project with name '{}' already exists", .0)]
-    ProjectAlreadyExists(String),
+    #[error("Slug '{0}' is already used by another project")]
+    SlugTaken(String),
+
+    #[error("Slug '{0}' has no letters or digits")]
+    InvalidSlug(String),
+
+    #[error("Invalid deadline date '{0}': {1}")]
+    InvalidDeadline(String, String),
 
     #[error("Storage error: {0}")]
     Storage(#[from] StorageError),
@@ -21,43 +35,737 @@ pub enum CreateProjectError {
 pub struct CreateProjectParameters {
     pub name: String,
     pub area: Option<String>,
+    /// A date, or an offset like "+2w" (see `parse_deadline`)
+    pub deadline: Option<String>,
+    /// Slug to use instead of one derived from the name
+    pub slug: Option<String>,
+    /// Titles of tasks to create in the project, in order
+    pub tasks: Vec<String>,
+}
+
+pub struct CreateProjectResult {
+    pub project: Project,
+    pub tasks: Vec<Task>,
 }
 
 pub fn create_project(
     store: &mut Store,
     storage: &impl Storage,
     parameters: CreateProjectParameters,
-) -> Result<Project, CreateProjectError> {
-    let project_slug = slugify(&parameters.name);
+) -> Result<CreateProjectResult, CreateProjectError> {
+    // A derived slug is numbered when taken; a chosen one must be free
+    let project_slug = match parameters.slug {
+        Some(requested) => {
+            let slug = slugify(&requested);
+            if slug.is_empty() {
+                return Err(CreateProjectError::InvalidSlug(requested));
+            }
+            if store.projects.values().any(|p| p.slug == slug) {
+                return Err(CreateProjectError::SlugTaken(slug));
+            }
+            slug
+        }
+        None => store.unique_project_slug(&parameters.name, None),
+    };
 
     let area_id = match parameters.area {
         Some(area_slug) => Some(
             store
                 .get_area_by_slug(&area_slug)
+                .filter(|a| a.deleted_at.is_none())
                 .ok_or(CreateProjectError::AreaNotFound(area_slug))?
                 .id,
         ),
         None => None,
     };
 
+    let deadline = match parameters.deadline {
+        Some(deadline_str) => Some(
+            parse_deadline(&deadline_str, jiff::Zoned::now().date())
+                .map_err(|e| CreateProjectError::InvalidDeadline(deadline_str.clone(), e))?,
+        ),
+        None => None,
+    };
+
     let project = Project {
         id: Uuid::new_v4(),
         name: parameters.name,
         slug: project_slug,
         created_at: jiff::Timestamp::now(),
         area_id,
+        deadline,
         ..Project::default()
     };
 
     let project_id = project.id;
+    let now = project.created_at;
 
     store.add_project(project);
 
+    // The tasks are planned already, so they skip the Inbox. Everything is
+    // saved at once: the project never exists without its tasks.
+    let mut task_ids = vec![];
+    for title in parameters.tasks {
+        let title = title.trim();
+        if title.is_empty() {
+            continue;
+        }
+        let mut task = Task {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            project_id: Some(project_id),
+            when: When::Anytime,
+            created_at: now,
+            ..Task::default()
+        };
+        task.record(TaskChange::Created);
+        task_ids.push(task.id);
+        store.add_task(task);
+    }
+
+    storage.save(store)?;
+
+    Ok(CreateProjectResult {
+        project: store.get_project(project_id).unwrap().clone(),
+        tasks: task_ids
+            .iter()
+            .filter_map(|id| store.get_task(*id))
+            .cloned()
+            .collect(),
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum EditProjectError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Invalid deadline date '{0}': {1}")]
+    InvalidDeadline(String, String),
+
+    #[error("Unknown color '{0}'")]
+    InvalidColor(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct EditProjectParameters {
+    pub slug: String,
+    pub add_tags: Vec<String>,
+    pub remove_tags: Vec<String>,
+    /// New deadline: a date, or an offset like "+2w"
+    pub deadline: Option<String>,
+    /// Remove the deadline
+    pub clear_deadline: bool,
+    /// Color name, e.g. "blue" (see `ui::normalize_color`)
+    pub color: Option<String>,
+    pub clear_color: bool,
+    /// Emoji or symbol shown before the name
+    pub icon: Option<String>,
+    pub clear_icon: bool,
+}
+
+pub fn edit_project(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: EditProjectParameters,
+) -> Result<Project, EditProjectError> {
+    let project_id = store
+        .get_active_projects()
+        .find(|p| p.slug.to_lowercase() == parameters.slug.to_lowercase())
+        .ok_or(EditProjectError::ProjectNotFound(parameters.slug))?
+        .id;
+
+    let deadline = match parameters.deadline {
+        Some(deadline_str) => Some(
+            parse_deadline(&deadline_str, jiff::Zoned::now().date())
+                .map_err(|e| EditProjectError::InvalidDeadline(deadline_str.clone(), e))?,
+        ),
+        None => None,
+    };
+
+    let color = match parameters.color {
+        Some(color) => Some(normalize_color(&color).ok_or(EditProjectError::InvalidColor(color))?),
+        None => None,
+    };
+
+    if let Some(project) = store.get_project_mut(project_id) {
+        if deadline.is_some() {
+            project.deadline = deadline;
+        } else if parameters.clear_deadline {
+            project.deadline = None;
+        }
+
+        if color.is_some() {
+            project.color = color;
+        } else if parameters.clear_color {
+            project.color = None;
+        }
+
+        if let Some(icon) = parameters.icon.filter(|i| !i.trim().is_empty()) {
+            project.icon = Some(icon.trim().to_string());
+        } else if parameters.clear_icon {
+            project.icon = None;
+        }
+
+        // Tags are matched case-insensitively, like task tags in `tag view`
+        project.tags.retain(|tag| {
+            !parameters
+                .remove_tags
+                .iter()
+                .any(|r| r.to_lowercase() == tag.to_lowercase())
+        });
+
+        for tag in parameters.add_tags {
+            if !project
+                .tags
+                .iter()
+                .any(|t| t.to_lowercase() == tag.to_lowercase())
+            {
+                project.tags.push(tag);
+            }
+        }
+    }
+
+    storage.save(store)?;
+
+    Ok(store.get_project(project_id).unwrap().clone())
+}
+
+#[derive(Debug, Error)]
+pub enum SetProjectNotesError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct SetProjectNotesParameters {
+    pub slug: String,
+    /// New notes; `None` or blank text removes them
+    pub notes: Option<String>,
+}
+
+pub fn set_project_notes(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: SetProjectNotesParameters,
+) -> Result<Project, SetProjectNotesError> {
+    let project_id = find_project_id_by_slug(store, &parameters.slug)
+        .ok_or(SetProjectNotesError::ProjectNotFound(parameters.slug))?;
+
+    let notes = parameters
+        .notes
+        .map(|n| n.trim_end().to_string())
+        .filter(|n| !n.trim().is_empty());
+    if let Some(project) = store.get_project_mut(project_id) {
+        project.notes = notes;
+    }
+
+    storage.save(store)?;
+
+    Ok(store.get_project(project_id).unwrap().clone())
+}
+
+#[derive(Debug, Error)]
+pub enum RenameProjectError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Project name is ambiguous. Multiple projects found: {}", .0.join(", "))]
+    AmbiguousProjectName(Vec<String>),
+
+    #[error("The new name is empty")]
+    EmptyName,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct RenameProjectParameters {
+    /// Slug, or part of the current name
+    pub project: String,
+    pub new_name: String,
+}
+
+pub struct RenameProjectResult {
+    pub previous_name: String,
+    pub project: Project,
+}
+
+/// Give a project a new name and a slug to match. Tasks point at the project
+/// by id, so they follow it without changes.
+pub fn rename_project(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: RenameProjectParameters,
+) -> Result<RenameProjectResult, RenameProjectError> {
+    let new_name = parameters.new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err(RenameProjectError::EmptyName);
+    }
+
+    let project_id = find_project_id(store, &parameters.project).map_err(|e| match e {
+        NameLookupError::NotFound => RenameProjectError::ProjectNotFound(parameters.project),
+        NameLookupError::Ambiguous(names) => RenameProjectError::AmbiguousProjectName(names),
+    })?;
+
+    let slug = store.unique_project_slug(&new_name, Some(project_id));
+    let project = store
+        .get_project_mut(project_id)
+        .expect("project was just found");
+    let previous_name = std::mem::replace(&mut project.name, new_name);
+    project.slug = slug;
+
+    storage.save(store)?;
+
+    Ok(RenameProjectResult {
+        previous_name,
+        project: store.get_project(project_id).unwrap().clone(),
+    })
+}
+
+/// Find an active project by slug, ignoring case
+fn find_project_id_by_slug(store: &Store, slug: &str) -> Option<Uuid> {
+    store
+        .get_active_projects()
+        .find(|p| p.slug.to_lowercase() == slug.to_lowercase())
+        .map(|p| p.id)
+}
+
+#[derive(Debug, Error)]
+pub enum ArchiveProjectError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Project name is ambiguous. Multiple projects found: {}", .0.join(", "))]
+    AmbiguousProjectName(Vec<String>),
+
+    #[error("Project '{0}' is already archived")]
+    AlreadyArchived(String),
+
+    #[error("Project '{0}' is not archived")]
+    NotArchived(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct ArchiveProjectParameters {
+    /// Slug, or part of the name
+    pub project: String,
+    /// Bring an archived project back instead
+    pub unarchive: bool,
+}
+
+/// Put a project away without finishing or trashing it, or bring it back.
+/// An archived project is left out of the lists and its tasks out of the
+/// working views; unlike deletion, nothing ends up in the trash.
+pub fn archive_project(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: ArchiveProjectParameters,
+) -> Result<Project, ArchiveProjectError> {
+    let project_id = find_project_id(store, &parameters.project).map_err(|e| match e {
+        NameLookupError::NotFound => ArchiveProjectError::ProjectNotFound(parameters.project),
+        NameLookupError::Ambiguous(names) => ArchiveProjectError::AmbiguousProjectName(names),
+    })?;
+    let project = store.get_project_mut(project_id).unwrap();
+
+    match (parameters.unarchive, project.archived_at) {
+        (false, Some(_)) => {
+            return Err(ArchiveProjectError::AlreadyArchived(project.name.clone()));
+        }
+        (true, None) => return Err(ArchiveProjectError::NotArchived(project.name.clone())),
+        (false, None) => project.archived_at = Some(jiff::Timestamp::now()),
+        (true, Some(_)) => project.archived_at = None,
+    }
+
     storage.save(store)?;
 
     Ok(store.get_project(project_id).unwrap().clone())
 }
 
+#[derive(Debug, Error)]
+pub enum DuplicateProjectError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("The new name is empty")]
+    EmptyName,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct DuplicateProjectParameters {
+    pub slug: String,
+    /// Name of the copy
+    pub name: String,
+}
+
+pub struct DuplicateProjectResult {
+    pub project: Project,
+    pub tasks_count: usize,
+}
+
+/// Copy a project's name, area, notes and tags into a new project, with a copy
+/// of each of its tasks that isn't trashed. Like `duplicate_task`, the copies
+/// start open and unchecked and leave dates behind; a schedule that has
+/// already passed becomes Anytime. The next action carries over to its copy.
+pub fn duplicate_project(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: DuplicateProjectParameters,
+) -> Result<DuplicateProjectResult, DuplicateProjectError> {
+    let name = parameters.name.trim().to_string();
+    if name.is_empty() {
+        return Err(DuplicateProjectError::EmptyName);
+    }
+
+    let original_id = find_project_id_by_slug(store, &parameters.slug)
+        .ok_or(DuplicateProjectError::ProjectNotFound(parameters.slug))?;
+    let original = store.get_project(original_id).unwrap().clone();
+
+    let project = Project {
+        id: Uuid::new_v4(),
+        slug: store.unique_project_slug(&name, None),
+        name,
+        area_id: original.area_id,
+        notes: original.notes.clone(),
+        tags: original.tags.clone(),
+        created_at: jiff::Timestamp::now(),
+        ..Project::default()
+    };
+    let project_id = project.id;
+
+    let mut originals: Vec<_> = store
+        .get_tasks_for_project(original_id)
+        .filter(|t| t.deleted_at.is_none())
+        .cloned()
+        .collect();
+    originals.sort_by_key(|t| t.task_number);
+
+    let today = jiff::Zoned::now().date();
+    let mut next_action = None;
+    let mut copies = vec![];
+    for task in &originals {
+        let when = match &task.when {
+            When::Scheduled { date, .. } if *date < today => When::Anytime,
+            when => when.clone(),
+        };
+        let mut copy = copy_task(task, when);
+        copy.project_id = Some(project_id);
+        if original.next_action == Some(task.id) {
+            next_action = Some(copy.id);
+        }
+        copies.push(copy);
+    }
+
+    let tasks_count = copies.len();
+    store.add_project(Project {
+        next_action,
+        ..project
+    });
+    for copy in copies {
+        store.add_task(copy);
+    }
+
+    storage.save(store)?;
+
+    Ok(DuplicateProjectResult {
+        project: store.get_project(project_id).unwrap().clone(),
+        tasks_count,
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum OrderProjectError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("A project can't be placed next to itself")]
+    SameProject,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Where to place a project relative to another one
+pub enum ProjectPlacement {
+    Before(String),
+    After(String),
+}
+
+pub struct OrderProjectParameters {
+    pub slug: String,
+    pub placement: ProjectPlacement,
+}
+
+/// Move a project right before or after another one. Every active project is
+/// renumbered in its current display order, so projects that had no position
+/// keep their place among the rest.
+pub fn order_project(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: OrderProjectParameters,
+) -> Result<Vec<Project>, OrderProjectError> {
+    let project_id = find_project_id_by_slug(store, &parameters.slug)
+        .ok_or(OrderProjectError::ProjectNotFound(parameters.slug))?;
+    let (other_slug, after) = match parameters.placement {
+        ProjectPlacement::Before(slug) => (slug, false),
+        ProjectPlacement::After(slug) => (slug, true),
+    };
+    let other_id = find_project_id_by_slug(store, &other_slug)
+        .ok_or(OrderProjectError::ProjectNotFound(other_slug))?;
+    if project_id == other_id {
+        return Err(OrderProjectError::SameProject);
+    }
+
+    let mut projects: Vec<_> = store.get_active_projects().collect();
+    projects.sort_by_key(|p| p.display_order());
+    let mut ids: Vec<Uuid> = projects
+        .iter()
+        .map(|p| p.id)
+        .filter(|id| *id != project_id)
+        .collect();
+    let other_index = ids.iter().position(|id| *id == other_id).unwrap();
+    ids.insert(
+        if after { other_index + 1 } else { other_index },
+        project_id,
+    );
+
+    for (index, id) in ids.iter().enumerate() {
+        if let Some(project) = store.get_project_mut(*id) {
+            project.sort_order = Some(index as u32);
+        }
+    }
+
+    storage.save(store)?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| store.get_project(*id))
+        .cloned()
+        .collect())
+}
+
+#[derive(Debug, Error)]
+pub enum BlockProjectError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("A project can't wait on itself")]
+    SameProject,
+
+    #[error("Project '{1}' already waits on '{0}'")]
+    Cycle(String, String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct BlockProjectParameters {
+    /// Project that has to wait
+    pub slug: String,
+    /// Project that has to be completed first
+    pub on_slug: String,
+}
+
+/// Make a project wait until another one is completed. Its tasks stay out of
+/// Today and Anytime until then. Returns the blocked and the blocking project.
+pub fn block_project(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: BlockProjectParameters,
+) -> Result<(Project, Project), BlockProjectError> {
+    let project_id = find_project_id_by_slug(store, &parameters.slug)
+        .ok_or(BlockProjectError::ProjectNotFound(parameters.slug))?;
+    let on_id = find_project_id_by_slug(store, &parameters.on_slug)
+        .ok_or(BlockProjectError::ProjectNotFound(parameters.on_slug))?;
+
+    if project_id == on_id {
+        return Err(BlockProjectError::SameProject);
+    }
+    if store.project_depends_on(on_id, project_id) {
+        let name = |id| {
+            store
+                .get_project(id)
+                .map(|p| p.name.clone())
+                .unwrap_or_default()
+        };
+        return Err(BlockProjectError::Cycle(name(project_id), name(on_id)));
+    }
+
+    if let Some(project) = store.get_project_mut(project_id)
+        && !project.blocked_by.contains(&on_id)
+    {
+        project.blocked_by.push(on_id);
+    }
+
+    storage.save(store)?;
+
+    Ok((
+        store.get_project(project_id).unwrap().clone(),
+        store.get_project(on_id).unwrap().clone(),
+    ))
+}
+
+#[derive(Debug, Error)]
+pub enum UnblockProjectError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Project '{0}' isn't waiting on '{1}'")]
+    NotBlockedOn(String, String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct UnblockProjectParameters {
+    pub slug: String,
+    /// Only stop waiting on this project; all of them when `None`
+    pub on_slug: Option<String>,
+}
+
+pub fn unblock_project(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: UnblockProjectParameters,
+) -> Result<Project, UnblockProjectError> {
+    let project_id = find_project_id_by_slug(store, &parameters.slug)
+        .ok_or(UnblockProjectError::ProjectNotFound(parameters.slug))?;
+
+    let on_id = match parameters.on_slug {
+        Some(on_slug) => Some(
+            find_project_id_by_slug(store, &on_slug)
+                .ok_or(UnblockProjectError::ProjectNotFound(on_slug))?,
+        ),
+        None => None,
+    };
+
+    let project = store
+        .get_project_mut(project_id)
+        .expect("project was just found");
+    match on_id {
+        Some(on_id) if !project.blocked_by.contains(&on_id) => {
+            let name = project.name.clone();
+            let on_name = store.get_project(on_id).map(|p| p.name.clone());
+            return Err(UnblockProjectError::NotBlockedOn(
+                name,
+                on_name.unwrap_or_default(),
+            ));
+        }
+        Some(on_id) => project.blocked_by.retain(|id| *id != on_id),
+        None => project.blocked_by.clear(),
+    }
+
+    storage.save(store)?;
+
+    Ok(store.get_project(project_id).unwrap().clone())
+}
+
+#[derive(Debug, Error)]
+pub enum IncubateProjectError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Project '{0}' is already in the incubator")]
+    AlreadyIncubated(String),
+
+    #[error("Project '{0}' is not in the incubator")]
+    NotIncubated(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct IncubateProjectParameters {
+    pub slug: String,
+    /// Take the project back out of the incubator instead
+    pub resume: bool,
+}
+
+/// Shelve a project for later, or resume a shelved one. While incubated its
+/// tasks stay out of Today, Anytime and the project counts.
+pub fn incubate_project(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: IncubateProjectParameters,
+) -> Result<Project, IncubateProjectError> {
+    let project_id = find_project_id_by_slug(store, &parameters.slug)
+        .ok_or(IncubateProjectError::ProjectNotFound(parameters.slug))?;
+    let project = store.get_project_mut(project_id).unwrap();
+
+    match (parameters.resume, project.incubated_at) {
+        (false, Some(_)) => {
+            return Err(IncubateProjectError::AlreadyIncubated(project.name.clone()));
+        }
+        (true, None) => return Err(IncubateProjectError::NotIncubated(project.name.clone())),
+        (false, None) => project.incubated_at = Some(jiff::Timestamp::now()),
+        (true, Some(_)) => project.incubated_at = None,
+    }
+
+    storage.save(store)?;
+
+    Ok(store.get_project(project_id).unwrap().clone())
+}
+
+#[derive(Debug, Error)]
+pub enum SetNextActionError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Task '{0}' is not in a project")]
+    NotInProject(String),
+
+    #[error("Task '{0}' is already completed")]
+    TaskCompleted(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct SetNextActionParameters {
+    pub task_number_or_fuzzy_name: String,
+}
+
+/// Choose a task as its project's next action instead of the oldest open task
+pub fn set_next_action(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: SetNextActionParameters,
+) -> Result<(Project, Task), SetNextActionError> {
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            SetNextActionError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => SetNextActionError::AmbiguousTaskName(titles),
+    })?;
+
+    if task.completed_at.is_some() {
+        return Err(SetNextActionError::TaskCompleted(task.title.clone()));
+    }
+    let task = task.clone();
+
+    let project = task
+        .project_id
+        .and_then(|id| store.get_project_mut(id))
+        .ok_or_else(|| SetNextActionError::NotInProject(task.title.clone()))?;
+    project.next_action = Some(task.id);
+    let project = project.clone();
+
+    storage.save(store)?;
+
+    Ok((project, task))
+}
+
 #[derive(Debug, Error)]
 pub enum DeleteProjectError {
     #[error("Project '{0}' not found")]
@@ -87,26 +795,20 @@ pub fn delete_project(
     storage: &impl Storage,
     parameters: DeleteProjectParameters,
 ) -> Result<DeleteProjectResult, DeleteProjectError> {
-    // Fuzzy match to find project
-    let matching_projects: Vec<_> = store
-        .get_active_projects()
-        .filter(|p| {
-            p.name
-                .to_lowercase()
-                .contains(&parameters.name.to_lowercase())
-        })
-        .collect();
-
-    let project = match matching_projects.len() {
-        0 => return Err(DeleteProjectError::ProjectNotFound(parameters.name)),
-        1 => matching_projects[0],
-        _ => {
-            let names: Vec<String> = matching_projects.iter().map(|p| p.name.clone()).collect();
-            return Err(DeleteProjectError::AmbiguousProjectName(names));
+    let project_id = find_project_id(store, &parameters.name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            // Point out when the project is already in the trash
+            match find_by_slug_or_name(
+                store.get_deleted_projects(),
+                |p| (&p.slug, &p.name),
+                &parameters.name,
+            ) {
+                Ok(project) => DeleteProjectError::ProjectAlreadyDeleted(project.name.clone()),
+                Err(_) => DeleteProjectError::ProjectNotFound(parameters.name.clone()),
+            }
         }
-    };
-
-    let project_id = project.id;
+        NameLookupError::Ambiguous(names) => DeleteProjectError::AmbiguousProjectName(names),
+    })?;
     let now = jiff::Timestamp::now();
 
     // Cascade delete: Find all tasks in this project and mark them deleted
@@ -121,6 +823,7 @@ pub fn delete_project(
     for task_id in task_ids_to_delete {
         if let Some(task) = store.get_task_mut(task_id) {
             task.deleted_at = Some(now);
+            task.record(TaskChange::Deleted);
         }
     }
 
@@ -146,38 +849,70 @@ pub enum RestoreProjectError {
     #[error("Project '{0}' is not deleted")]
     ProjectNotDeleted(String),
 
+    #[error("Project name is ambiguous. Multiple deleted projects found: {}", .0.join(", "))]
+    AmbiguousProjectName(Vec<String>),
+
     #[error("Storage error: {0}")]
     Storage(#[from] StorageError),
 }
 
 pub struct RestoreProjectParameters {
     pub name: String,
+    /// Also restore the tasks trashed along with the project
+    pub with_tasks: bool,
+}
+
+pub struct RestoreProjectResult {
+    pub project: Project,
+    pub restored_tasks_count: usize,
 }
 
 pub fn restore_project(
     store: &mut Store,
     storage: &impl Storage,
     parameters: RestoreProjectParameters,
-) -> Result<Project, RestoreProjectError> {
-    // Find deleted project by name
-    let matching_projects: Vec<_> = store
-        .get_deleted_projects()
-        .filter(|p| {
-            p.name
-                .to_lowercase()
-                .contains(&parameters.name.to_lowercase())
-        })
-        .collect();
+) -> Result<RestoreProjectResult, RestoreProjectError> {
+    // Find deleted project by slug or name
+    let project = find_by_slug_or_name(
+        store.get_deleted_projects(),
+        |p| (&p.slug, &p.name),
+        &parameters.name,
+    )
+    .map_err(|e| match e {
+        NameLookupError::NotFound => {
+            // Point out when the project exists but was never deleted
+            match find_project_id(store, &parameters.name) {
+                Ok(id) => RestoreProjectError::ProjectNotDeleted(
+                    store.get_project(id).unwrap().name.clone(),
+                ),
+                Err(_) => RestoreProjectError::ProjectNotFound(parameters.name.clone()),
+            }
+        }
+        NameLookupError::Ambiguous(names) => RestoreProjectError::AmbiguousProjectName(names),
+    })?;
 
-    let project = match matching_projects.len() {
-        0 => return Err(RestoreProjectError::ProjectNotFound(parameters.name)),
-        1 => matching_projects[0],
-        _ => return Err(RestoreProjectError::ProjectNotFound(parameters.name)),
+    let project_id = project.id;
+    let deleted_at = project.deleted_at;
+
+    // Tasks cascade-deleted with the project share its deletion timestamp;
+    // ones trashed on their own before keep theirs and stay in the trash
+    let task_ids_to_restore: Vec<Uuid> = if parameters.with_tasks {
+        store
+            .get_tasks_for_project(project_id)
+            .filter(|t| t.deleted_at.is_some() && t.deleted_at == deleted_at)
+            .map(|t| t.id)
+            .collect()
+    } else {
+        vec![]
     };
 
-    let project_id = project.id;
+    for task_id in &task_ids_to_restore {
+        if let Some(task) = store.get_task_mut(*task_id) {
+            task.deleted_at = None;
+            task.record(TaskChange::Restored);
+        }
+    }
 
-    // Restore project (does NOT auto-restore tasks - user must restore them separately)
     if let Some(project) = store.get_project_mut(project_id) {
         project.deleted_at = None;
     }
@@ -185,5 +920,109 @@ pub fn restore_project(
     // Persist to storage
     storage.save(store)?;
 
-    Ok(store.get_project(project_id).unwrap().clone())
+    Ok(RestoreProjectResult {
+        project: store.get_project(project_id).unwrap().clone(),
+        restored_tasks_count: task_ids_to_restore.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+    use crate::storage::json::JsonFileStorage;
+
+    fn test_storage(name: &str) -> JsonFileStorage {
+        let test_dir = PathBuf::from(format!("/tmp/tdo_{}_test", name));
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+        JsonFileStorage::new(test_dir.join("store.json"))
+    }
+
+    fn new_project(
+        store: &mut Store,
+        storage: &impl Storage,
+        name: &str,
+        tasks: &[&str],
+    ) -> Project {
+        let parameters = CreateProjectParameters {
+            name: name.to_string(),
+            area: None,
+            deadline: None,
+            slug: None,
+            tasks: tasks.iter().map(|t| t.to_string()).collect(),
+        };
+        create_project(store, storage, parameters).unwrap().project
+    }
+
+    #[test]
+    fn test_duplicate_leaves_the_original_name_resolvable() {
+        let storage = test_storage("duplicate_project");
+        let mut store = Store::default();
+        let original = new_project(&mut store, &storage, "Work", &["Plan sprint"]);
+
+        let copy = duplicate_project(
+            &mut store,
+            &storage,
+            DuplicateProjectParameters {
+                slug: "work".to_string(),
+                name: "Work Copy".to_string(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(copy.tasks_count, 1);
+        // "Work" is also part of the copy's name, but the exact name wins
+        assert_eq!(find_project_id(&store, "Work").ok(), Some(original.id));
+        assert_eq!(
+            find_project_id(&store, "work copy").ok(),
+            Some(copy.project.id)
+        );
+    }
+
+    #[test]
+    fn test_restore_project_brings_back_the_tasks_deleted_with_it() {
+        let storage = test_storage("restore_project");
+        let mut store = Store::default();
+        let project = new_project(&mut store, &storage, "Launch", &["Write copy", "Old draft"]);
+
+        // Trash one task on its own first, with an earlier timestamp
+        let old_draft = store
+            .get_tasks_for_project(project.id)
+            .find(|t| t.title == "Old draft")
+            .unwrap()
+            .id;
+        store.get_task_mut(old_draft).unwrap().deleted_at = Some(jiff::Timestamp::UNIX_EPOCH);
+
+        let deleted = delete_project(
+            &mut store,
+            &storage,
+            DeleteProjectParameters {
+                name: "launch".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(deleted.cascaded_tasks_count, 1);
+
+        let restored = restore_project(
+            &mut store,
+            &storage,
+            RestoreProjectParameters {
+                name: "launch".to_string(),
+                with_tasks: true,
+            },
+        )
+        .unwrap();
+
+        assert!(restored.project.deleted_at.is_none());
+        assert_eq!(restored.restored_tasks_count, 1);
+        let active: Vec<_> = store
+            .get_tasks_for_project(project.id)
+            .filter(|t| t.deleted_at.is_none())
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(active, ["Write copy"]);
+        assert!(store.get_task(old_draft).unwrap().deleted_at.is_some());
+    }
 }
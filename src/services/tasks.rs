@@ -1,15 +1,110 @@
-use jiff::civil::Date;
+use jiff::{SignedDuration, civil::Date};
 use thiserror::Error;
 use uuid::Uuid;
 
 use crate::{
     models::{
+        area::Area,
+        project::Project,
         store::Store,
-        task::{Task, When},
+        task::{
+            Attachment, ChecklistItem, Energy, Task, TaskChange, TaskEvent, TimeEntry, When,
+            normalize_context, parse_deadline, parse_estimate, parse_reminder, parse_weekday,
+        },
     },
     storage::{Storage, StorageError},
 };
 
+/// Why a fuzzy name didn't resolve to exactly one entity
+pub(crate) enum NameLookupError {
+    NotFound,
+    Ambiguous(Vec<String>),
+}
+
+/// Pick the candidate a query names, ignoring case: an exact slug wins, then
+/// an exact name, then a name containing the query. Ambiguous candidates are
+/// reported as "Name (slug)" so they can be told apart.
+pub(crate) fn find_by_slug_or_name<'a, T>(
+    candidates: impl Iterator<Item = &'a T>,
+    slug_and_name: impl Fn(&T) -> (&str, &str),
+    query: &str,
+) -> Result<&'a T, NameLookupError> {
+    let query = query.to_lowercase();
+    let rank = |candidate: &T| {
+        let (slug, name) = slug_and_name(candidate);
+        let name = name.to_lowercase();
+        if slug.to_lowercase() == query {
+            Some(0)
+        } else if name == query {
+            Some(1)
+        } else if name.contains(&query) {
+            Some(2)
+        } else {
+            None
+        }
+    };
+
+    let ranked: Vec<_> = candidates.filter_map(|c| rank(c).map(|r| (r, c))).collect();
+    let Some(best) = ranked.iter().map(|(r, _)| *r).min() else {
+        return Err(NameLookupError::NotFound);
+    };
+    let matching: Vec<_> = ranked
+        .into_iter()
+        .filter(|(r, _)| *r == best)
+        .map(|(_, c)| c)
+        .collect();
+
+    match matching.len() {
+        1 => Ok(matching[0]),
+        _ => Err(NameLookupError::Ambiguous(
+            matching
+                .iter()
+                .map(|c| {
+                    let (slug, name) = slug_and_name(c);
+                    format!("{} ({})", name, slug)
+                })
+                .collect(),
+        )),
+    }
+}
+
+/// Resolve a project slug or name among active projects
+pub(crate) fn find_project_id(store: &Store, query: &str) -> Result<Uuid, NameLookupError> {
+    find_by_slug_or_name(store.get_active_projects(), |p| (&p.slug, &p.name), query).map(|p| p.id)
+}
+
+/// Resolve an area slug or name among active areas
+pub(crate) fn find_area_id(store: &Store, query: &str) -> Result<Uuid, NameLookupError> {
+    find_by_slug_or_name(store.get_active_areas(), |a| (&a.slug, &a.name), query).map(|a| a.id)
+}
+
+/// Resolve a task by its number, or by a fuzzy title match among active incomplete tasks
+pub(crate) fn find_task<'a>(
+    store: &'a Store,
+    identifier: &str,
+) -> Result<&'a Task, NameLookupError> {
+    if let Ok(task_number) = identifier.parse::<u64>() {
+        return store
+            .get_task_by_number(task_number)
+            .filter(|t| t.deleted_at.is_none())
+            .ok_or(NameLookupError::NotFound);
+    }
+
+    let matching_tasks: Vec<_> = store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none())
+        .filter(|t| t.title.to_lowercase().contains(&identifier.to_lowercase()))
+        .collect();
+
+    match matching_tasks.len() {
+        0 => Err(NameLookupError::NotFound),
+        1 => Ok(matching_tasks[0]),
+        _ => Err(NameLookupError::Ambiguous(
+            matching_tasks.iter().map(|t| t.title.clone()).collect(),
+        )),
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum AddTaskError {
     #[error("Project '{0}' not found")]
@@ -24,13 +119,32 @@ pub enum AddTaskError {
     #[error("Area name is ambiguous. Multiple areas found: {}", .0.join(", "))]
     AmbiguousAreaName(Vec<String>),
 
+    #[error("A task in a project takes its area from the project; use an area override instead")]
+    AreaWithProject,
+
+    #[error("An area override requires a project")]
+    AreaOverrideWithoutProject,
+
     #[error("Invalid deadline date '{0}': {1}")]
     InvalidDeadline(String, String),
 
+    #[error("Invalid defer date '{0}': {1}")]
+    InvalidDeferDate(String, String),
+
+    #[error("Invalid reminder '{0}'")]
+    InvalidReminder(String),
+
+    #[error("Invalid estimate '{0}'")]
+    InvalidEstimate(String),
+
+    #[error("Task #{0} was already added with this id or idempotency key, with a different {fields}", fields = .1.join(", "))]
+    IdempotencyMismatch(u64, Vec<&'static str>),
+
     #[error("Storage error: {0}")]
     Storage(#[from] StorageError),
 }
 
+#[derive(Clone)]
 pub struct AddTaskParameters {
     pub title: String,
     pub notes: Option<String>,
@@ -38,125 +152,1126 @@ pub struct AddTaskParameters {
     pub deadline: Option<String>,
     pub project: Option<String>,
     pub area: Option<String>,
+    /// Area to surface a project task under instead of the project's own area
+    pub area_override: Option<String>,
+    pub tags: Vec<String>,
+    pub context: Option<String>,
+    pub energy: Option<Energy>,
+    /// Reminder in `parse_reminder` format, e.g. "today 17:00"
+    pub reminder: Option<String>,
+    /// Hide the task from Today and Anytime until this date
+    pub defer_until: Option<String>,
+    /// Expected duration in `parse_estimate` format, e.g. "30m"
+    pub estimate: Option<String>,
+    /// Create the project when no project matches its name
+    pub create_project: bool,
+    /// Create the area (or area override) when no area matches its name
+    pub create_area: bool,
+    /// Id for the new task instead of a random one
+    pub id: Option<Uuid>,
+    /// Key to recognize a retry of this add
+    pub idempotency_key: Option<String>,
+}
+
+/// Outcome of `add_task`
+pub enum AddedTask {
+    Created(Task),
+    /// A task with the requested id or idempotency key was already in the
+    /// store (possibly completed or trashed), so nothing was added
+    Existing(Task),
+}
+
+impl AddedTask {
+    pub fn task(&self) -> &Task {
+        match self {
+            AddedTask::Created(task) | AddedTask::Existing(task) => task,
+        }
+    }
+}
+
+/// Task added earlier with the same id or idempotency key
+fn find_existing_task<'a>(store: &'a Store, parameters: &AddTaskParameters) -> Option<&'a Task> {
+    let by_id = parameters.id.and_then(|id| store.get_task(id));
+    by_id.or_else(|| {
+        let key = parameters.idempotency_key.as_deref()?;
+        store
+            .tasks
+            .values()
+            .find(|t| t.idempotency_key.as_deref() == Some(key))
+    })
+}
+
+/// Fields a retried add sets differently from the task it already created.
+/// Dates are left out, since relative ones like "+1w" move from day to day.
+fn mismatched_fields(existing: &Task, retried: &Task) -> Vec<&'static str> {
+    [
+        ("title", existing.title != retried.title),
+        ("notes", existing.notes != retried.notes),
+        ("project", existing.project_id != retried.project_id),
+        ("area", existing.area_id != retried.area_id),
+        ("tags", existing.tags != retried.tags),
+        ("context", existing.context != retried.context),
+        ("energy", existing.energy != retried.energy),
+        ("estimate", existing.estimate != retried.estimate),
+    ]
+    .into_iter()
+    .filter(|(_, differs)| *differs)
+    .map(|(field, _)| field)
+    .collect()
+}
+
+/// Containers created on the fly for an add, kept to undo them if the add fails
+#[derive(Default)]
+struct CreatedContainers {
+    project_id: Option<Uuid>,
+    area_id: Option<Uuid>,
+}
+
+/// Create the project and area named in `parameters` that don't exist yet,
+/// when asked to. Names that match an existing container are left for
+/// `build_task` to resolve, so ambiguous names are still reported.
+fn create_missing_containers(
+    store: &mut Store,
+    parameters: &AddTaskParameters,
+) -> CreatedContainers {
+    let mut created = CreatedContainers::default();
+    let now = jiff::Timestamp::now();
+
+    if parameters.create_project
+        && let Some(name) = &parameters.project
+        && matches!(find_project_id(store, name), Err(NameLookupError::NotFound))
+    {
+        let project = Project {
+            id: Uuid::new_v4(),
+            name: name.clone(),
+            slug: store.unique_project_slug(name, None),
+            created_at: now,
+            ..Project::default()
+        };
+        created.project_id = Some(project.id);
+        store.add_project(project);
+    }
+
+    if parameters.create_area
+        && let Some(name) = parameters
+            .area
+            .as_ref()
+            .or(parameters.area_override.as_ref())
+        && matches!(find_area_id(store, name), Err(NameLookupError::NotFound))
+    {
+        let area = Area {
+            id: Uuid::new_v4(),
+            name: name.clone(),
+            slug: store.unique_area_slug(name, None),
+            ..Area::default()
+        };
+        created.area_id = Some(area.id);
+        store.add_area(area);
+    }
+
+    created
+}
+
+/// Undo `create_missing_containers` after a failed add
+fn discard_containers(store: &mut Store, created: CreatedContainers) {
+    if let Some(project_id) = created.project_id {
+        store.projects.remove(&project_id);
+    }
+    if let Some(area_id) = created.area_id {
+        store.areas.remove(&area_id);
+    }
+}
+
+pub fn add_task(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: AddTaskParameters,
+) -> Result<AddedTask, AddTaskError> {
+    // A retry of an add that already went through returns the original task,
+    // as long as it asks for the same task
+    if let Some(task) = find_existing_task(store, &parameters) {
+        let retried = build_task(store, parameters)?;
+        let fields = mismatched_fields(task, &retried);
+        if !fields.is_empty() {
+            return Err(AddTaskError::IdempotencyMismatch(task.task_number, fields));
+        }
+        return Ok(AddedTask::Existing(task.clone()));
+    }
+
+    let created = create_missing_containers(store, &parameters);
+    let task = match build_task(store, parameters) {
+        Ok(task) => task,
+        Err(e) => {
+            discard_containers(store, created);
+            return Err(e);
+        }
+    };
+    let task_id = task.id;
+
+    // Add to store (assigns task_number)
+    store.add_task(task);
+
+    // Persist to storage
+    storage.save(store)?;
+
+    // Return the created task (with the assigned task_number)
+    Ok(AddedTask::Created(store.get_task(task_id).unwrap().clone()))
+}
+
+/// Add several tasks with a single storage write. Every task is validated first,
+/// so either all of them are added or none is.
+pub fn add_tasks(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: Vec<AddTaskParameters>,
+) -> Result<Vec<Task>, AddTaskError> {
+    let mut created = vec![];
+    let mut tasks = vec![];
+    for p in parameters {
+        created.push(create_missing_containers(store, &p));
+        match build_task(store, p) {
+            Ok(task) => tasks.push(task),
+            Err(e) => {
+                for containers in created {
+                    discard_containers(store, containers);
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    for task in tasks {
+        store.add_task(task);
+    }
+
+    storage.save(store)?;
+
+    Ok(task_ids
+        .into_iter()
+        .filter_map(|id| store.get_task(id).cloned())
+        .collect())
+}
+
+pub struct LogTaskParameters {
+    pub task: AddTaskParameters,
+    /// When the work was done
+    pub completed_at: jiff::Timestamp,
+}
+
+/// Add a task that is already completed, so the Logbook keeps a record of work
+/// done outside the task list. It counts as created when it was done.
+pub fn log_task(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: LogTaskParameters,
+) -> Result<Task, AddTaskError> {
+    let at = parameters.completed_at;
+    let created = create_missing_containers(store, &parameters.task);
+    let mut task = match build_task(store, parameters.task) {
+        Ok(task) => task,
+        Err(e) => {
+            discard_containers(store, created);
+            return Err(e);
+        }
+    };
+    task.created_at = at;
+    task.completed_at = Some(at);
+    task.history = [TaskChange::Created, TaskChange::Completed]
+        .into_iter()
+        .map(|change| TaskEvent { at, change })
+        .collect();
+    let task_id = task.id;
+
+    store.add_task(task);
+    storage.save(store)?;
+
+    Ok(store.get_task(task_id).unwrap().clone())
+}
+
+/// Validate add parameters and build the task, without touching the store
+fn build_task(store: &Store, parameters: AddTaskParameters) -> Result<Task, AddTaskError> {
+    // 1. Validate the area flags: a project task only gets an area as an explicit override
+    if parameters.project.is_some() && parameters.area.is_some() {
+        return Err(AddTaskError::AreaWithProject);
+    }
+    if parameters.project.is_none() && parameters.area_override.is_some() {
+        return Err(AddTaskError::AreaOverrideWithoutProject);
+    }
+
+    // 2. Validate and resolve project name to project ID
+    let project_id = match parameters.project {
+        Some(project_name) => Some(find_project_id(store, &project_name).map_err(|e| match e {
+            NameLookupError::NotFound => AddTaskError::ProjectNotFound(project_name),
+            NameLookupError::Ambiguous(names) => AddTaskError::AmbiguousProjectName(names),
+        })?),
+        None => None,
+    };
+
+    // 3. Validate and resolve area name (or override) to area ID
+    let area_id = match parameters.area.or(parameters.area_override) {
+        Some(area_name) => Some(find_area_id(store, &area_name).map_err(|e| match e {
+            NameLookupError::NotFound => AddTaskError::AreaNotFound(area_name),
+            NameLookupError::Ambiguous(names) => AddTaskError::AmbiguousAreaName(names),
+        })?),
+        None => None,
+    };
+
+    // 4. Parse deadline if provided, either a date or an offset like "+1w"
+    let deadline = if let Some(deadline_str) = parameters.deadline {
+        Some(
+            parse_deadline(&deadline_str, jiff::Zoned::now().date())
+                .map_err(|e| AddTaskError::InvalidDeadline(deadline_str.clone(), e))?,
+        )
+    } else {
+        None
+    };
+
+    // 5. Parse defer date, reminder and estimate if provided
+    let defer_until = match parameters.defer_until {
+        Some(defer_str) => Some(
+            defer_str
+                .parse::<Date>()
+                .map_err(|e| AddTaskError::InvalidDeferDate(defer_str.clone(), e.to_string()))?,
+        ),
+        None => None,
+    };
+
+    let reminder = match parameters.reminder {
+        Some(reminder_str) => Some(
+            parse_reminder(&reminder_str, &jiff::Zoned::now())
+                .ok_or(AddTaskError::InvalidReminder(reminder_str))?,
+        ),
+        None => None,
+    };
+
+    let estimate = match parameters.estimate {
+        Some(estimate_str) => {
+            Some(parse_estimate(&estimate_str).ok_or(AddTaskError::InvalidEstimate(estimate_str))?)
+        }
+        None => None,
+    };
+
+    // 6. Create the task (task_number will be assigned by store.add_task)
+    let mut task = Task {
+        id: parameters.id.unwrap_or_else(Uuid::new_v4),
+        task_number: 0,
+        idempotency_key: parameters.idempotency_key,
+        title: parameters.title,
+        notes: parameters.notes,
+        project_id,
+        area_id,
+        tags: parameters.tags,
+        context: parameters
+            .context
+            .map(|c| normalize_context(&c))
+            .filter(|c| !c.is_empty()),
+        when: parameters.when,
+        deadline,
+        defer_until,
+        energy: parameters.energy,
+        snoozed_until: None,
+        reminder,
+        pinned: false,
+        estimate,
+        time_entries: vec![],
+        history: vec![],
+        checklist: vec![],
+        attachments: vec![],
+        completed_at: None,
+        deleted_at: None,
+        created_at: jiff::Timestamp::now(),
+    };
+    task.record(TaskChange::Created);
+
+    Ok(task)
+}
+
+#[derive(Debug, Error)]
+pub enum MoveTaskError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Project name is ambiguous. Multiple projects found: {}", .0.join(", "))]
+    AmbiguousProjectName(Vec<String>),
+
+    #[error("Area '{0}' not found")]
+    AreaNotFound(String),
+
+    #[error("Area name is ambiguous. Multiple areas found: {}", .0.join(", "))]
+    AmbiguousAreaName(Vec<String>),
+
+    #[error("A task in a project takes its area from the project; use an area override instead")]
+    AreaWithProject,
+
+    #[error("An area override requires a project")]
+    AreaOverrideWithoutProject,
+
+    #[error("Invalid deadline date '{0}': {1}")]
+    InvalidDeadline(String, String),
+
+    #[error("Invalid defer date '{0}': {1}")]
+    InvalidDeferDate(String, String),
+
+    #[error("Invalid estimate '{0}'")]
+    InvalidEstimate(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct MoveTaskParameters {
+    pub task_number_or_fuzzy_name: String,
+    pub changes: MoveChanges,
+}
+
+/// Changes to apply to a task. `None` / empty fields leave the task untouched.
+#[derive(Clone)]
+pub struct MoveChanges {
+    pub when: Option<When>,
+    pub deadline: Option<String>,
+    /// Move the task into this project (clears any direct area)
+    pub project: Option<String>,
+    /// Move the task directly into this area (removes it from its project)
+    pub area: Option<String>,
+    /// Surface the task's project under this area instead
+    pub area_override: Option<String>,
+    /// Tags to add to the task
     pub tags: Vec<String>,
+    /// Replace the task's notes
+    pub notes: Option<String>,
+    pub context: Option<String>,
+    pub energy: Option<Energy>,
+    /// Hide the task from Today and Anytime until this date
+    pub defer_until: Option<String>,
+    /// Expected duration in `parse_estimate` format, e.g. "30m"
+    pub estimate: Option<String>,
+}
+
+pub fn move_task(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: MoveTaskParameters,
+) -> Result<Task, MoveTaskError> {
+    // 1. Find the task
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            MoveTaskError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => MoveTaskError::AmbiguousTaskName(titles),
+    })?;
+
+    // 2. Apply the changes
+    let updated_task = apply_move(store, task, parameters.changes)?;
+
+    // 3. Update in store and persist
+    store.tasks.insert(updated_task.id, updated_task.clone());
+    storage.save(store)?;
+
+    Ok(updated_task)
+}
+
+/// `task` with `parameters` applied, recording the changes in its history.
+/// The store is only read, to resolve project and area names.
+fn apply_move(store: &Store, task: &Task, parameters: MoveChanges) -> Result<Task, MoveTaskError> {
+    let mut updated_task = task.clone();
+
+    // 1. Validate the area flags (same rules as add)
+    if parameters.project.is_some() && parameters.area.is_some() {
+        return Err(MoveTaskError::AreaWithProject);
+    }
+    if parameters.area_override.is_some()
+        && parameters.project.is_none()
+        && updated_task.project_id.is_none()
+    {
+        return Err(MoveTaskError::AreaOverrideWithoutProject);
+    }
+
+    // 2. Resolve project and area changes
+    let moving_into_area = parameters.area.is_some();
+
+    if let Some(project_name) = parameters.project {
+        let project_id = find_project_id(store, &project_name).map_err(|e| match e {
+            NameLookupError::NotFound => MoveTaskError::ProjectNotFound(project_name),
+            NameLookupError::Ambiguous(names) => MoveTaskError::AmbiguousProjectName(names),
+        })?;
+        updated_task.project_id = Some(project_id);
+        updated_task.area_id = None;
+    }
+
+    if let Some(area_name) = parameters.area.or(parameters.area_override) {
+        let area_id = find_area_id(store, &area_name).map_err(|e| match e {
+            NameLookupError::NotFound => MoveTaskError::AreaNotFound(area_name),
+            NameLookupError::Ambiguous(names) => MoveTaskError::AmbiguousAreaName(names),
+        })?;
+        if moving_into_area {
+            // Moving straight into an area takes the task out of its project
+            updated_task.project_id = None;
+        }
+        updated_task.area_id = Some(area_id);
+    }
+
+    // 3. Apply the remaining fields
+    if let Some(deadline_str) = parameters.deadline {
+        updated_task.deadline = Some(
+            parse_deadline(&deadline_str, jiff::Zoned::now().date())
+                .map_err(|e| MoveTaskError::InvalidDeadline(deadline_str.clone(), e))?,
+        );
+    }
+
+    if let Some(when) = parameters.when {
+        updated_task.when = when;
+    }
+
+    if let Some(defer_str) = parameters.defer_until {
+        updated_task.defer_until = Some(
+            defer_str
+                .parse::<Date>()
+                .map_err(|e| MoveTaskError::InvalidDeferDate(defer_str.clone(), e.to_string()))?,
+        );
+    }
+
+    if let Some(estimate_str) = parameters.estimate {
+        updated_task.estimate = Some(
+            parse_estimate(&estimate_str).ok_or(MoveTaskError::InvalidEstimate(estimate_str))?,
+        );
+    }
+
+    for tag in parameters.tags {
+        if !updated_task
+            .tags
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(&tag))
+        {
+            updated_task.tags.push(tag);
+        }
+    }
+
+    if let Some(notes) = parameters.notes {
+        updated_task.notes = Some(notes);
+    }
+
+    if let Some(context) = parameters.context {
+        let context = normalize_context(&context);
+        updated_task.context = if context.is_empty() {
+            None
+        } else {
+            Some(context)
+        };
+    }
+
+    if let Some(energy) = parameters.energy {
+        updated_task.energy = Some(energy);
+    }
+    updated_task.record_changes_from(task);
+
+    Ok(updated_task)
+}
+
+/// Lists `move --all --from` selects tasks from, and `--to` moves them to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum TaskList {
+    Inbox,
+    Today,
+    /// Scheduled for a later day (only as a source)
+    Upcoming,
+    Anytime,
+    Someday,
+}
+
+impl TaskList {
+    fn contains(self, task: &Task) -> bool {
+        matches!(
+            (self, &task.when),
+            (TaskList::Inbox, When::Inbox)
+                | (TaskList::Today, When::Today { .. })
+                | (TaskList::Upcoming, When::Scheduled { .. })
+                | (TaskList::Anytime, When::Anytime)
+                | (TaskList::Someday, When::Someday)
+        )
+    }
+
+    /// The schedule of tasks moved into this list; `None` for Upcoming,
+    /// which needs a date
+    pub fn as_when(self) -> Option<When> {
+        match self {
+            TaskList::Inbox => Some(When::Inbox),
+            TaskList::Today => Some(When::Today { evening: false }),
+            TaskList::Upcoming => None,
+            TaskList::Anytime => Some(When::Anytime),
+            TaskList::Someday => Some(When::Someday),
+        }
+    }
+}
+
+pub struct MoveTasksParameters {
+    /// Only move tasks in this list
+    pub from: Option<TaskList>,
+    /// Only move tasks in this project
+    pub from_project: Option<String>,
+    /// Only move tasks with this tag
+    pub from_tag: Option<String>,
+    pub changes: MoveChanges,
+}
+
+/// Apply the same changes to every open task matching all the filters, saving
+/// once. Nothing is saved if any task can't be moved.
+pub fn move_tasks(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: MoveTasksParameters,
+) -> Result<Vec<Task>, MoveTaskError> {
+    let from_project_id = match parameters.from_project {
+        Some(project_name) => Some(find_project_id(store, &project_name).map_err(|e| match e {
+            NameLookupError::NotFound => MoveTaskError::ProjectNotFound(project_name),
+            NameLookupError::Ambiguous(names) => MoveTaskError::AmbiguousProjectName(names),
+        })?),
+        None => None,
+    };
+    let from_tag = parameters
+        .from_tag
+        .as_deref()
+        .map(|tag| tag.trim_start_matches('#'));
+
+    let mut matching: Vec<&Task> = store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none())
+        .filter(|t| parameters.from.is_none_or(|list| list.contains(t)))
+        .filter(|t| from_project_id.is_none_or(|id| t.project_id == Some(id)))
+        .filter(|t| from_tag.is_none_or(|tag| t.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))))
+        .collect();
+    matching.sort_by_key(|t| t.task_number);
+
+    let moved = matching
+        .into_iter()
+        .map(|task| apply_move(store, task, parameters.changes.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !moved.is_empty() {
+        for task in &moved {
+            store.tasks.insert(task.id, task.clone());
+        }
+        storage.save(store)?;
+    }
+
+    Ok(moved)
+}
+
+#[derive(Debug, Error)]
+pub enum EditTaskError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Task title cannot be empty")]
+    EmptyTitle,
+
+    #[error("Invalid deadline date '{0}': {1}")]
+    InvalidDeadline(String, String),
+
+    #[error("Invalid reminder '{0}'")]
+    InvalidReminder(String),
+
+    #[error("Nothing to edit")]
+    NothingToEdit,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Field edits for a task. `None` / `false` fields leave the task untouched.
+pub struct EditTaskParameters {
+    pub task_number_or_fuzzy_name: String,
+    pub title: Option<String>,
+    pub notes: Option<String>,
+    pub clear_notes: bool,
+    pub deadline: Option<String>,
+    pub clear_deadline: bool,
+    pub reminder: Option<String>,
+    pub clear_reminder: bool,
+}
+
+pub fn edit_task(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: EditTaskParameters,
+) -> Result<Task, EditTaskError> {
+    // 1. Make sure there is something to change
+    if parameters.title.is_none()
+        && parameters.notes.is_none()
+        && !parameters.clear_notes
+        && parameters.deadline.is_none()
+        && !parameters.clear_deadline
+        && parameters.reminder.is_none()
+        && !parameters.clear_reminder
+    {
+        return Err(EditTaskError::NothingToEdit);
+    }
+
+    // 2. Find the task
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            EditTaskError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => EditTaskError::AmbiguousTaskName(titles),
+    })?;
+    let mut updated_task = task.clone();
+
+    // 3. Apply the edits
+    if let Some(title) = parameters.title {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(EditTaskError::EmptyTitle);
+        }
+        updated_task.title = title.to_string();
+    }
+
+    if parameters.clear_notes {
+        updated_task.notes = None;
+    } else if let Some(notes) = parameters.notes {
+        updated_task.notes = Some(notes);
+    }
+
+    if parameters.clear_deadline {
+        updated_task.deadline = None;
+    } else if let Some(deadline_str) = parameters.deadline {
+        updated_task.deadline = Some(
+            parse_deadline(&deadline_str, jiff::Zoned::now().date())
+                .map_err(|e| EditTaskError::InvalidDeadline(deadline_str.clone(), e))?,
+        );
+    }
+
+    if parameters.clear_reminder {
+        updated_task.reminder = None;
+    } else if let Some(reminder_str) = parameters.reminder {
+        updated_task.reminder = Some(
+            parse_reminder(&reminder_str, &jiff::Zoned::now())
+                .ok_or(EditTaskError::InvalidReminder(reminder_str))?,
+        );
+    }
+    updated_task.record_changes_from(task);
+
+    // 4. Update in store and persist
+    store.tasks.insert(updated_task.id, updated_task.clone());
+    storage.save(store)?;
+
+    Ok(updated_task)
+}
+
+#[derive(Debug, Error)]
+pub enum AppendNoteError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Note cannot be empty")]
+    EmptyNote,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct AppendNoteParameters {
+    pub task_number_or_fuzzy_name: String,
+    pub text: String,
+}
+
+/// Append a timestamped line to a task's notes, keeping what is already there
+pub fn append_note(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: AppendNoteParameters,
+) -> Result<Task, AppendNoteError> {
+    let text = parameters.text.trim();
+    if text.is_empty() {
+        return Err(AppendNoteError::EmptyNote);
+    }
+
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            AppendNoteError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => AppendNoteError::AmbiguousTaskName(titles),
+    })?;
+
+    let mut updated_task = task.clone();
+    updated_task.append_note(text, &jiff::Zoned::now());
+    updated_task.record_changes_from(task);
+
+    store.tasks.insert(updated_task.id, updated_task.clone());
+    storage.save(store)?;
+
+    Ok(updated_task)
+}
+
+#[derive(Debug, Error)]
+pub enum AttachError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("'{0}' is neither a URL nor an existing file")]
+    InvalidTarget(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct AttachParameters {
+    pub task_number_or_fuzzy_name: String,
+    /// URL or path of a file, relative to the current directory
+    pub target: String,
+    pub label: Option<String>,
+}
+
+/// Attach a URL or file to a task. File paths are stored absolute so they
+/// still open from another directory.
+pub fn attach(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: AttachParameters,
+) -> Result<Task, AttachError> {
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            AttachError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => AttachError::AmbiguousTaskName(titles),
+    })?;
+
+    let mut attachment = Attachment {
+        target: parameters.target.trim().to_string(),
+        label: parameters
+            .label
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty()),
+    };
+    if !attachment.is_url() {
+        attachment.target = std::fs::canonicalize(&attachment.target)
+            .map_err(|_| AttachError::InvalidTarget(parameters.target.clone()))?
+            .display()
+            .to_string();
+    }
+
+    let mut updated_task = task.clone();
+    updated_task.attachments.push(attachment);
+    updated_task.record_changes_from(task);
+
+    store.tasks.insert(updated_task.id, updated_task.clone());
+    storage.save(store)?;
+
+    Ok(updated_task)
+}
+
+#[derive(Debug, Error)]
+pub enum DuplicateTaskError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct DuplicateTaskParameters {
+    pub task_number_or_fuzzy_name: String,
+    /// Schedule for the copy; keeps the original's schedule when `None`
+    pub when: Option<When>,
+}
+
+/// Copy a task's title, notes, tags, checklist, attachments and placement into a new open task.
+/// Dates (deadline, defer, reminder) are not copied and the checklist starts unchecked.
+pub fn duplicate_task(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: DuplicateTaskParameters,
+) -> Result<Task, DuplicateTaskError> {
+    let original =
+        find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+            NameLookupError::NotFound => {
+                DuplicateTaskError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+            }
+            NameLookupError::Ambiguous(titles) => DuplicateTaskError::AmbiguousTaskName(titles),
+        })?;
+
+    let when = parameters.when.unwrap_or_else(|| original.when.clone());
+    let task = copy_task(original, when);
+    let task_id = task.id;
+
+    store.add_task(task);
+    storage.save(store)?;
+
+    Ok(store.get_task(task_id).unwrap().clone())
+}
+
+/// A new open task with the content and placement of `original`, as
+/// `duplicate_task` makes it. The caller adds it to the store.
+pub(crate) fn copy_task(original: &Task, when: When) -> Task {
+    let mut task = Task {
+        id: Uuid::new_v4(),
+        title: original.title.clone(),
+        notes: original.notes.clone(),
+        project_id: original.project_id,
+        area_id: original.area_id,
+        tags: original.tags.clone(),
+        context: original.context.clone(),
+        when,
+        energy: original.energy,
+        estimate: original.estimate,
+        checklist: original
+            .checklist
+            .iter()
+            .map(|item| ChecklistItem {
+                id: Uuid::new_v4(),
+                title: item.title.clone(),
+                completed: false,
+            })
+            .collect(),
+        attachments: original.attachments.clone(),
+        created_at: jiff::Timestamp::now(),
+        ..Task::default()
+    };
+    task.record(TaskChange::Created);
+    task
+}
+
+#[derive(Debug, Error)]
+pub enum CompleteTaskError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Task '{0}' was already completed at {1}")]
+    TaskAlreadyCompleted(String, jiff::Timestamp),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct CompleteTasksParameters {
+    pub task_numbers_or_fuzzy_names: Vec<String>,
+}
+
+/// Outcome of completing a single task from a batch
+pub struct TaskCompletion {
+    pub identifier: String,
+    pub result: Result<Task, CompleteTaskError>,
+}
+
+/// Complete every requested task, saving the store once if any of them succeeded
+pub fn complete_tasks(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: CompleteTasksParameters,
+) -> Result<Vec<TaskCompletion>, CompleteTaskError> {
+    let completions: Vec<_> = parameters
+        .task_numbers_or_fuzzy_names
+        .into_iter()
+        .map(|identifier| {
+            let result = mark_task_completed(store, &identifier);
+            TaskCompletion { identifier, result }
+        })
+        .collect();
+
+    if completions.iter().any(|c| c.result.is_ok()) {
+        storage.save(store)?;
+    }
+
+    Ok(completions)
+}
+
+/// Resolve a task and mark it completed in the store, without saving
+fn mark_task_completed(store: &mut Store, identifier: &str) -> Result<Task, CompleteTaskError> {
+    // Try to parse as task number first
+    let task = if let Ok(task_number) = identifier.parse::<u64>() {
+        // Look up by task number
+        store
+            .get_task_by_number(task_number)
+            .ok_or_else(|| CompleteTaskError::TaskNotFound(identifier.to_string()))?
+    } else {
+        // Fall back to fuzzy matching by title (similar to how projects/areas work)
+        let matching_tasks: Vec<_> = store
+            .get_active_tasks()
+            .filter(|t| t.completed_at.is_none()) // Only match incomplete tasks
+            .filter(|t| t.title.to_lowercase().contains(&identifier.to_lowercase()))
+            .collect();
+
+        match matching_tasks.len() {
+            0 => {
+                return Err(CompleteTaskError::TaskNotFound(identifier.to_string()));
+            }
+            1 => matching_tasks[0],
+            _ => {
+                let titles: Vec<String> = matching_tasks.iter().map(|t| t.title.clone()).collect();
+                return Err(CompleteTaskError::AmbiguousTaskName(titles));
+            }
+        }
+    };
+
+    // Re-stamping a completed task would move it around in the Logbook
+    if let Some(completed_at) = task.completed_at {
+        return Err(CompleteTaskError::TaskAlreadyCompleted(
+            task.title.clone(),
+            completed_at,
+        ));
+    }
+
+    // Mark task as completed, stopping its timer if one is running
+    let mut updated_task = task.clone();
+    let now = jiff::Timestamp::now();
+    updated_task.completed_at = Some(now);
+    updated_task.stop_timer(now);
+    updated_task.record(TaskChange::Completed);
+
+    // Update in store
+    store.tasks.insert(updated_task.id, updated_task.clone());
+
+    Ok(updated_task)
+}
+
+#[derive(Debug, Error)]
+pub enum PromoteDueTasksError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
 }
 
-pub fn add_task(
+pub struct PromoteDueTasksParameters {
+    /// Promote tasks whose deadline is at most this many days away
+    pub within_days: u32,
+}
+
+/// Move open tasks with a close deadline to Today. Snoozed and deferred tasks are left alone.
+pub fn promote_due_tasks(
     store: &mut Store,
     storage: &impl Storage,
-    parameters: AddTaskParameters,
-) -> Result<Task, AddTaskError> {
-    // 1. Validate and resolve project name to project ID
-    let project_id = if let Some(project_name) = parameters.project {
-        let matching_projects: Vec<_> = store
-            .get_active_projects()
-            .filter(|p| p.name.to_lowercase().contains(&project_name.to_lowercase()))
-            .collect();
-
-        match matching_projects.len() {
-            0 => return Err(AddTaskError::ProjectNotFound(project_name)),
-            1 => Some(matching_projects[0].id),
-            _ => {
-                let names: Vec<String> = matching_projects.iter().map(|p| p.name.clone()).collect();
-                return Err(AddTaskError::AmbiguousProjectName(names));
-            }
-        }
-    } else {
-        None
+    parameters: PromoteDueTasksParameters,
+) -> Result<Vec<Task>, PromoteDueTasksError> {
+    let today = jiff::Zoned::now().date();
+    let Ok(cutoff) = today.checked_add(jiff::Span::new().days(i64::from(parameters.within_days)))
+    else {
+        return Ok(Vec::new());
     };
 
-    // 2. Validate and resolve area name to area ID
-    let area_id = if let Some(area_name) = parameters.area {
-        let matching_areas: Vec<_> = store
-            .get_active_areas()
-            .filter(|a| a.name.to_lowercase().contains(&area_name.to_lowercase()))
-            .collect();
+    let task_ids: Vec<Uuid> = store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none() && !t.is_snoozed() && !t.is_deferred())
+        .filter(|t| t.deadline.is_some_and(|deadline| deadline <= cutoff))
+        .filter(|t| match t.when {
+            When::Today { .. } => false,
+            // Overdue scheduled tasks already show up in Today
+            When::Scheduled { date, .. } => date > today,
+            _ => true,
+        })
+        .map(|t| t.id)
+        .collect();
 
-        match matching_areas.len() {
-            0 => return Err(AddTaskError::AreaNotFound(area_name)),
-            1 => Some(matching_areas[0].id),
-            _ => {
-                let names: Vec<String> = matching_areas.iter().map(|a| a.name.clone()).collect();
-                return Err(AddTaskError::AmbiguousAreaName(names));
-            }
+    if task_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut promoted = Vec::new();
+    for task_id in task_ids {
+        if let Some(task) = store.get_task_mut(task_id) {
+            task.reschedule(When::Today { evening: false });
+            promoted.push(task.clone());
         }
-    } else {
-        None
-    };
+    }
 
-    // 3. Parse deadline if provided
-    let deadline = if let Some(deadline_str) = parameters.deadline {
-        Some(
-            deadline_str
-                .parse::<Date>()
-                .map_err(|e| AddTaskError::InvalidDeadline(deadline_str.clone(), e.to_string()))?,
-        )
-    } else {
-        None
-    };
+    storage.save(store)?;
 
-    // 4. Create the task (task_number will be assigned by store.add_task)
-    let task = Task {
-        id: Uuid::new_v4(),
-        task_number: 0,
-        title: parameters.title,
-        notes: parameters.notes,
-        project_id,
-        area_id,
-        tags: parameters.tags,
-        when: parameters.when,
-        deadline,
-        defer_until: None,
-        checklist: vec![],
-        completed_at: None,
-        deleted_at: None,
-        created_at: jiff::Timestamp::now(),
-    };
+    Ok(promoted)
+}
 
-    let task_id = task.id;
+#[derive(Debug, Error)]
+pub enum SurfaceDeferredTasksError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
 
-    // 5. Add to store (assigns task_number)
-    store.add_task(task);
+/// Move open tasks whose defer date has arrived to Today and clear the defer date
+pub fn surface_deferred_tasks(
+    store: &mut Store,
+    storage: &impl Storage,
+) -> Result<Vec<Task>, SurfaceDeferredTasksError> {
+    let task_ids: Vec<Uuid> = store
+        .get_active_tasks()
+        .filter(|t| t.completed_at.is_none())
+        .filter(|t| t.defer_until.is_some() && !t.is_deferred())
+        .map(|t| t.id)
+        .collect();
+
+    if task_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut surfaced = Vec::new();
+    for task_id in task_ids {
+        if let Some(task) = store.get_task_mut(task_id) {
+            task.defer_until = None;
+            if !matches!(task.when, When::Today { .. }) {
+                task.reschedule(When::Today { evening: false });
+            }
+            surfaced.push(task.clone());
+        }
+    }
 
-    // 6. Persist to storage
     storage.save(store)?;
 
-    // 7. Return the created task (with the assigned task_number)
-    Ok(store.get_task(task_id).unwrap().clone())
+    Ok(surfaced)
 }
 
 #[derive(Debug, Error)]
-pub enum CompleteTaskError {
+pub enum UncompleteTaskError {
     #[error("Task '{0}' not found")]
     TaskNotFound(String),
 
     #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
     AmbiguousTaskName(Vec<String>),
 
+    #[error("Task '{0}' is not completed")]
+    TaskNotCompleted(String),
+
     #[error("Storage error: {0}")]
     Storage(#[from] StorageError),
 }
 
-pub struct CompleteTaskParameters {
+pub struct UncompleteTaskParameters {
     pub task_number_or_fuzzy_name: String,
 }
 
-pub fn complete_task(
+pub fn uncomplete_task(
     store: &mut Store,
     storage: &impl Storage,
-    parameters: CompleteTaskParameters,
-) -> Result<Task, CompleteTaskError> {
+    parameters: UncompleteTaskParameters,
+) -> Result<Task, UncompleteTaskError> {
     // Try to parse as task number first
     let task = if let Ok(task_number) = parameters.task_number_or_fuzzy_name.parse::<u64>() {
-        // Look up by task number
-        store.get_task_by_number(task_number).ok_or_else(|| {
-            CompleteTaskError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
-        })?
+        store
+            .get_task_by_number(task_number)
+            .filter(|t| t.deleted_at.is_none())
+            .ok_or_else(|| {
+                UncompleteTaskError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+            })?
     } else {
-        // Fall back to fuzzy matching by title (similar to how projects/areas work)
+        // Fuzzy matching by title (only completed tasks can be reopened)
         let matching_tasks: Vec<_> = store
             .get_active_tasks()
-            .filter(|t| t.completed_at.is_none()) // Only match incomplete tasks
+            .filter(|t| t.completed_at.is_some())
             .filter(|t| {
                 t.title
                     .to_lowercase()
@@ -166,31 +1281,386 @@ pub fn complete_task(
 
         match matching_tasks.len() {
             0 => {
-                return Err(CompleteTaskError::TaskNotFound(
+                return Err(UncompleteTaskError::TaskNotFound(
                     parameters.task_number_or_fuzzy_name,
                 ));
             }
             1 => matching_tasks[0],
             _ => {
                 let titles: Vec<String> = matching_tasks.iter().map(|t| t.title.clone()).collect();
-                return Err(CompleteTaskError::AmbiguousTaskName(titles));
+                return Err(UncompleteTaskError::AmbiguousTaskName(titles));
             }
         }
     };
 
-    // Mark task as completed
+    if task.completed_at.is_none() {
+        return Err(UncompleteTaskError::TaskNotCompleted(task.title.clone()));
+    }
+
     let mut updated_task = task.clone();
-    updated_task.completed_at = Some(jiff::Timestamp::now());
+    updated_task.completed_at = None;
+    updated_task.record(TaskChange::Reopened);
 
-    // Update in store
     store.tasks.insert(updated_task.id, updated_task.clone());
+    storage.save(store)?;
 
-    // Persist to storage
+    Ok(updated_task)
+}
+
+#[derive(Debug, Error)]
+pub enum SnoozeTaskError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Invalid snooze duration '{0}': {1}")]
+    InvalidDuration(String, String),
+
+    #[error("Task '{0}' is not in Today or scheduled, so there is no date to push forward")]
+    NotScheduled(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct SnoozeTaskParameters {
+    pub task_number_or_fuzzy_name: String,
+    /// How long to snooze for. Days, weeks or a weekday ("1d", "1w", "monday")
+    /// push a Today or scheduled task forward; hours and minutes ("2h", "30m"),
+    /// or day units on an undated task, only silence it. `None` pushes it one day.
+    pub duration: Option<String>,
+    /// Remove an active snooze instead
+    pub clear: bool,
+}
+
+/// What `snooze_task` did to the task
+pub enum SnoozeOutcome {
+    /// Moved to a later day
+    Rescheduled { task: Task, from: When },
+    /// Kept its schedule, with reminders and overdue highlighting silenced
+    Silenced(Task),
+    /// Active snooze removed
+    Cleared(Task),
+}
+
+/// Parsed snooze amount
+enum SnoozeBy {
+    /// Push the date forward by whole days, weeks or months
+    Days(jiff::Span),
+    /// Push the date to the next occurrence of a weekday
+    Weekday(jiff::civil::Weekday),
+    /// Silence until this instant
+    Until(jiff::Timestamp),
+}
+
+fn parse_snooze(input: &str) -> Result<SnoozeBy, String> {
+    let lowered = input.trim().to_lowercase();
+    if lowered == "tomorrow" {
+        return Ok(SnoozeBy::Days(jiff::Span::new().days(1)));
+    }
+    if let Some(weekday) = parse_weekday(&lowered) {
+        return Ok(SnoozeBy::Weekday(weekday));
+    }
+
+    let span: jiff::Span = lowered.parse().map_err(|e: jiff::Error| e.to_string())?;
+    if span.is_negative() || span.is_zero() {
+        return Err("duration must be positive".to_string());
+    }
+    if span.get_hours() == 0 && span.get_minutes() == 0 && span.get_seconds() == 0 {
+        return Ok(SnoozeBy::Days(span));
+    }
+    snooze_until(span).map(SnoozeBy::Until)
+}
+
+/// The instant `span` from now. Goes through the local time zone so day units
+/// are calendar-aware.
+fn snooze_until(span: jiff::Span) -> Result<jiff::Timestamp, String> {
+    jiff::Zoned::now()
+        .checked_add(span)
+        .map(|until| until.timestamp())
+        .map_err(|e| e.to_string())
+}
+
+/// Snooze a task: push a Today or scheduled task to a later day (tomorrow by
+/// default), or silence it for a few hours without touching its schedule
+pub fn snooze_task(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: SnoozeTaskParameters,
+) -> Result<SnoozeOutcome, SnoozeTaskError> {
+    // 1. Parse the duration before touching the store
+    let by = match &parameters.duration {
+        Some(duration_str) => Some(
+            parse_snooze(duration_str)
+                .map_err(|e| SnoozeTaskError::InvalidDuration(duration_str.clone(), e))?,
+        ),
+        None => None,
+    };
+
+    // 2. Find the task
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            SnoozeTaskError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => SnoozeTaskError::AmbiguousTaskName(titles),
+    })?;
+
+    // 3. Overdue tasks are pushed forward from today, not from their old date
+    let today = jiff::Zoned::now().date();
+    let current_date = match task.when {
+        When::Today { .. } => Some(today),
+        When::Scheduled { date, .. } => Some(date.max(today)),
+        _ => None,
+    };
+
+    let mut updated_task = task.clone();
+    let duration_str = parameters.duration.unwrap_or_default();
+    let invalid = |e: String| SnoozeTaskError::InvalidDuration(duration_str.clone(), e);
+
+    let outcome = if parameters.clear {
+        updated_task.snoozed_until = None;
+        SnoozeOutcome::Cleared(updated_task.clone())
+    } else {
+        let by = by.unwrap_or(SnoozeBy::Days(jiff::Span::new().days(1)));
+        let new_date = match (by, current_date) {
+            (SnoozeBy::Until(until), _) => {
+                updated_task.snoozed_until = Some(until);
+                None
+            }
+            // Undated tasks keep the old meaning of "1d": silence for a day
+            (SnoozeBy::Days(span), None) if !duration_str.is_empty() => {
+                updated_task.snoozed_until = Some(snooze_until(span).map_err(invalid)?);
+                None
+            }
+            (_, None) => return Err(SnoozeTaskError::NotScheduled(task.title.clone())),
+            (SnoozeBy::Days(span), Some(date)) => {
+                Some(date.checked_add(span).map_err(|e| invalid(e.to_string()))?)
+            }
+            (SnoozeBy::Weekday(weekday), Some(date)) => Some(
+                date.nth_weekday(1, weekday)
+                    .map_err(|e| invalid(e.to_string()))?,
+            ),
+        };
+
+        match new_date {
+            Some(date) => {
+                updated_task.reschedule(When::Scheduled {
+                    date,
+                    evening: task.when.is_evening(),
+                });
+                SnoozeOutcome::Rescheduled {
+                    task: updated_task.clone(),
+                    from: task.when.clone(),
+                }
+            }
+            None => SnoozeOutcome::Silenced(updated_task.clone()),
+        }
+    };
+
+    store.tasks.insert(updated_task.id, updated_task.clone());
+    storage.save(store)?;
+
+    Ok(outcome)
+}
+
+#[derive(Debug, Error)]
+pub enum SetEveningError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Task '{0}' is not scheduled for a day")]
+    NotScheduledForADay(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct SetEveningParameters {
+    pub task_number_or_fuzzy_name: String,
+    /// `true` moves the task to this evening, `false` back to the regular Today list
+    pub evening: bool,
+}
+
+pub fn set_evening(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: SetEveningParameters,
+) -> Result<Task, SetEveningError> {
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            SetEveningError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => SetEveningError::AmbiguousTaskName(titles),
+    })?;
+
+    let when = match task.when {
+        When::Today { .. } => When::Today {
+            evening: parameters.evening,
+        },
+        When::Scheduled { date, .. } => When::Scheduled {
+            date,
+            evening: parameters.evening,
+        },
+        _ => return Err(SetEveningError::NotScheduledForADay(task.title.clone())),
+    };
+
+    let mut updated_task = task.clone();
+    updated_task.reschedule(when);
+
+    store.tasks.insert(updated_task.id, updated_task.clone());
+    storage.save(store)?;
+
+    Ok(updated_task)
+}
+
+#[derive(Debug, Error)]
+pub enum SetPinnedError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct SetPinnedParameters {
+    pub task_number_or_fuzzy_name: String,
+    pub pinned: bool,
+}
+
+pub fn set_pinned(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: SetPinnedParameters,
+) -> Result<Task, SetPinnedError> {
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            SetPinnedError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => SetPinnedError::AmbiguousTaskName(titles),
+    })?;
+
+    let mut updated_task = task.clone();
+    updated_task.pinned = parameters.pinned;
+    updated_task.record_changes_from(task);
+
+    store.tasks.insert(updated_task.id, updated_task.clone());
     storage.save(store)?;
 
     Ok(updated_task)
 }
 
+#[derive(Debug, Error)]
+pub enum StartTimerError {
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task name is ambiguous. Multiple tasks found: {}", .0.join(", "))]
+    AmbiguousTaskName(Vec<String>),
+
+    #[error("Task '{0}' is already completed")]
+    TaskCompleted(String),
+
+    #[error("A timer is already running on task '{0}'")]
+    TimerAlreadyRunning(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct StartTimerParameters {
+    pub task_number_or_fuzzy_name: String,
+}
+
+pub struct StartTimerResult {
+    pub task: Task,
+    /// Task whose timer was stopped to start this one, with how long it ran
+    pub stopped: Option<(Task, SignedDuration)>,
+}
+
+/// Start timing a task. Only one timer runs at a time, so a timer running on
+/// another task is stopped first.
+pub fn start_timer(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: StartTimerParameters,
+) -> Result<StartTimerResult, StartTimerError> {
+    let task = find_task(store, &parameters.task_number_or_fuzzy_name).map_err(|e| match e {
+        NameLookupError::NotFound => {
+            StartTimerError::TaskNotFound(parameters.task_number_or_fuzzy_name.clone())
+        }
+        NameLookupError::Ambiguous(titles) => StartTimerError::AmbiguousTaskName(titles),
+    })?;
+
+    if task.completed_at.is_some() {
+        return Err(StartTimerError::TaskCompleted(task.title.clone()));
+    }
+    if task.running_timer().is_some() {
+        return Err(StartTimerError::TimerAlreadyRunning(task.title.clone()));
+    }
+
+    let mut updated_task = task.clone();
+    let now = jiff::Timestamp::now();
+
+    let stopped = running_timer_task(store).cloned().map(|mut running| {
+        let duration = running.stop_timer(now).unwrap_or_default();
+        store.tasks.insert(running.id, running.clone());
+        (running, duration)
+    });
+
+    updated_task.time_entries.push(TimeEntry {
+        started_at: now,
+        stopped_at: None,
+    });
+
+    store.tasks.insert(updated_task.id, updated_task.clone());
+    storage.save(store)?;
+
+    Ok(StartTimerResult {
+        task: updated_task,
+        stopped,
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum StopTimerError {
+    #[error("No timer is running")]
+    NoTimerRunning,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Stop the running timer, returning its task and how long the timer ran
+pub fn stop_timer(
+    store: &mut Store,
+    storage: &impl Storage,
+) -> Result<(Task, SignedDuration), StopTimerError> {
+    let mut task = running_timer_task(store)
+        .cloned()
+        .ok_or(StopTimerError::NoTimerRunning)?;
+    let duration = task.stop_timer(jiff::Timestamp::now()).unwrap_or_default();
+
+    store.tasks.insert(task.id, task.clone());
+    storage.save(store)?;
+
+    Ok((task, duration))
+}
+
+/// The task whose timer is running, if any
+pub fn running_timer_task(store: &Store) -> Option<&Task> {
+    store
+        .get_active_tasks()
+        .find(|t| t.running_timer().is_some())
+}
+
 #[derive(Debug, Error)]
 pub enum DeleteTaskError {
     #[error("Task '{0}' not found")]
@@ -254,6 +1724,7 @@ pub fn delete_task(
     let task_id = task.id;
     let mut updated_task = task.clone();
     updated_task.deleted_at = Some(jiff::Timestamp::now());
+    updated_task.record(TaskChange::Deleted);
 
     // Update in store
     store.tasks.insert(task_id, updated_task.clone());
@@ -298,6 +1769,7 @@ pub fn restore_task(
     let task_id = task.id;
     let mut restored_task = task.clone();
     restored_task.deleted_at = None;
+    restored_task.record(TaskChange::Restored);
 
     // Update in store
     store.tasks.insert(task_id, restored_task.clone());
@@ -307,3 +1779,110 @@ pub fn restore_task(
 
     Ok(restored_task)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+    use crate::storage::json::JsonFileStorage;
+
+    fn store_with_projects(names_and_slugs: &[(&str, &str)]) -> Store {
+        let mut store = Store::default();
+        for (name, slug) in names_and_slugs {
+            store.add_project(Project {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+                slug: slug.to_string(),
+                ..Project::default()
+            });
+        }
+        store
+    }
+
+    fn project_name(store: &Store, query: &str) -> String {
+        match find_project_id(store, query) {
+            Ok(id) => store.get_project(id).unwrap().name.clone(),
+            Err(_) => panic!("'{}' should resolve to one project", query),
+        }
+    }
+
+    #[test]
+    fn test_exact_slug_wins_over_names() {
+        let store = store_with_projects(&[("Errands", "home"), ("Home", "home-2")]);
+        assert_eq!(project_name(&store, "HOME"), "Errands");
+        assert_eq!(project_name(&store, "home-2"), "Home");
+    }
+
+    #[test]
+    fn test_exact_name_wins_over_substring() {
+        let store = store_with_projects(&[("Work", "office"), ("Work Copy", "work-copy")]);
+        assert_eq!(project_name(&store, "work"), "Work");
+        assert_eq!(project_name(&store, "copy"), "Work Copy");
+    }
+
+    #[test]
+    fn test_ambiguous_and_missing_matches() {
+        let store = store_with_projects(&[("Garden", "garden"), ("Garage", "garage")]);
+
+        match find_project_id(&store, "gar") {
+            Err(NameLookupError::Ambiguous(mut candidates)) => {
+                candidates.sort();
+                assert_eq!(candidates, ["Garage (garage)", "Garden (garden)"]);
+            }
+            _ => panic!("'gar' should be ambiguous"),
+        }
+        assert!(matches!(
+            find_project_id(&store, "kitchen"),
+            Err(NameLookupError::NotFound)
+        ));
+    }
+
+    fn add_parameters(title: &str, idempotency_key: &str) -> AddTaskParameters {
+        AddTaskParameters {
+            title: title.to_string(),
+            notes: None,
+            when: When::Inbox,
+            deadline: None,
+            project: None,
+            area: None,
+            area_override: None,
+            tags: vec![],
+            context: None,
+            energy: None,
+            reminder: None,
+            defer_until: None,
+            estimate: None,
+            create_project: false,
+            create_area: false,
+            id: None,
+            idempotency_key: Some(idempotency_key.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_idempotent_add_returns_the_existing_task_or_rejects_a_different_one() {
+        let test_dir = PathBuf::from("/tmp/tdo_idempotent_add_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+        let storage = JsonFileStorage::new(test_dir.join("store.json"));
+        let mut store = Store::default();
+
+        let first = add_task(&mut store, &storage, add_parameters("Call Sam", "sync-42")).unwrap();
+        assert!(matches!(first, AddedTask::Created(_)));
+
+        let retry = add_task(&mut store, &storage, add_parameters("Call Sam", "sync-42")).unwrap();
+        assert!(matches!(retry, AddedTask::Existing(ref t) if t.id == first.task().id));
+
+        let mut different = add_parameters("Call Alex", "sync-42");
+        different.tags = vec!["phone".to_string()];
+        match add_task(&mut store, &storage, different) {
+            Err(AddTaskError::IdempotencyMismatch(task_number, fields)) => {
+                assert_eq!(task_number, first.task().task_number);
+                assert_eq!(fields, ["title", "tags"]);
+            }
+            _ => panic!("a different task under the same key should be rejected"),
+        }
+        assert_eq!(store.tasks.len(), 1);
+    }
+}
@@ -0,0 +1,611 @@
+use jiff::{Timestamp, civil::Date};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        area::Area,
+        project::Project,
+        store::Store,
+        task::{Task, TaskChange},
+    },
+    services::tasks::{NameLookupError, find_by_slug_or_name},
+    storage::{Storage, StorageError},
+};
+
+#[derive(Debug, Error)]
+pub enum RestoreFromTrashError {
+    #[error("Project '{0}' not found")]
+    ProjectNotFound(String),
+
+    #[error("Project name is ambiguous. Multiple projects found: {}", .0.join(", "))]
+    AmbiguousProjectName(Vec<String>),
+
+    #[error("Invalid date '{0}': {1}")]
+    InvalidDate(String, String),
+
+    #[error("Nothing in the trash matches")]
+    NothingToRestore,
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Which trashed items to restore. Both filters can be combined.
+pub struct RestoreFromTrashParameters {
+    /// Restore a project together with the tasks deleted along with it
+    pub project: Option<String>,
+    /// Restore everything deleted on or after this date
+    pub since: Option<String>,
+}
+
+pub struct RestoreFromTrashResult {
+    pub areas: Vec<Area>,
+    pub projects: Vec<Project>,
+    pub tasks: Vec<Task>,
+}
+
+pub fn restore_from_trash(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: RestoreFromTrashParameters,
+) -> Result<RestoreFromTrashResult, RestoreFromTrashError> {
+    // 1. Parse the date filter
+    let since =
+        match parameters.since {
+            Some(since_str) => Some(since_str.parse::<Date>().map_err(|e| {
+                RestoreFromTrashError::InvalidDate(since_str.clone(), e.to_string())
+            })?),
+            None => None,
+        };
+    let deleted_since = |deleted_at: Option<Timestamp>| {
+        deleted_at.is_some_and(|ts| since.is_none_or(|date| local_date(ts) >= date))
+    };
+
+    // 2. Pick what to restore
+    let (area_ids, project_ids, task_ids): (Vec<Uuid>, Vec<Uuid>, Vec<Uuid>) =
+        match parameters.project {
+            Some(name) => {
+                let project = find_any_project(store, &name)?;
+
+                // A trashed project brings back the tasks its deletion cascaded to;
+                // an active one gets back every trashed task it still owns
+                let task_ids = store
+                    .get_tasks_for_project(project.id)
+                    .filter(|t| deleted_since(t.deleted_at))
+                    .filter(|t| project.deleted_at.is_none_or(|ts| t.deleted_at == Some(ts)))
+                    .map(|t| t.id)
+                    .collect();
+                let project_ids = if deleted_since(project.deleted_at) {
+                    vec![project.id]
+                } else {
+                    vec![]
+                };
+
+                (vec![], project_ids, task_ids)
+            }
+            None => (
+                store
+                    .get_deleted_areas()
+                    .filter(|a| deleted_since(a.deleted_at))
+                    .map(|a| a.id)
+                    .collect(),
+                store
+                    .get_deleted_projects()
+                    .filter(|p| deleted_since(p.deleted_at))
+                    .map(|p| p.id)
+                    .collect(),
+                store
+                    .get_deleted_tasks()
+                    .filter(|t| deleted_since(t.deleted_at))
+                    .map(|t| t.id)
+                    .collect(),
+            ),
+        };
+
+    if area_ids.is_empty() && project_ids.is_empty() && task_ids.is_empty() {
+        return Err(RestoreFromTrashError::NothingToRestore);
+    }
+
+    // 3. Restore everything in one save
+    let mut result = RestoreFromTrashResult {
+        areas: vec![],
+        projects: vec![],
+        tasks: vec![],
+    };
+
+    for area_id in area_ids {
+        if let Some(area) = store.get_area_mut(area_id) {
+            area.deleted_at = None;
+            result.areas.push(area.clone());
+        }
+    }
+    for project_id in project_ids {
+        if let Some(project) = store.get_project_mut(project_id) {
+            project.deleted_at = None;
+            result.projects.push(project.clone());
+        }
+    }
+    for task_id in task_ids {
+        if let Some(task) = store.get_task_mut(task_id) {
+            task.deleted_at = None;
+            task.record(TaskChange::Restored);
+            result.tasks.push(task.clone());
+        }
+    }
+    result.tasks.sort_by_key(|t| t.task_number);
+
+    storage.save(store)?;
+
+    Ok(result)
+}
+
+/// Match a project by slug or name, trashed or not
+fn find_any_project<'a>(
+    store: &'a Store,
+    name: &str,
+) -> Result<&'a Project, RestoreFromTrashError> {
+    find_by_slug_or_name(store.projects.values(), |p| (&p.slug, &p.name), name).map_err(|e| match e
+    {
+        NameLookupError::NotFound => RestoreFromTrashError::ProjectNotFound(name.to_string()),
+        NameLookupError::Ambiguous(names) => RestoreFromTrashError::AmbiguousProjectName(names),
+    })
+}
+
+fn local_date(timestamp: Timestamp) -> Date {
+    jiff::Zoned::new(timestamp, jiff::tz::TimeZone::system()).date()
+}
+
+#[derive(Debug, Error)]
+pub enum EmptyTrashError {
+    #[error("Invalid age '{0}': {1}")]
+    InvalidAge(String, String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct EmptyTrashParameters {
+    /// Only purge items deleted longer ago than this (e.g., "30d", "2w")
+    pub older_than: Option<String>,
+    /// Remove the items instead of only listing them
+    pub apply: bool,
+}
+
+/// Items permanently removed from the store (or that would be, when not applied)
+pub struct PurgeResult {
+    pub areas: Vec<Area>,
+    pub projects: Vec<Project>,
+    pub tasks: Vec<Task>,
+}
+
+impl PurgeResult {
+    pub fn total(&self) -> usize {
+        self.areas.len() + self.projects.len() + self.tasks.len()
+    }
+}
+
+/// Permanently remove soft-deleted tasks, projects and areas. Without `apply`
+/// the store is left untouched and the result previews what would go.
+pub fn empty_trash(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: EmptyTrashParameters,
+) -> Result<PurgeResult, EmptyTrashError> {
+    // 1. Work out the cutoff from the age
+    let cutoff = match parameters.older_than {
+        Some(age_str) => Some(
+            age_cutoff(&age_str).map_err(|e| EmptyTrashError::InvalidAge(age_str.clone(), e))?,
+        ),
+        None => None,
+    };
+    let old_enough = |deleted_at: Option<Timestamp>| {
+        deleted_at.is_some_and(|ts| cutoff.is_none_or(|cutoff| ts <= cutoff))
+    };
+
+    // 2. Pick what to purge
+    let area_ids: Vec<Uuid> = store
+        .get_deleted_areas()
+        .filter(|a| old_enough(a.deleted_at))
+        .map(|a| a.id)
+        .collect();
+    let project_ids: Vec<Uuid> = store
+        .get_deleted_projects()
+        .filter(|p| old_enough(p.deleted_at))
+        .map(|p| p.id)
+        .collect();
+    let task_ids: Vec<Uuid> = store
+        .get_deleted_tasks()
+        .filter(|t| old_enough(t.deleted_at))
+        .map(|t| t.id)
+        .collect();
+
+    if !parameters.apply {
+        return Ok(preview_purge(store, &area_ids, &project_ids, &task_ids));
+    }
+
+    // 3. Remove everything in one save
+    let result = purge(store, &area_ids, &project_ids, &task_ids);
+    if result.total() > 0 {
+        storage.save(store)?;
+    }
+
+    Ok(result)
+}
+
+#[derive(Debug, Error)]
+pub enum PurgeItemError {
+    #[error("No trashed task, project or area matches '{0}'")]
+    NotFound(String),
+
+    #[error("{0} is not in the trash")]
+    NotInTrash(String),
+
+    #[error("Name is ambiguous. Multiple trashed items found: {}", .0.join(", "))]
+    Ambiguous(Vec<String>),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct PurgeItemParameters {
+    /// Task number, or the name of a trashed project or area
+    pub item: String,
+    /// Remove the item instead of only listing what would go
+    pub apply: bool,
+}
+
+/// Permanently remove one trashed item. A project or area takes the items
+/// that were deleted along with it, the same ones restoring it would bring back.
+/// Without `apply` the store is left untouched.
+pub fn purge_item(
+    store: &mut Store,
+    storage: &impl Storage,
+    parameters: PurgeItemParameters,
+) -> Result<PurgeResult, PurgeItemError> {
+    let (area_ids, project_ids, task_ids) = match parameters.item.parse::<u64>() {
+        Ok(task_number) => {
+            let task = store
+                .get_task_by_number(task_number)
+                .ok_or_else(|| PurgeItemError::NotFound(parameters.item.clone()))?;
+            if task.deleted_at.is_none() {
+                return Err(PurgeItemError::NotInTrash(format!("Task #{}", task_number)));
+            }
+            (vec![], vec![], vec![task.id])
+        }
+        Err(_) => match find_trashed_container(store, &parameters.item)? {
+            TrashedContainer::Project(project) => (
+                vec![],
+                vec![project.id],
+                store
+                    .get_tasks_for_project(project.id)
+                    .filter(|t| t.deleted_at == project.deleted_at)
+                    .map(|t| t.id)
+                    .collect(),
+            ),
+            TrashedContainer::Area(area) => {
+                let project_ids: Vec<Uuid> = store
+                    .projects
+                    .values()
+                    .filter(|p| p.area_id == Some(area.id) && p.deleted_at == area.deleted_at)
+                    .map(|p| p.id)
+                    .collect();
+                let task_ids = store
+                    .tasks
+                    .values()
+                    .filter(|t| t.deleted_at == area.deleted_at)
+                    .filter(|t| {
+                        t.area_id == Some(area.id)
+                            || t.project_id.is_some_and(|id| project_ids.contains(&id))
+                    })
+                    .map(|t| t.id)
+                    .collect();
+                (vec![area.id], project_ids, task_ids)
+            }
+        },
+    };
+
+    if !parameters.apply {
+        return Ok(preview_purge(store, &area_ids, &project_ids, &task_ids));
+    }
+
+    let result = purge(store, &area_ids, &project_ids, &task_ids);
+    storage.save(store)?;
+
+    Ok(result)
+}
+
+enum TrashedContainer<'a> {
+    Project(&'a Project),
+    Area(&'a Area),
+}
+
+/// Fuzzy match a trashed project or area by name. A name that only matches
+/// active containers is reported as not in the trash.
+fn find_trashed_container<'a>(
+    store: &'a Store,
+    name: &str,
+) -> Result<TrashedContainer<'a>, PurgeItemError> {
+    let matches = |candidate: &str| candidate.to_lowercase().contains(&name.to_lowercase());
+
+    let mut found: Vec<TrashedContainer> = store
+        .get_deleted_projects()
+        .filter(|p| matches(&p.name))
+        .map(TrashedContainer::Project)
+        .chain(
+            store
+                .get_deleted_areas()
+                .filter(|a| matches(&a.name))
+                .map(TrashedContainer::Area),
+        )
+        .collect();
+
+    match found.len() {
+        0 => {
+            let active = store
+                .get_active_projects()
+                .find(|p| matches(&p.name))
+                .map(|p| format!("Project '{}'", p.name))
+                .or_else(|| {
+                    store
+                        .get_active_areas()
+                        .find(|a| matches(&a.name))
+                        .map(|a| format!("Area '{}'", a.name))
+                });
+            Err(match active {
+                Some(label) => PurgeItemError::NotInTrash(label),
+                None => PurgeItemError::NotFound(name.to_string()),
+            })
+        }
+        1 => Ok(found.remove(0)),
+        _ => Err(PurgeItemError::Ambiguous(
+            found
+                .iter()
+                .map(|container| match container {
+                    TrashedContainer::Project(p) => format!("project '{}'", p.name),
+                    TrashedContainer::Area(a) => format!("area '{}'", a.name),
+                })
+                .collect(),
+        )),
+    }
+}
+
+/// What `purge` would remove, leaving the store untouched
+fn preview_purge(
+    store: &Store,
+    area_ids: &[Uuid],
+    project_ids: &[Uuid],
+    task_ids: &[Uuid],
+) -> PurgeResult {
+    let mut result = PurgeResult {
+        areas: area_ids
+            .iter()
+            .filter_map(|id| store.get_area(*id).cloned())
+            .collect(),
+        projects: project_ids
+            .iter()
+            .filter_map(|id| store.get_project(*id).cloned())
+            .collect(),
+        tasks: task_ids
+            .iter()
+            .filter_map(|id| store.get_task(*id).cloned())
+            .collect(),
+    };
+    result.tasks.sort_by_key(|t| t.task_number);
+    result
+}
+
+/// The instant `age` (e.g., "30d", "2w") ago. Goes through the local time zone
+/// so day units are calendar-aware.
+pub(crate) fn age_cutoff(age: &str) -> Result<Timestamp, String> {
+    let span: jiff::Span = age.parse().map_err(|e: jiff::Error| e.to_string())?;
+    if span.is_negative() {
+        return Err("age must not be negative".to_string());
+    }
+    jiff::Zoned::now()
+        .checked_sub(span)
+        .map(|cutoff| cutoff.timestamp())
+        .map_err(|e| e.to_string())
+}
+
+/// Remove items from the store for good. Whatever still points at a removed
+/// project or area (trashed tasks, dependencies, next actions) is detached so
+/// no references dangle.
+fn purge(
+    store: &mut Store,
+    area_ids: &[Uuid],
+    project_ids: &[Uuid],
+    task_ids: &[Uuid],
+) -> PurgeResult {
+    let mut result = PurgeResult {
+        areas: area_ids
+            .iter()
+            .filter_map(|id| store.areas.remove(id))
+            .collect(),
+        projects: project_ids
+            .iter()
+            .filter_map(|id| store.projects.remove(id))
+            .collect(),
+        tasks: task_ids
+            .iter()
+            .filter_map(|id| store.tasks.remove(id))
+            .collect(),
+    };
+    result.tasks.sort_by_key(|t| t.task_number);
+
+    for task in store.tasks.values_mut() {
+        if task.project_id.is_some_and(|id| project_ids.contains(&id)) {
+            task.project_id = None;
+        }
+        if task.area_id.is_some_and(|id| area_ids.contains(&id)) {
+            task.area_id = None;
+        }
+    }
+    for project in store.projects.values_mut() {
+        if project.area_id.is_some_and(|id| area_ids.contains(&id)) {
+            project.area_id = None;
+        }
+        project.blocked_by.retain(|id| !project_ids.contains(id));
+        if project.next_action.is_some_and(|id| task_ids.contains(&id)) {
+            project.next_action = None;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+    use crate::storage::json::JsonFileStorage;
+
+    fn test_storage(name: &str) -> JsonFileStorage {
+        let test_dir = PathBuf::from(format!("/tmp/tdo_{}_test", name));
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+        JsonFileStorage::new(test_dir.join("store.json"))
+    }
+
+    /// A trashed "Launch" project with the task deleted along with it, a task
+    /// of the project trashed long before, and an active project waiting on it
+    fn store_with_trashed_project() -> (Store, Uuid, Uuid) {
+        let now = Timestamp::now();
+        let mut store = Store::default();
+        let launch = Project {
+            id: Uuid::new_v4(),
+            name: "Launch".to_string(),
+            slug: "launch".to_string(),
+            deleted_at: Some(now),
+            ..Project::default()
+        };
+        let follow_up = Project {
+            id: Uuid::new_v4(),
+            name: "Follow-up".to_string(),
+            slug: "follow-up".to_string(),
+            blocked_by: vec![launch.id],
+            ..Project::default()
+        };
+        for (title, deleted_at) in [
+            ("Write announcement", Some(now)),
+            ("Old draft", Some(Timestamp::UNIX_EPOCH)),
+        ] {
+            store.add_task(Task {
+                id: Uuid::new_v4(),
+                title: title.to_string(),
+                project_id: Some(launch.id),
+                deleted_at,
+                ..Task::default()
+            });
+        }
+        let (launch_id, follow_up_id) = (launch.id, follow_up.id);
+        store.add_project(launch);
+        store.add_project(follow_up);
+        (store, launch_id, follow_up_id)
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_empty_trash_previews_then_purges_by_age() {
+        let storage = test_storage("empty_trash");
+        let (mut store, launch_id, follow_up_id) = store_with_trashed_project();
+
+        let preview = empty_trash(
+            &mut store,
+            &storage,
+            EmptyTrashParameters {
+                older_than: None,
+                apply: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(preview.total(), 3);
+        assert_eq!(store.tasks.len(), 2);
+
+        let old = empty_trash(
+            &mut store,
+            &storage,
+            EmptyTrashParameters {
+                older_than: Some("30d".to_string()),
+                apply: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(titles(&old.tasks), ["Old draft"]);
+        assert!(old.projects.is_empty());
+
+        let rest = empty_trash(
+            &mut store,
+            &storage,
+            EmptyTrashParameters {
+                older_than: None,
+                apply: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(titles(&rest.tasks), ["Write announcement"]);
+        assert_eq!(rest.projects.len(), 1);
+        assert!(store.tasks.is_empty());
+        assert!(store.get_project(launch_id).is_none());
+        // Nothing is left pointing at the purged project
+        assert!(
+            store
+                .get_project(follow_up_id)
+                .unwrap()
+                .blocked_by
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_purge_project_takes_the_tasks_deleted_with_it() {
+        let storage = test_storage("purge_item");
+        let (mut store, launch_id, _) = store_with_trashed_project();
+
+        let result = purge_item(
+            &mut store,
+            &storage,
+            PurgeItemParameters {
+                item: "launch".to_string(),
+                apply: true,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(result.projects.len(), 1);
+        assert_eq!(titles(&result.tasks), ["Write announcement"]);
+        assert!(store.get_project(launch_id).is_none());
+        // Trashed on its own before, so it stays in the trash, detached
+        let old_draft = store.tasks.values().next().unwrap();
+        assert_eq!(old_draft.title, "Old draft");
+        assert!(old_draft.deleted_at.is_some());
+        assert!(old_draft.project_id.is_none());
+    }
+
+    #[test]
+    fn test_purge_item_refuses_active_tasks() {
+        let storage = test_storage("purge_active");
+        let mut store = Store::default();
+        store.add_task(Task {
+            id: Uuid::new_v4(),
+            title: "Still open".to_string(),
+            ..Task::default()
+        });
+
+        let result = purge_item(
+            &mut store,
+            &storage,
+            PurgeItemParameters {
+                item: "1".to_string(),
+                apply: true,
+            },
+        );
+
+        assert!(matches!(result, Err(PurgeItemError::NotInTrash(_))));
+        assert_eq!(store.tasks.len(), 1);
+    }
+}
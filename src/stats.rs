@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use jiff::SignedDuration;
+
+use crate::{
+    models::{store::Store, task::Task},
+    ui,
+};
+
+/// How completed tasks are grouped when computing cycle times
+#[derive(Clone, Copy, Debug, Default, clap::ValueEnum)]
+pub enum CycleTimeGrouping {
+    /// Group by project
+    #[default]
+    Project,
+    /// Group by effective area
+    Area,
+    /// Group by tag (a task counts once for every tag it carries)
+    Tag,
+}
+
+/// Cycle-time summary for a single group
+pub struct CycleTimeRow {
+    pub label: String,
+    pub completed: usize,
+    pub median_days: f64,
+}
+
+/// Label used for tasks that don't belong to any group
+const UNGROUPED_LABEL: &str = "(none)";
+
+/// Median days from creation to completion for tasks completed in the last `window_days`,
+/// sorted slowest first so the groups where tasks stagnate come to the top
+pub fn cycle_times(
+    store: &Store,
+    grouping: CycleTimeGrouping,
+    window_days: i64,
+) -> Vec<CycleTimeRow> {
+    let mut grouped: HashMap<String, Vec<f64>> = HashMap::new();
+
+    let completed = store
+        .tasks
+        .values()
+        .filter(|t| t.deleted_at.is_none())
+        .filter(|t| {
+            t.completed_at
+                .is_some_and(|ts| ui::is_within_days(ts, window_days))
+        });
+
+    for task in completed {
+        let Some(days) = cycle_days(task) else {
+            continue;
+        };
+
+        for label in group_labels(task, store, grouping) {
+            grouped.entry(label).or_default().push(days);
+        }
+    }
+
+    let mut rows: Vec<_> = grouped
+        .into_iter()
+        .map(|(label, mut days)| CycleTimeRow {
+            label,
+            completed: days.len(),
+            median_days: median(&mut days),
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        b.median_days
+            .total_cmp(&a.median_days)
+            .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+    });
+
+    rows
+}
+
+/// Time tracked on the tasks of a single project
+pub struct TrackedTimeRow {
+    pub label: String,
+    pub tracked: SignedDuration,
+    pub tasks: usize,
+}
+
+/// Time tracked per project from entries started in the last `window_days`
+/// (a running timer counts up to now), sorted by most time first
+pub fn tracked_time_by_project(store: &Store, window_days: i64) -> Vec<TrackedTimeRow> {
+    let mut grouped: HashMap<String, (SignedDuration, usize)> = HashMap::new();
+
+    for task in store.tasks.values().filter(|t| t.deleted_at.is_none()) {
+        let tracked: SignedDuration = task
+            .time_entries
+            .iter()
+            .filter(|e| ui::is_within_days(e.started_at, window_days))
+            .map(|e| e.duration())
+            .sum();
+        if tracked.is_zero() {
+            continue;
+        }
+
+        for label in group_labels(task, store, CycleTimeGrouping::Project) {
+            let entry = grouped.entry(label).or_default();
+            entry.0 += tracked;
+            entry.1 += 1;
+        }
+    }
+
+    let mut rows: Vec<_> = grouped
+        .into_iter()
+        .map(|(label, (tracked, tasks))| TrackedTimeRow {
+            label,
+            tracked,
+            tasks,
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        b.tracked
+            .cmp(&a.tracked)
+            .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+    });
+
+    rows
+}
+
+/// Days between a task's creation and completion
+fn cycle_days(task: &Task) -> Option<f64> {
+    let completed_at = task.completed_at?;
+    let elapsed = completed_at.duration_since(task.created_at);
+    Some((elapsed.as_secs_f64() / 86_400.0).max(0.0))
+}
+
+fn group_labels(task: &Task, store: &Store, grouping: CycleTimeGrouping) -> Vec<String> {
+    match grouping {
+        CycleTimeGrouping::Project => {
+            let name = task
+                .project_id
+                .and_then(|id| store.projects.get(&id))
+                .map(|p| p.name.clone());
+            vec![name.unwrap_or_else(|| UNGROUPED_LABEL.to_string())]
+        }
+        CycleTimeGrouping::Area => {
+            let name = store
+                .get_task_area_id(task)
+                .and_then(|id| store.areas.get(&id))
+                .map(|a| a.name.clone());
+            vec![name.unwrap_or_else(|| UNGROUPED_LABEL.to_string())]
+        }
+        CycleTimeGrouping::Tag => {
+            if task.tags.is_empty() {
+                vec![UNGROUPED_LABEL.to_string()]
+            } else {
+                task.tags.iter().map(|t| format!("#{}", t)).collect()
+            }
+        }
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_odd_and_even() {
+        assert_eq!(median(&mut []), 0.0);
+        assert_eq!(median(&mut [3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), 2.5);
+    }
+}
@@ -2,8 +2,9 @@ use std::path::PathBuf;
 
 use thiserror::Error;
 
-use crate::models::store::Store;
+use crate::models::{store::Store, task::Task};
 
+pub mod journal;
 pub mod json;
 pub mod migrations;
 
@@ -36,6 +37,13 @@ pub enum StorageError {
         source: serde_json::Error,
     },
 
+    #[error("Failed to write journal '{path}': {source}")]
+    JournalFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error("Failed to create backup at '{path}': {source}")]
     BackupFailed {
         path: PathBuf,
@@ -57,9 +65,27 @@ pub enum StorageError {
 
     #[error("Store file has unsupported version {0}. This version of tdo cannot read this file.")]
     UnsupportedVersion(u32),
+
+    #[error(
+        "The store is busy: another tdo process is saving to '{path}'. Try again, or pass --wait to wait for it."
+    )]
+    Busy { path: PathBuf },
+
+    #[error(
+        "Refusing to save a store with broken references ({}). Run `tdo doctor --fix` to repair them.",
+        .0.join("; ")
+    )]
+    BrokenReferences(Vec<String>),
 }
 
 pub trait Storage {
     fn load(&self) -> Result<Store, StorageError>;
     fn save(&self, store: &Store) -> Result<(), StorageError>;
+
+    /// Add tasks to the archive, kept apart from the store. Tasks already in
+    /// the archive are replaced, so archiving the same task twice is harmless.
+    fn archive(&self, tasks: &[Task]) -> Result<(), StorageError>;
+
+    /// Every task in the archive
+    fn load_archive(&self) -> Result<Vec<Task>, StorageError>;
 }
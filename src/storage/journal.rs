@@ -0,0 +1,208 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::storage::StorageError;
+
+/// The command that is about to write the store
+#[derive(Clone, Debug)]
+pub struct Intent {
+    /// Subcommand name, e.g. `done` or `project new`
+    pub operation: String,
+    /// Command-line arguments, enough to run the command again
+    pub args: Vec<String>,
+}
+
+/// Written to the journal before the store file is replaced and removed once
+/// the save completes. Finding one on start means a command was interrupted.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IntentRecord {
+    pub operation: String,
+    pub args: Vec<String>,
+    pub started_at: Timestamp,
+    /// Checksum of the store file before the save, `None` when there was no file yet
+    pub store_checksum: Option<String>,
+}
+
+impl IntentRecord {
+    /// The command line as the user typed it
+    pub fn command_line(&self) -> String {
+        command_line(&self.args)
+    }
+}
+
+/// A completed save, kept so `tdo undo` can put back the store it replaced
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UndoRecord {
+    pub operation: String,
+    pub args: Vec<String>,
+    pub saved_at: Timestamp,
+    /// Checksum of the store file before the save, `None` when there was no file yet
+    pub before_checksum: Option<String>,
+    /// Checksum of the store file the save wrote
+    pub after_checksum: String,
+}
+
+impl UndoRecord {
+    /// The command line as the user typed it
+    pub fn command_line(&self) -> String {
+        command_line(&self.args)
+    }
+}
+
+fn command_line(args: &[String]) -> String {
+    std::iter::once("tdo".to_string())
+        .chain(args.iter().cloned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Single-entry intent journal stored next to the store file
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Record that `intent` is about to replace a store file with the given checksum
+    pub fn begin(
+        &self,
+        intent: &Intent,
+        store_checksum: Option<String>,
+    ) -> Result<(), StorageError> {
+        let record = IntentRecord {
+            operation: intent.operation.clone(),
+            args: intent.args.clone(),
+            started_at: Timestamp::now(),
+            store_checksum,
+        };
+
+        let json = serde_json::to_string_pretty(&record)
+            .map_err(|e| StorageError::SerializeFailed { source: e })?;
+        fs::write(&self.path, json).map_err(|e| StorageError::JournalFailed {
+            path: self.path.clone(),
+            source: e,
+        })?;
+
+        debug!(operation = %record.operation, "intent recorded");
+        Ok(())
+    }
+
+    /// Remove the intent record after a successful save
+    pub fn clear(&self) -> Result<(), StorageError> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::JournalFailed {
+                path: self.path.clone(),
+                source: e,
+            }),
+        }
+    }
+
+    /// The intent left behind by an interrupted command, if any
+    pub fn pending(&self) -> Result<Option<IntentRecord>, StorageError> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => {
+                serde_json::from_str(&content)
+                    .map(Some)
+                    .map_err(|e| StorageError::ParseFailed {
+                        path: self.path.clone(),
+                        source: e,
+                    })
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(StorageError::JournalFailed {
+                path: self.path.clone(),
+                source: e,
+            }),
+        }
+    }
+}
+
+/// Recent saves, oldest first, stored next to the store file
+pub struct UndoLog {
+    path: PathBuf,
+}
+
+impl UndoLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn read(&self) -> Result<Vec<UndoRecord>, StorageError> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| StorageError::ParseFailed {
+                path: self.path.clone(),
+                source: e,
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+            Err(e) => Err(StorageError::JournalFailed {
+                path: self.path.clone(),
+                source: e,
+            }),
+        }
+    }
+
+    fn write(&self, records: &[UndoRecord]) -> Result<(), StorageError> {
+        let json = serde_json::to_string_pretty(records)
+            .map_err(|e| StorageError::SerializeFailed { source: e })?;
+        fs::write(&self.path, json).map_err(|e| StorageError::JournalFailed {
+            path: self.path.clone(),
+            source: e,
+        })
+    }
+
+    /// Record a completed save, keeping only the most recent `limit` records
+    pub fn push(&self, record: UndoRecord, limit: usize) -> Result<(), StorageError> {
+        let mut records = self.read()?;
+        records.push(record);
+        let excess = records.len().saturating_sub(limit);
+        records.drain(..excess);
+
+        debug!(count = records.len(), "undo record added");
+        self.write(&records)
+    }
+
+    /// The most recent save
+    pub fn last(&self) -> Result<Option<UndoRecord>, StorageError> {
+        Ok(self.read()?.pop())
+    }
+
+    /// Forget the most recent save once it has been undone
+    pub fn pop(&self) -> Result<(), StorageError> {
+        let mut records = self.read()?;
+        records.pop();
+        self.write(&records)
+    }
+}
+
+/// Checksum of a file's contents, `None` when the file doesn't exist
+pub fn file_checksum(path: &Path) -> Result<Option<String>, StorageError> {
+    match fs::read(path) {
+        Ok(content) => Ok(Some(checksum(&content))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(StorageError::LoadFailed {
+            path: path.to_path_buf(),
+            source: e,
+        }),
+    }
+}
+
+/// FNV-1a: stable across builds, which `DefaultHasher` doesn't promise
+pub fn checksum(content: &[u8]) -> String {
+    let hash = content
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
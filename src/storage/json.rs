@@ -1,24 +1,198 @@
 use std::{
-    fs::{self, OpenOptions, rename, write},
+    fs::{self, File, OpenOptions, rename, write},
     path::{Path, PathBuf},
+    thread,
+    time::Duration,
 };
 
 use fs2::FileExt;
 use serde_json::to_string_pretty;
+use tracing::{debug, trace};
 use uuid::Uuid;
 
 use crate::{
-    models::store::{Store, StoredStore},
-    storage::{Storage, StorageError},
+    models::{
+        store::{IntegrityIssue, Store, StoredStore},
+        task::Task,
+    },
+    storage::{
+        Storage, StorageError,
+        journal::{Intent, IntentRecord, Journal, UndoLog, UndoRecord, checksum, file_checksum},
+    },
 };
 
 pub struct JsonFileStorage {
     path: PathBuf,
+    lock_wait: LockWait,
+    intent: Option<Intent>,
+}
+
+/// What to do when another process holds the save lock
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LockWait {
+    /// Fail right away
+    NoWait,
+    /// Retry with backoff for a couple of seconds, then fail
+    #[default]
+    Retry,
+    /// Block until the lock is released
+    Forever,
+}
+
+/// Delay before the first retry; doubled after every attempt up to the maximum
+const LOCK_RETRY_INITIAL_DELAY: Duration = Duration::from_millis(25);
+const LOCK_RETRY_MAX_DELAY: Duration = Duration::from_millis(400);
+
+/// Give up once this much time has been spent waiting
+const LOCK_RETRY_BUDGET: Duration = Duration::from_secs(2);
+
+/// Number of backups kept; older ones are removed on save. Saves can only be
+/// undone while the backup they replaced is still around, so the undo log
+/// keeps the same number of records.
+const BACKUPS_KEPT: usize = 5;
+
+/// Outcome of loading and checking a single backup file
+pub struct BackupVerification {
+    pub path: PathBuf,
+    pub result: Result<(Store, Vec<IntegrityIssue>), StorageError>,
 }
 
 impl JsonFileStorage {
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            lock_wait: LockWait::default(),
+            intent: None,
+        }
+    }
+
+    pub fn with_lock_wait(mut self, lock_wait: LockWait) -> Self {
+        self.lock_wait = lock_wait;
+        self
+    }
+
+    /// Journal saves as `intent` so an interrupted one can be recovered
+    pub fn with_intent(mut self, intent: Intent) -> Self {
+        self.intent = Some(intent);
+        self
+    }
+
+    fn journal(&self) -> Journal {
+        Journal::new(self.path.with_file_name("journal.json"))
+    }
+
+    fn undo_log(&self) -> UndoLog {
+        UndoLog::new(self.path.with_file_name("undo.json"))
+    }
+
+    /// The save left unfinished by an interrupted command, if any
+    pub fn pending_intent(&self) -> Result<Option<IntentRecord>, StorageError> {
+        self.journal().pending()
+    }
+
+    /// Forget an interrupted save without touching the store
+    pub fn clear_intent(&self) -> Result<(), StorageError> {
+        self.journal().clear()
+    }
+
+    /// Whether the store file changed since `record` was written, i.e. the
+    /// interrupted save did reach the disk
+    pub fn intent_was_applied(&self, record: &IntentRecord) -> Result<bool, StorageError> {
+        Ok(file_checksum(&self.path)? != record.store_checksum)
+    }
+
+    /// The backup holding the store as it was before `record`'s save
+    pub fn backup_before(&self, record: &IntentRecord) -> Result<Option<PathBuf>, StorageError> {
+        match &record.store_checksum {
+            Some(checksum) => self.find_backup(checksum),
+            None => Ok(None),
+        }
+    }
+
+    fn find_backup(&self, checksum: &str) -> Result<Option<PathBuf>, StorageError> {
+        for path in self.list_backups()? {
+            if file_checksum(&path)?.as_deref() == Some(checksum) {
+                return Ok(Some(path));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// The most recent save that `tdo undo` would revert
+    pub fn last_undoable(&self) -> Result<Option<UndoRecord>, StorageError> {
+        self.undo_log().last()
+    }
+
+    /// Whether the store file is still exactly what `record`'s save wrote
+    pub fn is_unchanged_since(&self, record: &UndoRecord) -> Result<bool, StorageError> {
+        Ok(file_checksum(&self.path)?.as_deref() == Some(record.after_checksum.as_str()))
+    }
+
+    /// The store as it was before `record`'s save, `None` when no backup holds it anymore
+    pub fn store_before(&self, record: &UndoRecord) -> Result<Option<Store>, StorageError> {
+        let Some(checksum) = &record.before_checksum else {
+            // The save created the store file
+            return Ok(Some(Store::default()));
+        };
+
+        match self.find_backup(checksum)? {
+            Some(path) => self.load_backup(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Save `previous` in place of `record`'s save, which is then dropped from
+    /// the undo log instead of being recorded as a new undoable save
+    pub fn save_undo(&self, previous: &Store, record: &UndoRecord) -> Result<(), StorageError> {
+        self.write_store(previous, false)?;
+        debug!(operation = %record.operation, "save undone");
+        self.undo_log().pop()
+    }
+
+    /// Take the exclusive save lock according to the configured wait policy
+    fn acquire_lock(&self, lock_file: &File, lock_file_path: &Path) -> Result<(), StorageError> {
+        let save_failed = |e| StorageError::SaveFailed {
+            path: lock_file_path.to_path_buf(),
+            source: e,
+        };
+        let busy = || StorageError::Busy {
+            path: self.path.clone(),
+        };
+
+        if self.lock_wait == LockWait::Forever {
+            debug!(path = %lock_file_path.display(), "waiting for save lock");
+            return lock_file.lock_exclusive().map_err(save_failed);
+        }
+
+        let mut delay = LOCK_RETRY_INITIAL_DELAY;
+        let mut waited = Duration::ZERO;
+
+        loop {
+            match lock_file.try_lock_exclusive() {
+                Ok(()) => {
+                    debug!(waited_ms = waited.as_millis() as u64, "save lock acquired");
+                    return Ok(());
+                }
+                Err(e) if e.kind() != fs2::lock_contended_error().kind() => {
+                    return Err(save_failed(e));
+                }
+                Err(_) if self.lock_wait == LockWait::NoWait || waited >= LOCK_RETRY_BUDGET => {
+                    debug!(
+                        waited_ms = waited.as_millis() as u64,
+                        "save lock busy, giving up"
+                    );
+                    return Err(busy());
+                }
+                Err(_) => {
+                    let step = delay.min(LOCK_RETRY_BUDGET - waited);
+                    trace!(retry_in_ms = step.as_millis() as u64, "save lock busy");
+                    thread::sleep(step);
+                    waited += step;
+                    delay = (delay * 2).min(LOCK_RETRY_MAX_DELAY);
+                }
+            }
+        }
     }
 
     fn create_backup_dir(&self) -> Result<(), StorageError> {
@@ -50,7 +224,10 @@ impl JsonFileStorage {
                 path: backup_path,
                 source: e,
             }),
-            Ok(bytes) => Ok(bytes),
+            Ok(bytes) => {
+                debug!(path = %backup_path.display(), bytes, "backup created");
+                Ok(bytes)
+            }
         }
     }
 
@@ -77,15 +254,13 @@ impl JsonFileStorage {
 
         file_entries.sort();
 
-        let number_of_files_to_delete = match file_entries.len() {
-            x if x > 5 => x - 5,
-            _ => 0,
-        };
+        let number_of_files_to_delete = file_entries.len().saturating_sub(BACKUPS_KEPT);
 
         if number_of_files_to_delete == 0 {
             return Ok(());
         }
 
+        debug!(count = number_of_files_to_delete, "removing old backups");
         for file_path in &file_entries[0..number_of_files_to_delete] {
             fs::remove_file(file_path).map_err(|e| StorageError::CleanupFailed {
                 dir: backup_dir.clone(),
@@ -109,59 +284,185 @@ impl JsonFileStorage {
 
         backups_dir.join(filename)
     }
-}
 
-impl Storage for JsonFileStorage {
-    fn load(&self) -> Result<Store, StorageError> {
+    /// Parse store contents read from `path`, migrating older schema versions
+    fn parse_store(&self, path: &Path, content: &str) -> Result<Store, StorageError> {
         use crate::models::store::CURRENT_VERSION;
         use crate::storage::migrations::{apply_migrations, detect_version};
 
-        match std::fs::read_to_string(&self.path) {
-            Ok(content) => {
-                let file_version = detect_version(&content)?;
+        let file_version = detect_version(content)?;
+        trace!(path = %path.display(), version = file_version, "parsing store");
 
-                if file_version > CURRENT_VERSION {
-                    return Err(StorageError::FutureVersion(file_version));
-                }
+        if file_version > CURRENT_VERSION {
+            return Err(StorageError::FutureVersion(file_version));
+        }
 
-                let mut data: serde_json::Value =
-                    serde_json::from_str(&content).map_err(|e| StorageError::ParseFailed {
-                        path: self.path.clone(),
-                        source: e,
-                    })?;
+        let mut data: serde_json::Value =
+            serde_json::from_str(content).map_err(|e| StorageError::ParseFailed {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
 
-                if file_version < CURRENT_VERSION {
-                    data = apply_migrations(data, file_version, CURRENT_VERSION)?;
-                }
+        if file_version < CURRENT_VERSION {
+            data = apply_migrations(data, file_version, CURRENT_VERSION)?;
+        }
 
-                if let Some(obj) = data.as_object_mut() {
-                    obj.insert("version".to_string(), serde_json::json!(CURRENT_VERSION));
-                }
+        if let Some(obj) = data.as_object_mut() {
+            obj.insert("version".to_string(), serde_json::json!(CURRENT_VERSION));
+        }
+
+        let stored_store: StoredStore =
+            serde_json::from_value(data).map_err(|e| StorageError::ParseFailed {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+
+        // Convert from storage format to working format
+        Ok(Store::from_stored(stored_store))
+    }
+
+    /// List backup files, most recent first
+    pub fn list_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+        let backup_dir = self.get_backup_dir();
+        let backup_dir_exists = fs::exists(&backup_dir).map_err(|e| StorageError::LoadFailed {
+            path: backup_dir.clone(),
+            source: e,
+        })?;
+        if !backup_dir_exists {
+            return Ok(vec![]);
+        }
+
+        let mut file_entries = fs::read_dir(&backup_dir)
+            .map_err(|e| StorageError::LoadFailed {
+                path: backup_dir.clone(),
+                source: e,
+            })?
+            .flatten()
+            .filter(|entry| entry.metadata().map(|m| m.is_file()).unwrap_or(false))
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+
+        file_entries.sort();
+        file_entries.reverse();
+
+        Ok(file_entries)
+    }
+
+    /// Load a backup file the same way the main store is loaded
+    pub fn load_backup(&self, path: &Path) -> Result<Store, StorageError> {
+        let content = fs::read_to_string(path).map_err(|e| StorageError::LoadFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        self.parse_store(path, &content)
+    }
 
-                let stored_store: StoredStore =
-                    serde_json::from_value(data).map_err(|e| StorageError::ParseFailed {
-                        path: self.path.clone(),
-                        source: e,
-                    })?;
+    /// Load every backup and check it for integrity issues
+    pub fn verify_backups(&self) -> Result<Vec<BackupVerification>, StorageError> {
+        Ok(self
+            .list_backups()?
+            .into_iter()
+            .map(|path| {
+                let result = self.load_backup(&path).map(|store| {
+                    let issues = store.integrity_issues();
+                    (store, issues)
+                });
+                BackupVerification { path, result }
+            })
+            .collect())
+    }
 
-                // Convert from storage format to working format
-                Ok(Store::from_stored(stored_store))
+    /// Archive file for tasks completed in `year`, next to the store
+    fn archive_path(&self, year: i16) -> PathBuf {
+        self.path.with_file_name(format!("archive-{}.json", year))
+    }
+
+    /// List archive files, oldest year first
+    fn list_archives(&self) -> Result<Vec<PathBuf>, StorageError> {
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => {
+                return Err(StorageError::LoadFailed {
+                    path: dir,
+                    source: e,
+                });
             }
+        };
+
+        let mut archives: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.strip_prefix("archive-"))
+                    .and_then(|name| name.strip_suffix(".json"))
+                    .is_some_and(|year| year.parse::<i16>().is_ok())
+            })
+            .collect();
+        archives.sort();
+
+        Ok(archives)
+    }
+
+    /// Load an archive file, or an empty one if it doesn't exist yet
+    fn load_archive_file(&self, path: &Path) -> Result<Store, StorageError> {
+        match fs::read_to_string(path) {
+            Ok(content) => self.parse_store(path, &content),
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Store::default()),
             Err(e) => Err(StorageError::LoadFailed {
-                path: self.path.clone(),
+                path: path.to_path_buf(),
                 source: e,
             }),
         }
     }
 
-    fn save(&self, store: &Store) -> Result<(), StorageError> {
+    /// Atomically replace an archive file. Archives hold tasks only, in the
+    /// store format so they are migrated like the store when read.
+    fn write_archive_file(&self, path: &Path, archive: &Store) -> Result<(), StorageError> {
+        let json = to_string_pretty(&archive.to_stored())
+            .map_err(|e| StorageError::SerializeFailed { source: e })?;
+
+        let temp_path = PathBuf::from(format!("{}.tmp.{}", path.display(), Uuid::new_v4()));
+        write(&temp_path, json).map_err(|e| StorageError::SaveFailed {
+            path: temp_path.clone(),
+            source: e,
+        })?;
+        rename(&temp_path, path).map_err(|e| StorageError::SaveFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })
+    }
+
+    /// Atomically replace the store file, recording the save in the undo log
+    /// when `undoable` is set and the save is journaled
+    fn write_store(&self, store: &Store, undoable: bool) -> Result<(), StorageError> {
+        let started = std::time::Instant::now();
+
+        // Never write references to projects or areas that don't exist
+        let broken_references: Vec<String> = store
+            .integrity_issues()
+            .into_iter()
+            .filter(|issue| issue.is_broken_reference())
+            .map(|issue| issue.to_string())
+            .collect();
+        if !broken_references.is_empty() {
+            return Err(StorageError::BrokenReferences(broken_references));
+        }
+
         // Convert from working format to storage format
         let stored_store = store.to_stored();
 
         let json = to_string_pretty(&stored_store)
             .map_err(|e| StorageError::SerializeFailed { source: e })?;
 
+        let after_checksum = checksum(json.as_bytes());
+
         let unique_temp = format!("{}.tmp.{}", self.path.display(), Uuid::new_v4());
         let temp_path = PathBuf::from(&unique_temp);
         write(&temp_path, json).map_err(|e| StorageError::SaveFailed {
@@ -179,12 +480,20 @@ impl Storage for JsonFileStorage {
                 path: lock_file_path.clone(),
                 source: e,
             })?;
-        lock_file
-            .lock_exclusive()
-            .map_err(|e| StorageError::SaveFailed {
-                path: lock_file_path,
-                source: e,
-            })?;
+        if let Err(e) = self.acquire_lock(&lock_file, &lock_file_path) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+
+        // Record what is about to happen before the store file is touched
+        let before_checksum = match &self.intent {
+            Some(intent) => {
+                let before_checksum = file_checksum(&self.path)?;
+                self.journal().begin(intent, before_checksum.clone())?;
+                before_checksum
+            }
+            None => None,
+        };
 
         self.create_backup()?;
         self.cleanup_old_backups()?;
@@ -194,22 +503,110 @@ impl Storage for JsonFileStorage {
             source: e,
         })?;
 
+        if let Some(intent) = &self.intent {
+            self.journal().clear()?;
+
+            // A save that changed nothing isn't worth an undo step
+            if undoable && before_checksum.as_deref() != Some(after_checksum.as_str()) {
+                let record = UndoRecord {
+                    operation: intent.operation.clone(),
+                    args: intent.args.clone(),
+                    saved_at: jiff::Timestamp::now(),
+                    before_checksum,
+                    after_checksum,
+                };
+                self.undo_log().push(record, BACKUPS_KEPT)?;
+            }
+        }
+
         lock_file.unlock().map_err(|e| StorageError::SaveFailed {
             path: self.path.clone(),
             source: e,
         })?;
 
+        debug!(
+            path = %self.path.display(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "store saved"
+        );
+
         Ok(())
     }
 }
 
+impl Storage for JsonFileStorage {
+    fn load(&self) -> Result<Store, StorageError> {
+        let started = std::time::Instant::now();
+        match std::fs::read_to_string(&self.path) {
+            Ok(content) => {
+                let store = self.parse_store(&self.path, &content)?;
+                debug!(
+                    path = %self.path.display(),
+                    bytes = content.len(),
+                    tasks = store.tasks.len(),
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "store loaded"
+                );
+                Ok(store)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                debug!(path = %self.path.display(), "no store file yet, starting empty");
+                Ok(Store::default())
+            }
+            Err(e) => Err(StorageError::LoadFailed {
+                path: self.path.clone(),
+                source: e,
+            }),
+        }
+    }
+
+    fn save(&self, store: &Store) -> Result<(), StorageError> {
+        self.write_store(store, true)
+    }
+
+    /// Tasks go to `archive-YYYY.json` by the year they were completed in
+    fn archive(&self, tasks: &[Task]) -> Result<(), StorageError> {
+        let mut by_year: std::collections::BTreeMap<i16, Vec<&Task>> = Default::default();
+        for task in tasks {
+            let completed_at = task.completed_at.unwrap_or(task.created_at);
+            let year = jiff::Zoned::new(completed_at, jiff::tz::TimeZone::system()).year();
+            by_year.entry(year).or_default().push(task);
+        }
+
+        for (year, tasks) in by_year {
+            let path = self.archive_path(year);
+            let mut archive = self.load_archive_file(&path)?;
+            for task in tasks {
+                archive.tasks.insert(task.id, task.clone());
+            }
+            self.write_archive_file(&path, &archive)?;
+            debug!(path = %path.display(), tasks = archive.tasks.len(), "archive saved");
+        }
+
+        Ok(())
+    }
+
+    fn load_archive(&self) -> Result<Vec<Task>, StorageError> {
+        let mut tasks = vec![];
+        for path in self.list_archives()? {
+            tasks.extend(self.load_archive_file(&path)?.tasks.into_values());
+        }
+        Ok(tasks)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
 
     use crate::{
-        models::{area::Area, project::Project, store::Store, task::Task},
+        models::{
+            area::Area,
+            project::Project,
+            store::{Orphan, Store},
+            task::Task,
+        },
         storage::json::JsonFileStorage,
     };
 
@@ -240,10 +637,8 @@ mod tests {
         store.add_project(project);
         store.add_task(task);
 
-        let json_file_storage = JsonFileStorage {
-            path: PathBuf::from("/tmp/test_store.json"),
-        };
-        if let Err(_) = json_file_storage.save(&store) {
+        let json_file_storage = JsonFileStorage::new(PathBuf::from("/tmp/test_store.json"));
+        if json_file_storage.save(&store).is_err() {
             panic!("Should correctly save the store");
         }
         match json_file_storage.load() {
@@ -329,8 +724,10 @@ mod tests {
         let storage = JsonFileStorage::new(store_path.clone());
 
         for i in 1..=7 {
-            let mut store = Store::default();
-            store.version = i;
+            let mut store = Store {
+                version: i,
+                ..Store::default()
+            };
 
             // Add a unique task to make each save different
             let task = Task {
@@ -376,8 +773,10 @@ mod tests {
             "Backups dir should not exist after first save"
         );
 
-        let mut store2 = Store::default();
-        store2.version = 2;
+        let mut store2 = Store {
+            version: 2,
+            ..Store::default()
+        };
 
         // Add a task to make it different from first save
         let task = Task {
@@ -429,7 +828,7 @@ mod tests {
         let storage = JsonFileStorage::new(path);
         let store = storage.load().expect("Migration should succeed");
 
-        assert_eq!(store.version, 2);
+        assert_eq!(store.version, crate::models::store::CURRENT_VERSION);
         assert_eq!(store.next_task_number, 3);
 
         // "First task" (earlier created_at) gets task_number 1
@@ -467,4 +866,247 @@ mod tests {
         assert!(store.get_task_by_number(1).is_none());
         assert!(store.get_task_by_number(999).is_none());
     }
+
+    #[test]
+    fn test_verify_backups_reports_corrupt_backup() {
+        let test_dir = PathBuf::from("/tmp/tdo_backup_verify_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+
+        let storage = JsonFileStorage::new(test_dir.join("store.json"));
+
+        for i in 1..=3 {
+            let mut store = Store::default();
+            store.add_task(Task {
+                title: format!("Task {}", i),
+                ..Task::default()
+            });
+            storage.save(&store).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let backups = storage.list_backups().unwrap();
+        assert_eq!(backups.len(), 2);
+
+        // Corrupt the oldest backup
+        fs::write(&backups[1], "{ not json").unwrap();
+
+        let verifications = storage.verify_backups().unwrap();
+        assert_eq!(verifications.len(), 2);
+        assert!(matches!(&verifications[0].result, Ok((_, issues)) if issues.is_empty()));
+        assert!(matches!(
+            verifications[1].result,
+            Err(StorageError::ParseFailed { .. })
+        ));
+
+        fs::remove_dir_all(&test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_archive_groups_by_year_and_replaces_archived_tasks() {
+        let test_dir = PathBuf::from("/tmp/tdo_archive_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+
+        let storage = JsonFileStorage::new(test_dir.join("store.json"));
+        let completed = |title: &str, at: &str| Task {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            completed_at: Some(at.parse().unwrap()),
+            ..Task::default()
+        };
+        let old = completed("Old", "2024-06-01T12:00:00Z");
+        let recent = completed("Recent", "2025-06-01T12:00:00Z");
+
+        storage.archive(&[old.clone(), recent]).unwrap();
+        // Archiving a task again replaces it instead of duplicating it
+        storage.archive(&[old]).unwrap();
+
+        assert_eq!(storage.list_archives().unwrap().len(), 2);
+        let mut titles: Vec<_> = storage
+            .load_archive()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.title)
+            .collect();
+        titles.sort();
+        assert_eq!(titles, ["Old", "Recent"]);
+
+        fs::remove_dir_all(&test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_rejects_broken_references() {
+        let test_dir = PathBuf::from("/tmp/tdo_broken_reference_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+
+        let storage = JsonFileStorage::new(test_dir.join("store.json"));
+
+        let mut store = Store::default();
+        store.add_task(Task {
+            title: String::from("Task in a project that does not exist"),
+            project_id: Some(Uuid::new_v4()),
+            ..Task::default()
+        });
+
+        assert!(matches!(
+            storage.save(&store),
+            Err(StorageError::BrokenReferences(_))
+        ));
+        assert!(!test_dir.join("store.json").exists());
+
+        fs::remove_dir_all(&test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_reports_busy_when_lock_is_held() {
+        let test_dir = PathBuf::from("/tmp/tdo_busy_lock_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+
+        let store_path = test_dir.join("store.json");
+        let storage = JsonFileStorage::new(store_path.clone()).with_lock_wait(LockWait::NoWait);
+
+        // Another handle holding the lock stands in for a concurrent process
+        let holder = File::create(store_path.with_extension("lock")).unwrap();
+        holder.lock_exclusive().unwrap();
+
+        let result = storage.save(&Store::default());
+        assert!(matches!(result, Err(StorageError::Busy { .. })));
+        assert!(!store_path.exists());
+
+        holder.unlock().unwrap();
+        storage.save(&Store::default()).unwrap();
+        assert!(store_path.exists());
+
+        fs::remove_dir_all(&test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_journal_detects_interrupted_save() {
+        let test_dir = PathBuf::from("/tmp/tdo_journal_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+
+        let store_path = test_dir.join("store.json");
+        let intent = Intent {
+            operation: String::from("add"),
+            args: vec![String::from("add"), String::from("Buy milk")],
+        };
+        let storage = JsonFileStorage::new(store_path.clone()).with_intent(intent.clone());
+
+        // A completed save leaves nothing behind
+        storage.save(&Store::default()).unwrap();
+        assert!(storage.pending_intent().unwrap().is_none());
+
+        // Interrupted before the store was replaced
+        let journal = storage.journal();
+        journal
+            .begin(&intent, file_checksum(&store_path).unwrap())
+            .unwrap();
+        let record = storage.pending_intent().unwrap().unwrap();
+        assert_eq!(record.command_line(), "tdo add Buy milk");
+        assert!(!storage.intent_was_applied(&record).unwrap());
+
+        // Interrupted after the store was replaced: the previous store is in the backups
+        let mut store = Store::default();
+        store.add_task(Task {
+            title: String::from("Buy milk"),
+            ..Task::default()
+        });
+        JsonFileStorage::new(store_path.clone())
+            .save(&store)
+            .unwrap();
+        assert!(storage.intent_was_applied(&record).unwrap());
+        let backup = storage.backup_before(&record).unwrap().unwrap();
+        assert!(storage.load_backup(&backup).unwrap().tasks.is_empty());
+
+        storage.clear_intent().unwrap();
+        assert!(storage.pending_intent().unwrap().is_none());
+
+        fs::remove_dir_all(&test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_undo_restores_store_before_last_save() {
+        let test_dir = PathBuf::from("/tmp/tdo_undo_test");
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).unwrap();
+
+        let storage = JsonFileStorage::new(test_dir.join("store.json")).with_intent(Intent {
+            operation: String::from("add"),
+            args: vec![String::from("add"), String::from("Buy milk")],
+        });
+
+        let mut store = Store::default();
+        storage.save(&store).unwrap();
+        store.add_task(Task {
+            title: String::from("Buy milk"),
+            ..Task::default()
+        });
+        storage.save(&store).unwrap();
+        // Saving the same store again isn't recorded
+        storage.save(&store).unwrap();
+
+        let record = storage.last_undoable().unwrap().unwrap();
+        assert_eq!(record.command_line(), "tdo add Buy milk");
+        assert!(storage.is_unchanged_since(&record).unwrap());
+
+        let previous = storage.store_before(&record).unwrap().unwrap();
+        let changes = store.changes_to(&previous);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].to_string(), "Task #1 'Buy milk' removed");
+
+        storage.save_undo(&previous, &record).unwrap();
+        assert!(storage.load().unwrap().tasks.is_empty());
+
+        // The first save created the file, so undoing it leaves an empty store
+        let record = storage.last_undoable().unwrap().unwrap();
+        assert!(record.before_checksum.is_none());
+        storage.save_undo(&Store::default(), &record).unwrap();
+        assert!(storage.last_undoable().unwrap().is_none());
+
+        fs::remove_dir_all(&test_dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_orphans_in_deleted_containers() {
+        let area = Area {
+            id: Uuid::new_v4(),
+            name: String::from("Old Job"),
+            deleted_at: Some(jiff::Timestamp::now()),
+            ..Area::default()
+        };
+        let project = Project {
+            id: Uuid::new_v4(),
+            name: String::from("Restored Project"),
+            area_id: Some(area.id),
+            ..Project::default()
+        };
+        let task = Task {
+            id: Uuid::new_v4(),
+            title: String::from("Task added later"),
+            area_id: Some(area.id),
+            ..Task::default()
+        };
+
+        let mut store = Store::default();
+        store.add_area(area);
+        store.add_project(project);
+        store.add_task(task);
+
+        let orphans = store.find_orphans();
+        assert_eq!(orphans.len(), 2);
+        assert!(
+            orphans
+                .iter()
+                .any(|o| matches!(o, Orphan::ProjectInDeletedArea { .. }))
+        );
+        assert!(
+            orphans
+                .iter()
+                .any(|o| matches!(o, Orphan::TaskInDeletedArea { .. }))
+        );
+    }
 }
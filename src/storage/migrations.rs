@@ -7,7 +7,32 @@ use crate::storage::StorageError;
 type MigrationFn = fn(Value) -> Result<Value, StorageError>;
 
 fn get_migrations() -> Vec<MigrationFn> {
-    vec![migrate_v1_to_v2, migrate_v2_to_v3]
+    vec![
+        migrate_v1_to_v2,
+        migrate_v2_to_v3,
+        migrate_v3_to_v4,
+        migrate_v4_to_v5,
+        migrate_v5_to_v6,
+        migrate_v6_to_v7,
+        migrate_v7_to_v8,
+        migrate_v8_to_v9,
+        migrate_v9_to_v10,
+        migrate_v10_to_v11,
+        migrate_v11_to_v12,
+        migrate_v12_to_v13,
+        migrate_v13_to_v14,
+        migrate_v14_to_v15,
+        migrate_v15_to_v16,
+        migrate_v16_to_v17,
+        migrate_v17_to_v18,
+        migrate_v18_to_v19,
+        migrate_v19_to_v20,
+        migrate_v20_to_v21,
+        migrate_v21_to_v22,
+        migrate_v22_to_v23,
+        migrate_v23_to_v24,
+        migrate_v24_to_v25,
+    ]
 }
 
 fn migrate_v1_to_v2(mut value: Value) -> Result<Value, StorageError> {
@@ -72,6 +97,441 @@ fn migrate_v2_to_v3(mut value: Value) -> Result<Value, StorageError> {
     Ok(value)
 }
 
+fn migrate_v3_to_v4(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(4));
+
+        // Add energy: null to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("energy".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v4_to_v5(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(5));
+
+        // Add context: null to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("context".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v5_to_v6(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(6));
+
+        // Add tags: [] to all projects
+        if let Some(projects) = obj.get_mut("projects").and_then(|p| p.as_array_mut()) {
+            for project in projects {
+                if let Some(project_obj) = project.as_object_mut() {
+                    project_obj.insert("tags".to_string(), Value::Array(Vec::new()));
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v6_to_v7(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(7));
+
+        // Add snoozed_until: null to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("snoozed_until".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v7_to_v8(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(8));
+
+        // Add reminder: null to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("reminder".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v8_to_v9(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(9));
+
+        // Rewrite every task's `when` in the internally tagged form
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    let when = task_obj.remove("when").unwrap_or(Value::Null);
+                    task_obj.insert("when".to_string(), normalize_when(when));
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v9_to_v10(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(10));
+
+        // Add pinned: false to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("pinned".to_string(), Value::Bool(false));
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v10_to_v11(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(11));
+
+        // Add estimate: null to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("estimate".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v11_to_v12(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(12));
+
+        // Add time_entries: [] to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("time_entries".to_string(), Value::Array(vec![]));
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v12_to_v13(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(13));
+
+        // Add history: [] to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("history".to_string(), Value::Array(vec![]));
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v13_to_v14(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(14));
+
+        // Add next_action: null to all projects
+        if let Some(projects) = obj.get_mut("projects").and_then(|p| p.as_array_mut()) {
+            for project in projects {
+                if let Some(project_obj) = project.as_object_mut() {
+                    project_obj.insert("next_action".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v14_to_v15(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(15));
+
+        // Add attachments: [] to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("attachments".to_string(), Value::Array(vec![]));
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v15_to_v16(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(16));
+
+        // Add blocked_by: [] to all projects
+        if let Some(projects) = obj.get_mut("projects").and_then(|p| p.as_array_mut()) {
+            for project in projects {
+                if let Some(project_obj) = project.as_object_mut() {
+                    project_obj.insert("blocked_by".to_string(), Value::Array(vec![]));
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v16_to_v17(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(17));
+
+        // Add incubated_at: null to all projects
+        if let Some(projects) = obj.get_mut("projects").and_then(|p| p.as_array_mut()) {
+            for project in projects {
+                if let Some(project_obj) = project.as_object_mut() {
+                    project_obj.insert("incubated_at".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v17_to_v18(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(18));
+
+        // Add idempotency_key: null to all tasks
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                if let Some(task_obj) = task.as_object_mut() {
+                    task_obj.insert("idempotency_key".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v18_to_v19(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(19));
+
+        // Add evening: false to scheduled dates, including those in the history
+        if let Some(tasks) = obj.get_mut("tasks").and_then(|t| t.as_array_mut()) {
+            for task in tasks {
+                let Some(task_obj) = task.as_object_mut() else {
+                    continue;
+                };
+                if let Some(when) = task_obj.get_mut("when") {
+                    add_scheduled_evening(when);
+                }
+                if let Some(history) = task_obj.get_mut("history").and_then(|h| h.as_array_mut()) {
+                    for event in history {
+                        if let Some(change) = event.get_mut("change") {
+                            for key in ["from", "to"] {
+                                if let Some(when) = change.get_mut(key) {
+                                    add_scheduled_evening(when);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v19_to_v20(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(20));
+
+        // Add archived_at: null to all projects
+        if let Some(projects) = obj.get_mut("projects").and_then(|p| p.as_array_mut()) {
+            for project in projects {
+                if let Some(project_obj) = project.as_object_mut() {
+                    project_obj.insert("archived_at".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v20_to_v21(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(21));
+
+        // Add sort_order: null to all projects
+        if let Some(projects) = obj.get_mut("projects").and_then(|p| p.as_array_mut()) {
+            for project in projects {
+                if let Some(project_obj) = project.as_object_mut() {
+                    project_obj.insert("sort_order".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v21_to_v22(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(22));
+
+        // Add color: null and icon: null to all projects and areas
+        for key in ["projects", "areas"] {
+            if let Some(containers) = obj.get_mut(key).and_then(|c| c.as_array_mut()) {
+                for container in containers {
+                    if let Some(container_obj) = container.as_object_mut() {
+                        container_obj.insert("color".to_string(), Value::Null);
+                        container_obj.insert("icon".to_string(), Value::Null);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v22_to_v23(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(23));
+
+        // Add notes: null to all areas
+        if let Some(areas) = obj.get_mut("areas").and_then(|a| a.as_array_mut()) {
+            for area in areas {
+                if let Some(area_obj) = area.as_object_mut() {
+                    area_obj.insert("notes".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v23_to_v24(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(24));
+
+        // Add sort_order: null to all areas
+        if let Some(areas) = obj.get_mut("areas").and_then(|a| a.as_array_mut()) {
+            for area in areas {
+                if let Some(area_obj) = area.as_object_mut() {
+                    area_obj.insert("sort_order".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn migrate_v24_to_v25(mut value: Value) -> Result<Value, StorageError> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), Value::from(25));
+
+        // Add archived_at: null to all areas
+        if let Some(areas) = obj.get_mut("areas").and_then(|a| a.as_array_mut()) {
+            for area in areas {
+                if let Some(area_obj) = area.as_object_mut() {
+                    area_obj.insert("archived_at".to_string(), Value::Null);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+/// Give a `{"type": "Scheduled", ...}` when the `evening` flag it gained in v19
+fn add_scheduled_evening(when: &mut Value) {
+    if when["type"] == "Scheduled"
+        && let Some(when_obj) = when.as_object_mut()
+    {
+        when_obj.insert("evening".to_string(), Value::Bool(false));
+    }
+}
+
+/// Convert older `when` encodings to `{"type": ..., ...}`:
+/// bare variant names (`"Inbox"`), externally tagged variants
+/// (`{"Scheduled": "2025-03-01"}`, `{"Today": {"evening": true}}`)
+/// and `Today` without its `evening` flag. A missing `when` means Inbox.
+fn normalize_when(when: Value) -> Value {
+    let (kind, mut fields) = match when {
+        Value::String(kind) => (kind, serde_json::Map::new()),
+        Value::Object(mut obj) => match obj.remove("type") {
+            Some(Value::String(kind)) => (kind, obj),
+            _ if obj.len() == 1 => {
+                let (kind, content) = obj.into_iter().next().expect("one entry");
+                let fields = match content {
+                    Value::Object(fields) => fields,
+                    // Tuple variant: `{"Scheduled": "2025-03-01"}`
+                    Value::String(date) if kind == "Scheduled" => {
+                        serde_json::Map::from_iter([("date".to_string(), Value::String(date))])
+                    }
+                    _ => serde_json::Map::new(),
+                };
+                (kind, fields)
+            }
+            _ => ("Inbox".to_string(), serde_json::Map::new()),
+        },
+        _ => ("Inbox".to_string(), serde_json::Map::new()),
+    };
+
+    if kind == "Today" && !fields.contains_key("evening") {
+        fields.insert("evening".to_string(), Value::Bool(false));
+    }
+
+    fields.insert("type".to_string(), Value::String(kind));
+    Value::Object(fields)
+}
+
 /// Returns 1 if version field is missing (assumes v1, our first versioned schema)
 pub fn detect_version(content: &str) -> Result<u32, StorageError> {
     let value: Value = serde_json::from_str(content).map_err(|e| StorageError::ParseFailed {
@@ -116,6 +576,7 @@ pub fn apply_migrations(
             return Err(StorageError::UnsupportedVersion(version));
         }
 
+        tracing::debug!(from = version, to = version + 1, "applying migration");
         data = migrations[migration_idx](data)?;
     }
 
@@ -151,4 +612,136 @@ mod tests {
         let result = apply_migrations(data, 5, 1);
         assert!(matches!(result, Err(StorageError::FutureVersion(5))));
     }
+
+    #[test]
+    fn test_v5_to_v6_adds_project_tags() {
+        let data = serde_json::json!({
+            "version": 5,
+            "tasks": [],
+            "projects": [{"name": "Website"}],
+            "areas": []
+        });
+        let result = apply_migrations(data, 5, 6).unwrap();
+        assert_eq!(result["version"], 6);
+        assert_eq!(result["projects"][0]["tags"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_v18_to_v19_adds_scheduled_evening() {
+        use serde_json::json;
+
+        let scheduled = json!({"type": "Scheduled", "date": "2025-03-01"});
+        let data = json!({
+            "version": 18,
+            "tasks": [{
+                "when": scheduled,
+                "history": [
+                    {"change": {"type": "Created"}},
+                    {"change": {"type": "Rescheduled", "from": {"type": "Inbox"}, "to": scheduled}}
+                ]
+            }],
+            "projects": [],
+            "areas": []
+        });
+        let result = apply_migrations(data, 18, 19).unwrap();
+        let evening = json!({"type": "Scheduled", "date": "2025-03-01", "evening": false});
+        assert_eq!(result["tasks"][0]["when"], evening);
+        assert_eq!(
+            result["tasks"][0]["history"][1]["change"]["from"],
+            json!({"type": "Inbox"})
+        );
+        assert_eq!(result["tasks"][0]["history"][1]["change"]["to"], evening);
+    }
+
+    #[test]
+    fn test_normalize_when_encodings() {
+        use serde_json::json;
+
+        assert_eq!(normalize_when(json!("Someday")), json!({"type": "Someday"}));
+        assert_eq!(normalize_when(Value::Null), json!({"type": "Inbox"}));
+        assert_eq!(
+            normalize_when(json!({"Scheduled": "2025-03-01"})),
+            json!({"type": "Scheduled", "date": "2025-03-01"})
+        );
+        assert_eq!(
+            normalize_when(json!({"Today": {"evening": true}})),
+            json!({"type": "Today", "evening": true})
+        );
+        assert_eq!(
+            normalize_when(json!({"type": "Today"})),
+            json!({"type": "Today", "evening": false})
+        );
+        assert_eq!(
+            normalize_when(json!({"type": "Scheduled", "date": "2025-03-01"})),
+            json!({"type": "Scheduled", "date": "2025-03-01"})
+        );
+    }
+
+    /// One store written by each historical schema version
+    const FIXTURES: [(u32, &str); 24] = [
+        (1, include_str!("fixtures/store_v1.json")),
+        (2, include_str!("fixtures/store_v2.json")),
+        (3, include_str!("fixtures/store_v3.json")),
+        (4, include_str!("fixtures/store_v4.json")),
+        (5, include_str!("fixtures/store_v5.json")),
+        (6, include_str!("fixtures/store_v6.json")),
+        (7, include_str!("fixtures/store_v7.json")),
+        (8, include_str!("fixtures/store_v8.json")),
+        (9, include_str!("fixtures/store_v9.json")),
+        (10, include_str!("fixtures/store_v10.json")),
+        (11, include_str!("fixtures/store_v11.json")),
+        (12, include_str!("fixtures/store_v12.json")),
+        (13, include_str!("fixtures/store_v13.json")),
+        (14, include_str!("fixtures/store_v14.json")),
+        (15, include_str!("fixtures/store_v15.json")),
+        (16, include_str!("fixtures/store_v16.json")),
+        (17, include_str!("fixtures/store_v17.json")),
+        (18, include_str!("fixtures/store_v18.json")),
+        (19, include_str!("fixtures/store_v19.json")),
+        (20, include_str!("fixtures/store_v20.json")),
+        (21, include_str!("fixtures/store_v21.json")),
+        (22, include_str!("fixtures/store_v22.json")),
+        (23, include_str!("fixtures/store_v23.json")),
+        (24, include_str!("fixtures/store_v24.json")),
+    ];
+
+    #[test]
+    fn test_every_historical_schema_migrates_and_parses() {
+        use crate::models::{
+            store::{CURRENT_VERSION, Store, StoredStore},
+            task::When,
+        };
+
+        for (version, content) in FIXTURES {
+            assert_eq!(detect_version(content).unwrap(), version);
+
+            let data: Value = serde_json::from_str(content).unwrap();
+            let migrated = apply_migrations(data, version, CURRENT_VERSION)
+                .unwrap_or_else(|e| panic!("v{} failed to migrate: {}", version, e));
+            assert_eq!(migrated["version"], CURRENT_VERSION);
+
+            let stored: StoredStore = serde_json::from_value(migrated)
+                .unwrap_or_else(|e| panic!("v{} failed to parse: {}", version, e));
+            let store = Store::from_stored(stored);
+
+            assert_eq!(store.tasks.len(), 3, "v{}", version);
+            assert_eq!(store.next_task_number, 4, "v{}", version);
+
+            let scheduled = store.get_task_by_number(1).unwrap();
+            assert_eq!(scheduled.title, "Write landing page");
+            assert!(
+                matches!(scheduled.when, When::Scheduled { date, evening: false } if date.to_string() == "2025-06-10"),
+                "v{}",
+                version
+            );
+            assert_eq!(scheduled.checklist.len(), 1);
+
+            let evening = store.get_task_by_number(3).unwrap();
+            assert!(
+                matches!(evening.when, When::Today { evening: true }),
+                "v{}",
+                version
+            );
+        }
+    }
 }
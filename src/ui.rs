@@ -1,9 +1,138 @@
-use std::{fmt::format, str::MatchIndices};
+use std::{
+    collections::HashMap,
+    io::IsTerminal,
+    sync::atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering},
+};
 
 use colored::*;
 use jiff::civil::Date;
 
-use crate::models::{store::Store, task::Task};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    project::Project,
+    store::Store,
+    task::{Task, TaskChange, When},
+};
+
+/// Whether output goes to a person at a terminal (colors, alignment, prompts)
+static INTERACTIVE: AtomicBool = AtomicBool::new(true);
+
+/// Whether task lines are followed by the first line of their notes
+static SHOW_NOTES: AtomicBool = AtomicBool::new(false);
+
+/// Whether long titles wrap onto continuation lines instead of being truncated
+static FULL_TITLES: AtomicBool = AtomicBool::new(false);
+
+/// The `Palette` status cues are drawn with, stored as its discriminant
+static PALETTE: AtomicU8 = AtomicU8::new(Palette::Default as u8);
+
+/// Days an Inbox or Anytime task can go untouched before its age is shown; 0 is off
+static STALE_AFTER_DAYS: AtomicU32 = AtomicU32::new(0);
+
+/// Colors for status cues such as overdue tasks and passed checks
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Palette {
+    /// Red for problems, green for success
+    #[default]
+    Default,
+    /// Vermillion and blue, told apart without red-green vision
+    Deuteranopia,
+    /// Orange and blue; reds look dark to protanopes so a brighter warm color is used
+    Protanopia,
+}
+
+impl Palette {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Palette::Deuteranopia,
+            2 => Palette::Protanopia,
+            _ => Palette::Default,
+        }
+    }
+}
+
+/// Select the palette for status cues, from the `palette` config setting
+pub fn set_palette(palette: Palette) {
+    PALETTE.store(palette as u8, Ordering::Relaxed);
+}
+
+/// Show the age of tasks left untouched this long, from the `stale_after_days` config setting
+pub fn set_stale_after_days(days: Option<u32>) {
+    STALE_AFTER_DAYS.store(days.unwrap_or(0), Ordering::Relaxed);
+}
+
+fn palette() -> Palette {
+    Palette::from_u8(PALETTE.load(Ordering::Relaxed))
+}
+
+/// Whether the terminal advertises 24-bit color. Without it `colored` maps
+/// RGB values to the nearest basic color, which turns vermillion back into red.
+fn supports_truecolor() -> bool {
+    std::env::var("COLORTERM").is_ok_and(|value| value == "truecolor" || value == "24bit")
+}
+
+/// A project or area color name as stored, e.g. "bright magenta" for
+/// "Bright_Magenta"; `None` for names the terminal colors don't include
+pub fn normalize_color(name: &str) -> Option<String> {
+    let name = name.trim().to_lowercase().replace(['_', '-'], " ");
+    name.parse::<Color>().ok().map(|_| name)
+}
+
+/// Style text that needs attention: overdue tasks, close deadlines, failures.
+/// The color-blind palettes also make it bold so it doesn't rely on hue alone.
+pub fn alert(text: &str) -> ColoredString {
+    match (palette(), supports_truecolor()) {
+        (Palette::Default, _) => text.red(),
+        (Palette::Deuteranopia, true) => text.truecolor(213, 94, 0).bold(),
+        (Palette::Protanopia, true) => text.truecolor(230, 159, 0).bold(),
+        (_, false) => text.bright_yellow().bold(),
+    }
+}
+
+/// Style text that marks something as done or fine
+pub fn success(text: &str) -> ColoredString {
+    match (palette(), supports_truecolor()) {
+        (Palette::Default, _) => text.green(),
+        (_, true) => text.truecolor(86, 180, 233),
+        (_, false) => text.bright_blue(),
+    }
+}
+
+/// Global display options, set once from the command line
+pub struct OutputOptions {
+    /// Keep terminal behaviour even when stdout is not a terminal
+    pub force_interactive: bool,
+    /// Print the first line of notes beneath each task
+    pub show_notes: bool,
+    /// Wrap long titles instead of truncating them to the terminal width
+    pub full_titles: bool,
+}
+
+/// Detect whether stdout is a terminal and configure output accordingly.
+/// When it isn't, colors and terminal-width padding are disabled so piped
+/// output stays plain and stable. `force_interactive` restores the terminal
+/// behaviour regardless.
+pub fn init_output(options: OutputOptions) {
+    let force_interactive = options.force_interactive;
+    let interactive = force_interactive || std::io::stdout().is_terminal();
+    INTERACTIVE.store(interactive, Ordering::Relaxed);
+    SHOW_NOTES.store(options.show_notes, Ordering::Relaxed);
+    FULL_TITLES.store(options.full_titles, Ordering::Relaxed);
+
+    if force_interactive {
+        colored::control::set_override(true);
+    } else if !interactive {
+        colored::control::set_override(false);
+    }
+}
+
+/// Whether the CLI may prompt and render terminal-specific formatting
+pub fn is_interactive() -> bool {
+    INTERACTIVE.load(Ordering::Relaxed)
+}
 
 /// Get the terminal width, defaulting to 80 if unavailable
 fn get_terminal_width() -> usize {
@@ -14,8 +143,14 @@ fn get_terminal_width() -> usize {
 pub fn get_status_glyph(task: &Task, is_overdue: bool) -> ColoredString {
     if task.completed_at.is_some() {
         "✓".dimmed()
+    } else if task.pinned {
+        if is_overdue {
+            alert("★")
+        } else {
+            "★".yellow()
+        }
     } else if is_overdue {
-        "●".red()
+        alert("●")
     } else {
         "○".normal()
     }
@@ -24,25 +159,68 @@ pub fn get_status_glyph(task: &Task, is_overdue: bool) -> ColoredString {
 /// Build the context string for a task (Area/Project hierarchy)
 /// Returns None if task has no area or project associations
 pub fn get_task_context(task: &Task, store: &Store) -> Option<String> {
-    if let Some(project_id) = task.project_id {
-        if let Some(project) = store.get_project(project_id) {
-            if let Some(area_id) = project.area_id {
-                if let Some(area) = store.get_area(area_id) {
-                    // Rule A: {Area Name} / {Project Name}
-                    return Some(format!("{} / {}", area.name, project.name));
-                }
-            }
-            return Some(project.name.clone());
+    let area = store
+        .get_task_area_id(task)
+        .and_then(|area_id| store.get_area(area_id));
+
+    if let Some(project_id) = task.project_id
+        && let Some(project) = store.get_project(project_id)
+    {
+        if let Some(area) = area {
+            // Rule A: {Area Name} / {Project Name}, where the area may be a task-level override
+            return Some(format!("{} / {}", area.name, project.name));
         }
+        return Some(project.name.clone());
     }
 
-    if let Some(area_id) = task.area_id {
-        if let Some(area) = store.get_area(area_id) {
-            return Some(area.name.clone());
-        }
+    area.map(|area| area.name.clone())
+}
+
+/// `get_task_context` with each project and area behind its icon and in its
+/// color, or dimmed when it has none: (plain text for measuring, styled text)
+fn styled_task_context(task: &Task, store: &Store) -> Option<(String, String)> {
+    let area = store
+        .get_task_area_id(task)
+        .and_then(|area_id| store.get_area(area_id))
+        .map(|a| container_label(&a.name, a.color.as_deref(), a.icon.as_deref()));
+    let project = task
+        .project_id
+        .and_then(|project_id| store.get_project(project_id))
+        .map(|p| container_label(&p.name, p.color.as_deref(), p.icon.as_deref()));
+
+    let labels: Vec<_> = area.into_iter().chain(project).collect();
+    if labels.is_empty() {
+        return None;
+    }
+    let plain: Vec<_> = labels.iter().map(|(plain, _)| plain.as_str()).collect();
+    let styled: Vec<_> = labels.iter().map(|(_, styled)| styled.as_str()).collect();
+    Some((plain.join(" / "), styled.join(&" / ".dimmed().to_string())))
+}
+
+/// A project or area's color and icon as set, e.g. "🚀 · blue" shown in blue
+pub fn format_label(color: Option<&str>, icon: Option<&str>) -> Option<String> {
+    let parts: Vec<&str> = icon.into_iter().chain(color).collect();
+    if parts.is_empty() {
+        return None;
     }
+    let label = parts.join(" · ");
+    Some(match color.and_then(|c| c.parse::<Color>().ok()) {
+        Some(color) => label.color(color).to_string(),
+        None => label,
+    })
+}
 
-    None
+/// A project or area name with its icon, plain and styled
+fn container_label(name: &str, color: Option<&str>, icon: Option<&str>) -> (String, String) {
+    let plain = match icon {
+        Some(icon) => format!("{} {}", icon, name),
+        None => name.to_string(),
+    };
+    let styled = match color.and_then(|c| c.parse::<Color>().ok()) {
+        Some(color) => plain.color(color).to_string(),
+        None => plain.dimmed().to_string(),
+    };
+    (plain, styled)
 }
 
 /// Render a single task line with ID, glyph, title, and right-aligned context
@@ -55,6 +233,85 @@ pub fn render_task_line_with_completion_date(task: &Task, store: &Store, is_over
     render_task_line_with_options(task, store, is_overdue, true);
 }
 
+/// Maximum visible width of the inline tag list before it gets truncated
+const MAX_TAGS_WIDTH: usize = 24;
+
+/// Format a task's tags for inline display (e.g., "#work #urgent +2")
+/// Returns None if the task has no tags
+pub fn format_tags(tags: &[String]) -> Option<String> {
+    if tags.is_empty() {
+        return None;
+    }
+
+    let mut shown: Vec<String> = Vec::new();
+    let mut width = 0;
+
+    for (index, tag) in tags.iter().enumerate() {
+        let label = truncate_with_ellipsis(&format!("#{}", tag), MAX_TAGS_WIDTH);
+
+        let separator = if shown.is_empty() { 0 } else { 1 };
+        let remaining = tags.len() - index;
+        if !shown.is_empty() && width + separator + label.chars().count() > MAX_TAGS_WIDTH {
+            shown.push(format!("+{}", remaining));
+            break;
+        }
+
+        width += separator + label.chars().count();
+        shown.push(label);
+    }
+
+    Some(shown.join(" "))
+}
+
+/// Titles are never truncated below this many characters, even on narrow terminals
+const MIN_TITLE_WIDTH: usize = 20;
+
+/// Shorten text to at most `width` characters, ending in "…" when cut
+fn truncate_with_ellipsis(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Break text into lines of at most `width` characters at word boundaries,
+/// splitting words that don't fit on a line of their own
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+
+        while word.len() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..width).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+
+        if !line.is_empty() && line.chars().count() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.extend(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
 /// Internal function to render a task line with various options
 fn render_task_line_with_options(
     task: &Task,
@@ -64,14 +321,21 @@ fn render_task_line_with_options(
 ) {
     let terminal_width = get_terminal_width();
 
+    // Snoozed tasks are never highlighted as overdue or urgent
+    let snoozed = task.is_snoozed();
+    let is_overdue = is_overdue && !snoozed;
+
     let id_str = format!("{:>3}", task.task_number);
     let glyph = get_status_glyph(task, is_overdue);
     let title = &task.title;
+    let glyph_width = glyph.chars().count();
 
-    let styled_title = if task.completed_at.is_some() {
-        title.dimmed()
-    } else {
-        title.white()
+    let style_title = |text: &str| {
+        if task.completed_at.is_some() {
+            text.dimmed()
+        } else {
+            text.white()
+        }
     };
 
     let styled_glyph = if task.completed_at.is_some() {
@@ -80,53 +344,244 @@ fn render_task_line_with_options(
         glyph.white()
     };
 
-    let context = get_task_context(task, store);
+    let context = styled_task_context(task, store);
 
-    let left_section = format!(
-        " {}  {}  {}",
-        id_str.italic().dimmed(),
-        styled_glyph,
-        styled_title
-    );
+    // Markers after the title are built twice: styled for printing, plain for measuring
+    let styled_prefix = format!(" {}  {}  ", id_str.italic().dimmed(), styled_glyph);
+    let mut suffix_plain = String::new();
+    let mut styled_suffix = String::new();
+
+    if is_overdue && let Some(days) = days_overdue(task) {
+        let late = format!("({}d late)", days);
+        suffix_plain.push_str(&format!(" {}", late));
+        styled_suffix.push_str(&format!(" {}", alert(&late)));
+    }
+
+    if let Some(days) = days_stale(task) {
+        let age = format!("·{}d", days);
+        suffix_plain.push_str(&format!(" {}", age));
+        styled_suffix.push_str(&format!(" {}", age.dimmed()));
+    }
 
-    let styled_left = left_section;
+    let notes_preview = task
+        .notes
+        .as_deref()
+        .and_then(|notes| notes.lines().map(str::trim).find(|l| !l.is_empty()));
+
+    if notes_preview.is_some() {
+        suffix_plain.push_str(" ¶");
+        styled_suffix.push_str(&format!(" {}", "¶".dimmed()));
+    }
+
+    if snoozed {
+        suffix_plain.push_str(" zz");
+        styled_suffix.push_str(&format!(" {}", "zz".dimmed()));
+    }
+
+    if let Some(estimate) = task.estimate
+        && task.completed_at.is_none()
+    {
+        let estimate = format!("~{}", format_estimate(estimate));
+        suffix_plain.push_str(&format!("  {}", estimate));
+        styled_suffix.push_str(&format!("  {}", estimate.dimmed()));
+    }
 
-    // Build right-aligned section with completion date and/or context
-    let right_section = if show_completion_date && task.completed_at.is_some() {
-        let completion_date = format_completion_date(task.completed_at.unwrap());
-        if let Some(ctx) = context {
-            format!("{}  ·  {}", completion_date, ctx)
+    if let Some(deadline) = task.deadline
+        && task.completed_at.is_none()
+    {
+        let days_left = days_until(deadline);
+        let countdown = format!("⚑ {}", format_days_until(days_left));
+        suffix_plain.push_str(&format!("  {}", countdown));
+        if days_left <= 1 && !snoozed {
+            styled_suffix.push_str(&format!("  {}", alert(&countdown)));
         } else {
-            completion_date
+            styled_suffix.push_str(&format!("  {}", countdown.dimmed()));
+        }
+    }
+
+    if let Some(tags) = format_tags(&task.tags) {
+        suffix_plain.push_str(&format!("  {}", tags));
+        styled_suffix.push_str(&format!("  {}", tags.blue()));
+    }
+
+    // Build right-aligned section with completion date and/or context, plain for measuring
+    let (mut right_section, styled_right) =
+        match (task.completed_at.filter(|_| show_completion_date), context) {
+            (Some(completed_at), Some((plain, styled))) => {
+                let completion_date = format_completion_date(completed_at);
+                (
+                    format!("{}  ·  {}", completion_date, plain),
+                    format!("{}  ·  {}", completion_date.dimmed(), styled),
+                )
+            }
+            (Some(completed_at), None) => {
+                let completion_date = format_completion_date(completed_at);
+                let styled = completion_date.dimmed().to_string();
+                (completion_date, styled)
+            }
+            (None, Some((plain, styled))) => (plain, styled),
+            (None, None) => (String::new(), String::new()),
+        };
+
+    if !is_interactive() {
+        // Plain output: full titles, no terminal-dependent padding
+        let styled_left = format!("{}{}{}", styled_prefix, style_title(title), styled_suffix);
+        if right_section.is_empty() {
+            println!("{}", styled_left);
+        } else {
+            println!("{}  ·  {}", styled_left, styled_right);
         }
-    } else if let Some(ctx) = context {
-        ctx
     } else {
-        String::new()
-    };
+        let prefix_width = id_str.chars().count() + glyph_width + 5;
+        let suffix_width = suffix_plain.chars().count();
 
-    if !right_section.is_empty() {
-        let right_dimmed = right_section.dimmed();
+        let title_lines = if FULL_TITLES.load(Ordering::Relaxed) {
+            wrap_text(title, terminal_width.saturating_sub(prefix_width + 1))
+        } else {
+            // Shorten the title before giving up the right-aligned context
+            let fixed_width = prefix_width + suffix_width;
+            let with_right =
+                terminal_width.saturating_sub(fixed_width + right_section.chars().count() + 5);
+            let budget = if right_section.is_empty()
+                || with_right >= MIN_TITLE_WIDTH
+                || with_right >= title.chars().count()
+            {
+                with_right.max(MIN_TITLE_WIDTH)
+            } else {
+                right_section.clear();
+                terminal_width.saturating_sub(fixed_width + 1)
+            };
+            vec![truncate_with_ellipsis(title, budget.max(MIN_TITLE_WIDTH))]
+        };
+
+        // Continuation lines are indented under the title; markers and context follow the last one
+        let (last_line, first_lines) = title_lines.split_last().expect("at least one line");
+        for (index, line) in first_lines.iter().enumerate() {
+            if index == 0 {
+                println!("{}{}", styled_prefix, style_title(line));
+            } else {
+                println!("{}{}", " ".repeat(prefix_width), style_title(line));
+            }
+        }
 
-        let left_visible_len = format!("  {}  {}  {}", id_str, " ", title).len();
-        let right_visible_len = if show_completion_date && task.completed_at.is_some() {
-            // Account for the visible length without ANSI codes
-            right_section.chars().count()
+        let styled_left = if first_lines.is_empty() {
+            format!(
+                "{}{}{}",
+                styled_prefix,
+                style_title(last_line),
+                styled_suffix
+            )
         } else {
-            right_section.len()
+            format!(
+                "{}{}{}",
+                " ".repeat(prefix_width),
+                style_title(last_line),
+                styled_suffix
+            )
         };
 
-        let total_content = left_visible_len + right_visible_len;
+        // Account for the visible length without ANSI codes
+        let total_content =
+            prefix_width + last_line.chars().count() + suffix_width + right_section.chars().count();
 
-        if total_content + 4 < terminal_width {
+        if !right_section.is_empty() && total_content + 4 < terminal_width {
             let padding = terminal_width - total_content - 2;
-            println!("{}{}{}", styled_left, " ".repeat(padding), right_dimmed);
+            println!("{}{}{}", styled_left, " ".repeat(padding), styled_right);
         } else {
-            // Not enough space for right alignment, just print normally
+            // No context, or not enough space for right alignment
             println!("{}", styled_left);
         }
+    }
+
+    if SHOW_NOTES.load(Ordering::Relaxed)
+        && let Some(preview) = notes_preview
+    {
+        // Indent the preview under the title
+        println!("         {}", preview.dimmed().italic());
+    }
+}
+
+/// Number of days from today until a date (negative if it is in the past)
+pub fn days_until(date: Date) -> i64 {
+    let today = jiff::Zoned::now().date();
+    (date - today).get_days() as i64
+}
+
+/// Format a day offset as a short countdown (e.g., "in 3d", "today", "2d ago")
+pub fn format_days_until(days: i64) -> String {
+    match days {
+        0 => "today".to_string(),
+        d if d > 0 => format!("in {}d", d),
+        d => format!("{}d ago", -d),
+    }
+}
+
+/// Format a time estimate (e.g., "45m", "1h 30m")
+pub fn format_estimate(estimate: jiff::SignedDuration) -> String {
+    format!("{:#}", estimate)
+}
+
+/// Format tracked time to the minute (e.g., "0m", "2h 5m")
+pub fn format_tracked_time(tracked: jiff::SignedDuration) -> String {
+    let minutes = tracked.as_mins();
+    if minutes < 1 {
+        "0m".to_string()
+    } else {
+        format_estimate(jiff::SignedDuration::from_mins(minutes))
+    }
+}
+
+/// Format the open projects a project waits on (e.g., "⧗ after Website, Launch")
+pub fn format_waiting_on(blockers: &[&Project]) -> Option<ColoredString> {
+    if blockers.is_empty() {
+        return None;
+    }
+    let names: Vec<_> = blockers.iter().map(|p| p.name.as_str()).collect();
+    Some(format!("⧗ after {}", names.join(", ")).yellow())
+}
+
+/// Format a container's nearest deadline (e.g., "next due: Jun 12 (in 3d)")
+pub fn format_next_due(date: jiff::civil::Date) -> ColoredString {
+    let days = days_until(date);
+    let label = format!(
+        "next due: {} ({})",
+        date.strftime("%b %d"),
+        format_days_until(days)
+    );
+
+    if days <= 1 {
+        alert(&label)
     } else {
-        println!("{}", styled_left);
+        label.dimmed()
+    }
+}
+
+/// Lines of a container's notes shown at the top of its view
+const NOTES_PREVIEW_LINES: usize = 3;
+
+/// The first lines of some notes, leading and trailing blank lines dropped,
+/// and whether there is more after them
+pub fn notes_preview(notes: &str) -> (Vec<&str>, bool) {
+    let mut lines: Vec<&str> = notes
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|l| l.is_empty())
+        .collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    let more = lines.len() > NOTES_PREVIEW_LINES;
+    (lines.into_iter().take(NOTES_PREVIEW_LINES).collect(), more)
+}
+
+/// Print the notes preview under a view header, dimmed
+pub fn render_notes_preview(notes: &str) {
+    let (lines, more) = notes_preview(notes);
+    for line in &lines {
+        println!("  {}", line.dimmed());
+    }
+    if more {
+        println!("  {}", "…".dimmed());
     }
 }
 
@@ -146,6 +601,214 @@ fn format_completion_date(timestamp: jiff::Timestamp) -> String {
     }
 }
 
+/// Render every field of a single task (used by `tdo show`)
+pub fn render_task_detail(task: &Task, store: &Store) {
+    let glyph = get_status_glyph(task, is_overdue(task));
+    println!(
+        "\n  {}  {}  {}\n",
+        format!("#{}", task.task_number).italic().dimmed(),
+        glyph,
+        task.title.bold()
+    );
+
+    let when = match task.when {
+        When::Inbox => "Inbox".to_string(),
+        When::Today { evening: false } => "Today".to_string(),
+        When::Today { evening: true } => "This evening".to_string(),
+        When::Someday => "Someday".to_string(),
+        When::Anytime => "Anytime".to_string(),
+        When::Scheduled { date, evening } => format!(
+            "{}{} ({})",
+            date.strftime("%a, %b %d %Y"),
+            if evening { ", evening" } else { "" },
+            format_days_until(days_until(date))
+        ),
+    };
+    render_detail_row("When", &when);
+
+    if let Some(deadline) = task.deadline {
+        render_detail_row(
+            "Deadline",
+            &format!(
+                "{} ({})",
+                deadline.strftime("%a, %b %d %Y"),
+                format_days_until(days_until(deadline))
+            ),
+        );
+    }
+
+    if let Some(reminder) = &task.reminder {
+        render_detail_row("Reminder", &format_timestamp(reminder.timestamp()));
+    }
+
+    if let Some(estimate) = task.estimate {
+        render_detail_row("Estimate", &format_estimate(estimate));
+    }
+
+    if !task.time_entries.is_empty() {
+        let tracked = format_tracked_time(task.tracked_time());
+        match task.running_timer() {
+            Some(entry) => render_detail_row(
+                "Tracked",
+                &format!(
+                    "{} (running since {})",
+                    tracked,
+                    format_timestamp(entry.started_at)
+                ),
+            ),
+            None => render_detail_row("Tracked", &tracked),
+        }
+    }
+
+    if let Some(defer_until) = task.defer_until {
+        render_detail_row(
+            "Deferred",
+            &format!("until {}", defer_until.strftime("%b %d %Y")),
+        );
+    }
+
+    if let Some(snoozed_until) = task.snoozed_until
+        && task.is_snoozed()
+    {
+        render_detail_row(
+            "Snoozed",
+            &format!("until {}", format_timestamp(snoozed_until)),
+        );
+    }
+
+    if let Some(hierarchy) = get_task_context(task, store) {
+        let label = if task.project_id.is_some() {
+            "Project"
+        } else {
+            "Area"
+        };
+        render_detail_row(label, &hierarchy);
+    }
+
+    if !task.tags.is_empty() {
+        let tags: Vec<_> = task.tags.iter().map(|t| format!("#{}", t)).collect();
+        render_detail_row("Tags", &tags.join(" "));
+    }
+
+    if let Some(context) = &task.context {
+        render_detail_row("Context", &format!("@{}", context));
+    }
+
+    if let Some(energy) = task.energy
+        && let Some(value) = energy.to_possible_value()
+    {
+        render_detail_row("Energy", value.get_name());
+    }
+
+    render_detail_row("Created", &format_timestamp(task.created_at));
+
+    if let Some(completed_at) = task.completed_at {
+        render_detail_row("Completed", &format_timestamp(completed_at));
+    }
+
+    if let Some(deleted_at) = task.deleted_at {
+        render_detail_row("Deleted", &format_timestamp(deleted_at));
+    }
+
+    if let Some(notes) = task.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        render_section_header("Notes");
+        for line in notes.lines() {
+            println!("    {}", line);
+        }
+    }
+
+    if !task.checklist.is_empty() {
+        let done = task.checklist.iter().filter(|i| i.completed).count();
+        render_section_header(&format!("Checklist — {}/{}", done, task.checklist.len()));
+        for item in &task.checklist {
+            if item.completed {
+                println!("    {}  {}", success("✓"), item.title.dimmed());
+            } else {
+                println!("    ○  {}", item.title);
+            }
+        }
+    }
+
+    if !task.attachments.is_empty() {
+        render_section_header("Attachments");
+        for (index, attachment) in task.attachments.iter().enumerate() {
+            let glyph = if attachment.is_url() { "↗" } else { "▤" };
+            match &attachment.label {
+                Some(label) => println!(
+                    "    {}. {} {}  {}",
+                    index + 1,
+                    glyph,
+                    label,
+                    attachment.target.dimmed()
+                ),
+                None => println!("    {}. {} {}", index + 1, glyph, attachment.target),
+            }
+        }
+    }
+
+    if !task.history.is_empty() {
+        render_section_header("History");
+        for event in &task.history {
+            println!(
+                "    {}  {}",
+                format_timestamp(event.at).dimmed(),
+                describe_change(&event.change)
+            );
+        }
+    }
+
+    println!();
+}
+
+/// One-line description of a history entry (e.g., "Rescheduled: Inbox → Today")
+fn describe_change(change: &TaskChange) -> String {
+    match change {
+        TaskChange::Created => "Created".to_string(),
+        TaskChange::Rescheduled { from, to } => {
+            format!(
+                "Rescheduled: {} → {}",
+                describe_when(from),
+                describe_when(to)
+            )
+        }
+        TaskChange::Edited { fields } => format!("Edited: {}", fields.join(", ")),
+        TaskChange::Completed => "Completed".to_string(),
+        TaskChange::Reopened => "Reopened".to_string(),
+        TaskChange::Deleted => "Deleted".to_string(),
+        TaskChange::Restored => "Restored".to_string(),
+    }
+}
+
+/// Name of a list or date, as shown in a task's history
+pub fn describe_when(when: &When) -> String {
+    match when {
+        When::Inbox => "Inbox".to_string(),
+        When::Today { evening: false } => "Today".to_string(),
+        When::Today { evening: true } => "This evening".to_string(),
+        When::Someday => "Someday".to_string(),
+        When::Anytime => "Anytime".to_string(),
+        When::Scheduled {
+            date,
+            evening: false,
+        } => date.strftime("%b %d %Y").to_string(),
+        When::Scheduled {
+            date,
+            evening: true,
+        } => format!("{} (evening)", date.strftime("%b %d %Y")),
+    }
+}
+
+fn render_detail_row(label: &str, value: &str) {
+    println!("  {}  {}", format!("{:<10}", label).dimmed(), value);
+}
+
+/// Format a timestamp in the local timezone, e.g. "Fri, Oct 16 2026 09:30"
+pub fn format_timestamp(timestamp: jiff::Timestamp) -> String {
+    jiff::Zoned::new(timestamp, jiff::tz::TimeZone::system())
+        .strftime("%a, %b %d %Y %H:%M")
+        .to_string()
+}
+
 /// Render a view header with title and count
 pub fn render_view_header(title: &str, count: usize) {
     let task_word = if count == 1 { "task" } else { "tasks" };
@@ -157,28 +820,116 @@ pub fn render_view_header(title: &str, count: usize) {
     );
 }
 
+/// Projects or tags named in the view footer before the rest are summed up as "+N"
+const FOOTER_TOP_COUNT: usize = 3;
+
+/// Render a one-line summary under a view: how the displayed tasks spread over
+/// projects and tags, and the nearest deadline among them
+pub fn render_view_footer(tasks: &[&Task], store: &Store) {
+    let mut parts = Vec::new();
+
+    let projects = tasks
+        .iter()
+        .filter_map(|t| t.project_id)
+        .filter_map(|id| store.get_project(id))
+        .map(|p| p.name.clone());
+    if let Some(projects) = format_top_counts(projects) {
+        parts.push(projects.dimmed());
+    }
+
+    let tags = tasks
+        .iter()
+        .flat_map(|t| t.tags.iter().map(|tag| format!("#{}", tag)));
+    if let Some(tags) = format_top_counts(tags) {
+        parts.push(tags.blue());
+    }
+
+    let nearest_deadline = tasks
+        .iter()
+        .filter(|t| t.completed_at.is_none())
+        .filter_map(|t| t.deadline)
+        .min();
+    if let Some(deadline) = nearest_deadline {
+        parts.push(format_next_due(deadline));
+    }
+
+    if parts.is_empty() {
+        return;
+    }
+
+    let separator = format!("  {}  ", "·".dimmed());
+    let parts: Vec<_> = parts.iter().map(ToString::to_string).collect();
+    println!("\n  {}", parts.join(&separator));
+}
+
+/// Count occurrences of each name, most common first (e.g., "Website 3, Errands 2, +1")
+fn format_top_counts(names: impl Iterator<Item = String>) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for name in names {
+        *counts.entry(name).or_default() += 1;
+    }
+
+    if counts.is_empty() {
+        return None;
+    }
+
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut shown: Vec<_> = counts
+        .iter()
+        .take(FOOTER_TOP_COUNT)
+        .map(|(name, count)| format!("{} {}", name, count))
+        .collect();
+    if counts.len() > FOOTER_TOP_COUNT {
+        shown.push(format!("+{}", counts.len() - FOOTER_TOP_COUNT));
+    }
+
+    Some(shown.join(", "))
+}
+
 /// Render a section header (e.g., "Evening", "Tomorrow")
 pub fn render_section_header(title: &str) {
     println!("\n  ─── {} ───\n", title.bold());
 }
 
-/// Render a section separator
-pub fn render_section_separator() {
-    println!();
-}
-
 /// Check if a task is overdue
 pub fn is_overdue(task: &Task) -> bool {
+    days_overdue(task).is_some()
+}
+
+/// How many days have passed since an overdue task was scheduled, or `None`
+/// for tasks that aren't overdue
+pub fn days_overdue(task: &Task) -> Option<i64> {
     if task.completed_at.is_some() || task.deleted_at.is_some() {
-        return false;
+        return None;
     }
 
-    if let crate::models::task::When::Scheduled { date } = task.when {
-        let today = jiff::Zoned::now().date();
-        return date < today;
+    match task.when {
+        crate::models::task::When::Scheduled { date, .. } => {
+            Some(-days_until(date)).filter(|days| *days > 0)
+        }
+        _ => None,
+    }
+}
+
+/// How many days an open Inbox or Anytime task has gone without changes, or
+/// `None` while that's under the `stale_after_days` threshold
+pub fn days_stale(task: &Task) -> Option<i64> {
+    let threshold = i64::from(STALE_AFTER_DAYS.load(Ordering::Relaxed));
+    if threshold == 0
+        || task.completed_at.is_some()
+        || !matches!(task.when, When::Inbox | When::Anytime)
+    {
+        return None;
     }
 
-    false
+    let last_touched = task
+        .history
+        .iter()
+        .map(|event| event.at)
+        .fold(task.created_at, jiff::Timestamp::max);
+    Some(-days_until(get_local_date(last_touched))).filter(|days| *days >= threshold)
 }
 
 /// Check if a timestamp is within the last N days
@@ -207,15 +958,126 @@ pub fn format_date_header(date: Date) -> String {
     }
 }
 
-/// Extract year and month from a timestamp for grouping purposes
-pub fn get_year_month(timestamp: jiff::Timestamp) -> (i16, i8) {
-    let zoned = jiff::Zoned::new(timestamp, jiff::tz::TimeZone::system());
-    let date = zoned.date();
-    (date.year(), date.month())
+/// Convert a timestamp to a calendar date in the system timezone
+pub fn get_local_date(timestamp: jiff::Timestamp) -> Date {
+    jiff::Zoned::new(timestamp, jiff::tz::TimeZone::system()).date()
 }
 
-/// Format a timestamp as a month header (e.g., "February 2026")
-pub fn format_month_header(timestamp: jiff::Timestamp) -> String {
-    let zoned = jiff::Zoned::new(timestamp, jiff::tz::TimeZone::system());
-    zoned.strftime("%B %Y").to_string()
+/// Format a past date as a human-readable header (e.g., "Today", "Yesterday", "Monday, Feb 17")
+pub fn format_past_date_header(date: Date) -> String {
+    let today = jiff::Zoned::now().date();
+
+    if date == today {
+        "Today".to_string()
+    } else if date == today.yesterday().expect("yesterday should be valid") {
+        "Yesterday".to_string()
+    } else {
+        date.strftime("%A, %b %d").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_color() {
+        assert_eq!(normalize_color("Blue").as_deref(), Some("blue"));
+        assert_eq!(
+            normalize_color(" bright_magenta ").as_deref(),
+            Some("bright magenta")
+        );
+        assert_eq!(normalize_color("teal"), None);
+    }
+
+    #[test]
+    fn test_notes_preview() {
+        assert_eq!(
+            notes_preview("\n  Goal: ship v2\n"),
+            (vec!["  Goal: ship v2"], false)
+        );
+        assert_eq!(
+            notes_preview("one\ntwo\n\nfour"),
+            (vec!["one", "two", ""], true)
+        );
+        assert_eq!(
+            notes_preview("one\ntwo\nthree"),
+            (vec!["one", "two", "three"], false)
+        );
+    }
+
+    #[test]
+    fn test_truncate_with_ellipsis() {
+        assert_eq!(truncate_with_ellipsis("Short", 10), "Short");
+        assert_eq!(truncate_with_ellipsis("Exactly ten", 11), "Exactly ten");
+        assert_eq!(truncate_with_ellipsis("Write the report", 8), "Write t…");
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(
+            wrap_text("Write the quarterly report for the board", 16),
+            ["Write the", "quarterly report", "for the board"]
+        );
+        assert_eq!(
+            wrap_text("abcdefghij klm", 4),
+            ["abcd", "efgh", "ij", "klm"]
+        );
+        assert_eq!(wrap_text("", 10), [""]);
+    }
+
+    #[test]
+    fn test_days_overdue() {
+        let today = jiff::Zoned::now().date();
+        let scheduled = |days: i64| Task {
+            when: crate::models::task::When::Scheduled {
+                date: today.checked_add(jiff::Span::new().days(days)).unwrap(),
+                evening: false,
+            },
+            ..Task::default()
+        };
+
+        assert_eq!(days_overdue(&scheduled(-5)), Some(5));
+        assert_eq!(days_overdue(&scheduled(0)), None);
+        assert_eq!(days_overdue(&scheduled(2)), None);
+
+        let done = Task {
+            completed_at: Some(jiff::Timestamp::now()),
+            ..scheduled(-5)
+        };
+        assert_eq!(days_overdue(&done), None);
+    }
+
+    #[test]
+    fn test_days_stale() {
+        let days_ago = |days: i64| {
+            jiff::Timestamp::now()
+                .checked_sub(jiff::SignedDuration::from_hours(days * 24))
+                .unwrap()
+        };
+        let mut task = Task {
+            when: When::Anytime,
+            created_at: days_ago(20),
+            ..Task::default()
+        };
+
+        set_stale_after_days(Some(14));
+        assert_eq!(days_stale(&task), Some(20));
+
+        // Any change restarts the count
+        task.history.push(crate::models::task::TaskEvent {
+            at: days_ago(3),
+            change: TaskChange::Edited {
+                fields: vec!["title".to_string()],
+            },
+        });
+        assert_eq!(days_stale(&task), None);
+
+        let someday = Task {
+            when: When::Someday,
+            created_at: days_ago(20),
+            ..Task::default()
+        };
+        assert_eq!(days_stale(&someday), None);
+    }
 }
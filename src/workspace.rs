@@ -0,0 +1,210 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::models::{store::Store, task::Task};
+
+/// A named slice of the store picked with `--workspace`, defined in the config.
+/// A task belongs to it when it sits in one of the areas (directly or through
+/// its project) or carries one of the tags.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Workspace {
+    /// Area names; new tasks without a project or area go to the first one
+    pub areas: Vec<String>,
+    /// Tags without the leading #; new tasks get all of them
+    pub tags: Vec<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum WorkspaceError {
+    #[error("Area '{0}' of the workspace doesn't exist")]
+    AreaNotFound(String),
+}
+
+impl Workspace {
+    /// Tags normalized the way tasks store them
+    fn tag_names(&self) -> Vec<&str> {
+        self.tags
+            .iter()
+            .map(|tag| tag.trim_start_matches('#'))
+            .collect()
+    }
+
+    /// Area given to new tasks that are filed nowhere else
+    pub fn default_area(&self) -> Option<&str> {
+        self.areas.first().map(String::as_str)
+    }
+
+    /// Add the workspace tags a new task doesn't have yet
+    pub fn add_default_tags(&self, tags: &mut Vec<String>) {
+        for tag in self.tag_names() {
+            if !tags
+                .iter()
+                .any(|t| t.trim_start_matches('#').eq_ignore_ascii_case(tag))
+            {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+
+    /// A copy of the store holding only what belongs to the workspace, for
+    /// read-only views. Projects and areas are kept when they are part of the
+    /// workspace or hold one of its tasks. Never save the result.
+    pub fn scope(&self, store: &Store) -> Result<Store, WorkspaceError> {
+        let area_ids: HashSet<Uuid> = self
+            .areas
+            .iter()
+            .map(|name| {
+                store
+                    .areas
+                    .values()
+                    .find(|a| a.deleted_at.is_none() && a.name.eq_ignore_ascii_case(name))
+                    .map(|a| a.id)
+                    .ok_or_else(|| WorkspaceError::AreaNotFound(name.clone()))
+            })
+            .collect::<Result<_, _>>()?;
+        let tags = self.tag_names();
+        let has_tag = |task_tags: &[String]| {
+            task_tags
+                .iter()
+                .any(|t| tags.iter().any(|tag| t.eq_ignore_ascii_case(tag)))
+        };
+
+        let effective_area = |task: &Task| {
+            task.area_id.or_else(|| {
+                task.project_id
+                    .and_then(|id| store.get_project(id))
+                    .and_then(|p| p.area_id)
+            })
+        };
+
+        let mut scoped = Store {
+            version: store.version,
+            next_task_number: store.next_task_number,
+            ..Store::default()
+        };
+
+        for task in store.tasks.values() {
+            if effective_area(task).is_some_and(|id| area_ids.contains(&id)) || has_tag(&task.tags)
+            {
+                scoped.tasks.insert(task.id, task.clone());
+            }
+        }
+
+        let project_ids: HashSet<Uuid> =
+            scoped.tasks.values().filter_map(|t| t.project_id).collect();
+        for project in store.projects.values() {
+            if project_ids.contains(&project.id)
+                || project.area_id.is_some_and(|id| area_ids.contains(&id))
+                || has_tag(&project.tags)
+            {
+                scoped.projects.insert(project.id, project.clone());
+            }
+        }
+
+        let kept_area_ids: HashSet<Uuid> = scoped
+            .tasks
+            .values()
+            .filter_map(|t| t.area_id)
+            .chain(scoped.projects.values().filter_map(|p| p.area_id))
+            .chain(area_ids)
+            .collect();
+        for area in store.areas.values() {
+            if kept_area_ids.contains(&area.id) {
+                scoped.areas.insert(area.id, area.clone());
+            }
+        }
+
+        Ok(scoped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{area::Area, project::Project};
+
+    #[test]
+    fn test_scope_keeps_workspace_areas_and_tags() {
+        let mut store = Store::default();
+        let work = Area {
+            id: Uuid::new_v4(),
+            name: "Work".to_string(),
+            ..Area::default()
+        };
+        let home = Area {
+            id: Uuid::new_v4(),
+            name: "Home".to_string(),
+            ..Area::default()
+        };
+        let launch = Project {
+            id: Uuid::new_v4(),
+            name: "Launch".to_string(),
+            area_id: Some(work.id),
+            ..Project::default()
+        };
+        let (work_id, home_id, launch_id) = (work.id, home.id, launch.id);
+        store.add_area(work);
+        store.add_area(home);
+        store.add_project(launch);
+
+        for (title, project_id, area_id, tags) in [
+            ("In project", Some(launch_id), None, vec![]),
+            ("In area", None, Some(work_id), vec![]),
+            (
+                "Tagged at home",
+                None,
+                Some(home_id),
+                vec!["Work".to_string()],
+            ),
+            ("Home only", None, Some(home_id), vec![]),
+            ("Loose", None, None, vec![]),
+        ] {
+            store.add_task(Task {
+                id: Uuid::new_v4(),
+                title: title.to_string(),
+                project_id,
+                area_id,
+                tags,
+                ..Task::default()
+            });
+        }
+
+        let workspace = Workspace {
+            areas: vec!["work".to_string()],
+            tags: vec!["#work".to_string()],
+        };
+        let scoped = workspace.scope(&store).unwrap();
+
+        let mut titles: Vec<_> = scoped.tasks.values().map(|t| t.title.as_str()).collect();
+        titles.sort();
+        assert_eq!(titles, ["In area", "In project", "Tagged at home"]);
+        assert_eq!(scoped.projects.len(), 1);
+        // Home is kept for the tagged task's context
+        assert_eq!(scoped.areas.len(), 2);
+        assert_eq!(scoped.next_task_number, store.next_task_number);
+
+        let missing = Workspace {
+            areas: vec!["Gym".to_string()],
+            tags: vec![],
+        };
+        assert!(matches!(
+            missing.scope(&store),
+            Err(WorkspaceError::AreaNotFound(name)) if name == "Gym"
+        ));
+    }
+
+    #[test]
+    fn test_add_default_tags_skips_existing() {
+        let workspace = Workspace {
+            areas: vec![],
+            tags: vec!["work".to_string(), "#office".to_string()],
+        };
+        let mut tags = vec!["Work".to_string()];
+        workspace.add_default_tags(&mut tags);
+        assert_eq!(tags, ["Work", "office"]);
+    }
+}